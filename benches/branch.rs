@@ -1,4 +1,11 @@
-//! Branch primitive benchmarks: create, switch, delete
+//! Branch primitive benchmarks: create, switch, delete, command branch resolution,
+//! transaction commit on a named branch, new_handle
+//!
+//! new_handle measures `Strata::new_handle()`, the per-thread setup cost in
+//! `run_scaling_experiment`, with 0 to 1000 other live handles and, separately,
+//! 0 to 1000 existing branches. The Criterion pass includes dropping the
+//! handle; the percentile pass times creation alone. Compare the p50 with the
+//! per-op latency of a short scaling burst to see how much setup skews it.
 //!
 //! All benchmarks report latency percentiles.
 
@@ -7,13 +14,16 @@
 mod harness;
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use harness::scaling::fmt_duration;
 use harness::{
-    counter_delta, create_db, measure_with_counters, report_counters, report_percentiles,
-    snapshot_counters, DurabilityConfig, PERCENTILE_SAMPLES,
+    counter_delta, create_db, kv_key, kv_value, measure_percentiles, measure_with_counters,
+    percentile_samples, percentiles_from_timings, report_counters, report_percentiles,
+    snapshot_counters, DurabilityConfig,
 };
-use harness::measure_percentiles;
+use stratadb::Command;
 
 fn branch_create(c: &mut Criterion) {
     let mut group = c.benchmark_group("branch/create");
@@ -35,7 +45,7 @@ fn branch_create(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(u64::MAX / 2);
         let label = format!("branch/create/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed);
             bench_db
                 .db
@@ -43,7 +53,7 @@ fn branch_create(c: &mut Criterion) {
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
@@ -76,7 +86,7 @@ fn branch_switch(c: &mut Criterion) {
         let label = format!("branch/switch/{}", mode.label());
         // Manual counter snapshot because set_branch takes &mut self
         let before = snapshot_counters(&bench_db);
-        let p = measure_percentiles(PERCENTILE_SAMPLES, || {
+        let p = measure_percentiles(percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed) % 100;
             bench_db
                 .db
@@ -86,7 +96,7 @@ fn branch_switch(c: &mut Criterion) {
         let after = snapshot_counters(&bench_db);
         let counters = counter_delta(&before, &after);
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
@@ -110,17 +120,240 @@ fn branch_delete(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(u64::MAX / 2);
         let label = format!("branch/delete/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed);
             let name = format!("del_branch_{}", i);
             bench_db.db.create_branch(&name).unwrap();
             bench_db.db.delete_branch(&name).unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
+    }
+    group.finish();
+}
+
+/// `branch: None` in a command — resolved to the session's current branch.
+const BRANCH_IMPLICIT: &str = "implicit";
+/// `branch: Some(name)` naming the current branch explicitly.
+const BRANCH_EXPLICIT: &str = "explicit";
+
+/// `to - from` as a signed, human-readable duration.
+fn fmt_difference(from: Duration, to: Duration) -> String {
+    if to >= from {
+        format!("+{}", fmt_duration(to - from))
+    } else {
+        format!("-{}", fmt_duration(from - to))
+    }
+}
+
+/// Session `KvPut` with `branch: None` versus `branch: Some(current)`.
+///
+/// Both insert fresh keys into the same branch; any difference is the cost
+/// of resolving an explicit branch name in the command layer.
+fn branch_resolution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("branch/resolution");
+    group.throughput(Throughput::Elements(1));
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: branch/resolution (Session KvPut) ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let current = bench_db.db.current_branch().to_string();
+        let mut session = bench_db.db.session();
+        // Shared by both variants and passes, so every put inserts a fresh
+        // key rather than one variant overwriting the other's
+        let counter = AtomicU64::new(0);
+
+        let mut p50s = [Duration::ZERO; 2];
+        for (v, variant) in [BRANCH_IMPLICIT, BRANCH_EXPLICIT].into_iter().enumerate() {
+            let mut put = |i: u64| {
+                let branch = if variant == BRANCH_EXPLICIT {
+                    Some(current.as_str().into())
+                } else {
+                    None
+                };
+                session
+                    .execute(Command::KvPut {
+                        branch,
+                        key: kv_key(i),
+                        value: kv_value(),
+                    })
+                    .unwrap();
+            };
+
+            let id = format!("{}/{}", variant, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| put(counter.fetch_add(1, Ordering::Relaxed)));
+            });
+
+            let label = format!("branch/resolution/{}/{}", variant, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                put(counter.fetch_add(1, Ordering::Relaxed));
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+            p50s[v] = p.p50;
+        }
+        rows.push((mode, p50s[0], p50s[1]));
     }
     group.finish();
+
+    eprintln!("\n--- Session KvPut p50: branch None vs Some(current) ---");
+    eprintln!(
+        "  {:<10} {:>12} {:>12} {:>12}",
+        "mode", BRANCH_IMPLICIT, BRANCH_EXPLICIT, "difference"
+    );
+    for (mode, implicit, explicit) in rows {
+        eprintln!(
+            "  {:<10} {:>12} {:>12} {:>12}",
+            mode.label(),
+            fmt_duration(implicit),
+            fmt_duration(explicit),
+            fmt_difference(implicit, explicit),
+        );
+    }
+}
+
+/// Named branch used by `branch_txn_commit`.
+const TXN_BRANCH: &str = "bench_txn_branch";
+
+/// Session transaction (`TxnBegin` + `KvPut` + `TxnCommit`) begun on the
+/// default branch versus a named branch.
+fn branch_txn_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("branch/txn_commit");
+    group.throughput(Throughput::Elements(1));
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: branch/txn_commit (begin + put + commit) ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        bench_db.db.create_branch(TXN_BRANCH).unwrap();
+        let mut session = bench_db.db.session();
+
+        let mut p50s = [Duration::ZERO; 2];
+        for (v, target) in ["default", "named"].into_iter().enumerate() {
+            let mut commit = |i: u64| {
+                let branch = if target == "named" {
+                    Some(TXN_BRANCH.into())
+                } else {
+                    None
+                };
+                session
+                    .execute(Command::TxnBegin {
+                        branch,
+                        options: None,
+                    })
+                    .unwrap();
+                session
+                    .execute(Command::KvPut {
+                        branch: None,
+                        key: kv_key(i),
+                        value: kv_value(),
+                    })
+                    .unwrap();
+                session.execute(Command::TxnCommit).unwrap();
+            };
+
+            let counter = AtomicU64::new(0);
+            let id = format!("{}/{}", target, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| commit(counter.fetch_add(1, Ordering::Relaxed)));
+            });
+
+            let pct_counter = AtomicU64::new(u64::MAX / 2);
+            let label = format!("branch/txn_commit/{}/{}", target, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                commit(pct_counter.fetch_add(1, Ordering::Relaxed));
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+            p50s[v] = p.p50;
+        }
+        rows.push((mode, p50s[0], p50s[1]));
+    }
+    group.finish();
+
+    eprintln!("\n--- Session txn commit p50: default vs named branch ---");
+    eprintln!("  {:<10} {:>12} {:>12} {:>12}", "mode", "default", "named", "difference");
+    for (mode, default, named) in rows {
+        eprintln!(
+            "  {:<10} {:>12} {:>12} {:>12}",
+            mode.label(),
+            fmt_duration(default),
+            fmt_duration(named),
+            fmt_difference(default, named),
+        );
+    }
+}
+
+/// Live handles or existing branches present while timing `new_handle()`.
+const HANDLE_SCALE: [usize; 4] = [0, 10, 100, 1000];
+
+/// `new_handle()` latency as other handles, then branches, accumulate.
+fn branch_new_handle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("branch/new_handle");
+    group.throughput(Throughput::Elements(1));
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: branch/new_handle ---");
+    for mode in DurabilityConfig::ALL {
+        for axis in ["handles", "branches"] {
+            for count in HANDLE_SCALE {
+                let bench_db = create_db(mode);
+                let held: Vec<_> = if axis == "handles" {
+                    (0..count).map(|_| bench_db.db.new_handle().unwrap()).collect()
+                } else {
+                    for i in 0..count {
+                        bench_db.db.create_branch(&format!("handle_branch_{}", i)).unwrap();
+                    }
+                    Vec::new()
+                };
+
+                let id = format!("{}={}/{}", axis, count, mode.label());
+                group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                    b.iter(|| bench_db.db.new_handle().unwrap());
+                });
+
+                // Timed by hand so the handle's drop is not included
+                let timings = (0..percentile_samples())
+                    .map(|_| {
+                        let start = Instant::now();
+                        let handle = bench_db.db.new_handle().unwrap();
+                        let elapsed = start.elapsed();
+                        drop(handle);
+                        elapsed
+                    })
+                    .collect();
+                let p = percentiles_from_timings(timings);
+                report_percentiles(&format!("branch/new_handle/{}", id), &p);
+                rows.push((mode, axis, count, p.p50, p.p99));
+                drop(held);
+            }
+        }
+    }
+    group.finish();
+
+    eprintln!("\n--- new_handle() latency by live handles / existing branches ---");
+    eprintln!("  {:<10} {:<9} {:>7} {:>12} {:>12}", "mode", "axis", "count", "p50", "p99");
+    for (mode, axis, count, p50, p99) in rows {
+        eprintln!(
+            "  {:<10} {:<9} {:>7} {:>12} {:>12}",
+            mode.label(),
+            axis,
+            count,
+            fmt_duration(p50),
+            fmt_duration(p99),
+        );
+    }
 }
 
-criterion_group!(benches, branch_create, branch_switch, branch_delete);
-criterion_main!(benches);
+criterion_group!(
+    benches,
+    branch_create,
+    branch_switch,
+    branch_delete,
+    branch_resolution,
+    branch_txn_commit,
+    branch_new_handle
+);
+criterion_main!(benches, harness::baseline::check_baseline);
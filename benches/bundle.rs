@@ -0,0 +1,374 @@
+//! Bundle Export/Import Benchmark for StrataDB
+//!
+//! Populates a branch with a sweep of entry counts, spread round-robin over
+//! KV, State, Event and JSON, then times `branch_export` to a
+//! `.runbundle.tar.zst`, `branch_validate_bundle` on the result, and
+//! `branch_import` of that bundle into a fresh database. Reports bundle
+//! size, compression ratio (logical entry bytes / bundle bytes) and
+//! throughput; `µs/entry` staying flat across the sweep means export and
+//! import scale linearly.
+//!
+//! MB/s is logical entry data (keys + `approx_value_bytes` of the values)
+//! per second, so export, validation and import figures are directly
+//! comparable. The ~1M-entry memory check for validation lives in
+//! `tests/bundle_stress.rs`.
+//!
+//! Uses a custom harness (like fill_level.rs): each sample is one
+//! export/import of a freshly populated branch, with the entry count as the
+//! axis of interest.
+//!
+//! Run:    `cargo bench --bench bundle`
+//! Custom: `cargo bench --bench bundle -- --entries 1000,100000,500000`
+//! Random: `cargo bench --bench bundle -- --entropy random`
+//! CSV:    `cargo bench --bench bundle -- --csv`
+//! Help:   `cargo bench --bench bundle -- --help`
+
+#[allow(unused)]
+#[path = "harness/mod.rs"]
+mod harness;
+
+use harness::metrics::fmt_mb;
+use harness::scaling::{fmt_duration, fmt_num};
+use harness::{
+    approx_value_bytes, check_unknown_args, create_db, event_payload, json_document,
+    print_hardware_info, state_value, DurabilityConfig, ValueEntropy, ValueSize,
+};
+use std::time::{Duration, Instant};
+use stratadb::{Strata, Value};
+
+// ---------------------------------------------------------------------------
+// Parameters
+// ---------------------------------------------------------------------------
+
+const DEFAULT_ENTRIES: &[usize] = &[1_000, 10_000, 100_000];
+const BRANCH: &str = "bundle-bench";
+const EVENT_TYPE: &str = "bundle_bench";
+/// Distinct pre-generated KV values, so random-value generation stays out of
+/// the populate loop.
+const VALUE_POOL: usize = 16;
+
+// ---------------------------------------------------------------------------
+// Populate
+// ---------------------------------------------------------------------------
+
+/// Write `n` entries to the current branch, cycling KV, State, Event and
+/// JSON. Returns the logical bytes written (keys + values).
+fn populate(db: &Strata, n: usize, entropy: ValueEntropy) -> u64 {
+    let kv_pool: Vec<Value> = (0..VALUE_POOL).map(|_| entropy.value(ValueSize::Medium)).collect();
+    let mut bytes = 0;
+    for i in 0..n {
+        let key = format!("entry:{:012}", i);
+        let (key_len, value) = match i % 4 {
+            0 => {
+                let v = kv_pool[(i / 4) % VALUE_POOL].clone();
+                db.kv_put(&key, v.clone()).unwrap();
+                (key.len(), v)
+            }
+            1 => {
+                let v = state_value();
+                db.state_set(&key, v.clone()).unwrap();
+                (key.len(), v)
+            }
+            2 => {
+                let v = event_payload();
+                db.event_append(EVENT_TYPE, v.clone()).unwrap();
+                (EVENT_TYPE.len(), v)
+            }
+            _ => {
+                let v = json_document(i as u64);
+                db.json_set(&key, "$", v.clone()).unwrap();
+                (key.len(), v)
+            }
+        };
+        bytes += (key_len + approx_value_bytes(&value)) as u64;
+    }
+    bytes
+}
+
+// ---------------------------------------------------------------------------
+// Benchmark
+// ---------------------------------------------------------------------------
+
+struct BundleResult {
+    mode: DurabilityConfig,
+    entries: usize,
+    /// Logical bytes written while populating.
+    entry_bytes: u64,
+    bundle_bytes: u64,
+    /// `entry_count` reported by the export.
+    exported: u64,
+    export_time: Duration,
+    validate_time: Duration,
+    import_time: Duration,
+    /// `keys_written` reported by the import.
+    imported_keys: u64,
+}
+
+impl BundleResult {
+    fn ratio(&self) -> f64 {
+        if self.bundle_bytes == 0 {
+            return 0.0;
+        }
+        self.entry_bytes as f64 / self.bundle_bytes as f64
+    }
+
+    fn mb_per_sec(&self, d: Duration) -> f64 {
+        self.entry_bytes as f64 / (1024.0 * 1024.0) / d.as_secs_f64()
+    }
+
+    fn us_per_entry(&self, d: Duration) -> f64 {
+        d.as_secs_f64() * 1e6 / self.entries as f64
+    }
+}
+
+fn run(mode: DurabilityConfig, entries: usize, entropy: ValueEntropy) -> BundleResult {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let bundle_path = dir.path().join(format!("{}.runbundle.tar.zst", BRANCH));
+    let bundle_path = bundle_path.to_str().expect("temp path is not UTF-8");
+
+    let mut source = create_db(mode);
+    source.db.create_branch(BRANCH).unwrap();
+    source.db.set_branch(BRANCH).unwrap();
+    let entry_bytes = populate(&source.db, entries, entropy);
+
+    let start = Instant::now();
+    let export = source.db.branch_export(BRANCH, bundle_path).unwrap();
+    let export_time = start.elapsed();
+
+    let start = Instant::now();
+    let validate = source.db.branch_validate_bundle(bundle_path).unwrap();
+    let validate_time = start.elapsed();
+    assert!(validate.checksums_valid, "exported bundle failed validation");
+    assert_eq!(validate.entry_count, export.entry_count, "validate/export entry_count mismatch");
+
+    let target = create_db(mode);
+    let start = Instant::now();
+    let import = target.db.branch_import(bundle_path).unwrap();
+    let import_time = start.elapsed();
+
+    BundleResult {
+        mode,
+        entries,
+        entry_bytes,
+        bundle_bytes: export.bundle_size,
+        exported: export.entry_count,
+        export_time,
+        validate_time,
+        import_time,
+        imported_keys: import.keys_written,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+fn print_table_header() {
+    eprintln!(
+        "{:<10} {:>10} {:>11} {:>11} {:>7} {:>11} {:>9} {:>8} {:>11} {:>9} {:>11} {:>9} {:>8}",
+        "mode",
+        "entries",
+        "data",
+        "bundle",
+        "ratio",
+        "export",
+        "exp MB/s",
+        "µs/ent",
+        "validate",
+        "val MB/s",
+        "import",
+        "imp MB/s",
+        "µs/ent"
+    );
+    eprintln!("{}", "-".repeat(139));
+}
+
+fn print_table_row(r: &BundleResult) {
+    eprintln!(
+        "{:<10} {:>10} {:>11} {:>11} {:>6.1}x {:>11} {:>9.1} {:>8.2} {:>11} {:>9.1} {:>11} {:>9.1} \
+         {:>8.2}",
+        r.mode.label(),
+        fmt_num(r.entries as u64),
+        fmt_mb(r.entry_bytes),
+        fmt_mb(r.bundle_bytes),
+        r.ratio(),
+        fmt_duration(r.export_time),
+        r.mb_per_sec(r.export_time),
+        r.us_per_entry(r.export_time),
+        fmt_duration(r.validate_time),
+        r.mb_per_sec(r.validate_time),
+        fmt_duration(r.import_time),
+        r.mb_per_sec(r.import_time),
+        r.us_per_entry(r.import_time)
+    );
+}
+
+fn print_csv_header() {
+    println!(
+        "mode,entries,entry_bytes,bundle_bytes,exported_entries,export_ns,validate_ns,\
+import_ns,imported_keys"
+    );
+}
+
+fn print_csv_row(r: &BundleResult) {
+    println!(
+        "{},{},{},{},{},{},{},{},{}",
+        r.mode.label(),
+        r.entries,
+        r.entry_bytes,
+        r.bundle_bytes,
+        r.exported,
+        r.export_time.as_nanos(),
+        r.validate_time.as_nanos(),
+        r.import_time.as_nanos(),
+        r.imported_keys
+    );
+}
+
+// ---------------------------------------------------------------------------
+// CLI
+// ---------------------------------------------------------------------------
+
+struct Config {
+    entries: Vec<usize>,
+    modes: Vec<DurabilityConfig>,
+    entropy: ValueEntropy,
+    csv: bool,
+}
+
+fn print_usage() {
+    let entries: Vec<String> = DEFAULT_ENTRIES.iter().map(|n| n.to_string()).collect();
+    println!(
+        "\
+Bundle benchmark: branch_export / validate / import throughput and bundle size.
+
+Usage: cargo bench --bench bundle -- [OPTIONS]
+
+Options:
+  --entries <list>      Comma-separated entry counts, split across KV/State/Event/JSON
+                        [default: {entries}]
+  --durability <list>   Comma-separated: cache, standard, always [default: standard]
+  --entropy <e>         KV value entropy: const, mixed, random [default: mixed]
+  --csv                 Print results as CSV on stdout
+  --strict              Exit with an error on unknown arguments instead of warning
+  -h, --help            Print this help and exit
+
+Examples:
+  cargo bench --bench bundle -- --entries 1000,100000,500000
+  cargo bench --bench bundle -- --entropy random --csv",
+        entries = entries.join(","),
+    );
+}
+
+fn parse_mode(s: &str) -> DurabilityConfig {
+    match s.trim() {
+        "cache" => DurabilityConfig::Cache,
+        "standard" => DurabilityConfig::Standard,
+        "always" => DurabilityConfig::Always,
+        other => {
+            eprintln!("error: unknown durability mode {:?} (cache, standard, always)", other);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn parse_args() -> Config {
+    let args: Vec<String> = std::env::args().collect();
+    let mut config = Config {
+        entries: DEFAULT_ENTRIES.to_vec(),
+        modes: vec![DurabilityConfig::Standard],
+        entropy: ValueEntropy::Mixed,
+        csv: false,
+    };
+
+    let mut unknown = Vec::new();
+    let mut strict = false;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--entries" => {
+                i += 1;
+                config.entries = args[i]
+                    .split(',')
+                    .filter_map(|s| s.trim().parse().ok())
+                    .filter(|&n| n > 0)
+                    .collect();
+            }
+            "--durability" => {
+                i += 1;
+                config.modes = args[i].split(',').map(parse_mode).collect();
+            }
+            "--entropy" => {
+                i += 1;
+                config.entropy = match args[i].as_str() {
+                    "const" => ValueEntropy::Constant,
+                    "mixed" => ValueEntropy::Mixed,
+                    "random" => ValueEntropy::Random,
+                    other => {
+                        eprintln!("error: unknown entropy {:?} (const, mixed, random)", other);
+                        std::process::exit(2);
+                    }
+                };
+            }
+            "--csv" => config.csv = true,
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
+        }
+        i += 1;
+    }
+    check_unknown_args(&unknown, strict);
+
+    config
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() {
+    let config = parse_args();
+    print_hardware_info();
+
+    if config.csv {
+        print_csv_header();
+    } else {
+        eprintln!("=== StrataDB Bundle Export/Validate/Import Benchmark ===");
+        eprintln!(
+            "Parameters: entries {:?} across KV/State/Event/JSON, {} 1KB KV values",
+            config.entries,
+            config.entropy.label()
+        );
+        eprintln!("ratio = logical entry bytes / bundle bytes; MB/s is logical entry data.");
+        eprintln!();
+    }
+
+    let mut results = Vec::new();
+    for &mode in &config.modes {
+        for &entries in &config.entries {
+            if !config.csv {
+                eprint!("  {} @ {} entries...", mode.label(), fmt_num(entries as u64));
+            }
+            let result = run(mode, entries, config.entropy);
+            if config.csv {
+                print_csv_row(&result);
+            } else {
+                eprintln!(" done");
+            }
+            results.push(result);
+        }
+    }
+
+    if !config.csv {
+        eprintln!();
+        print_table_header();
+        for r in &results {
+            print_table_row(r);
+        }
+        eprintln!();
+        eprintln!("=== Benchmark complete ===");
+    }
+}
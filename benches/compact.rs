@@ -0,0 +1,363 @@
+//! Compaction Effectiveness Benchmark for StrataDB
+//!
+//! Fills a database, deletes a fraction of the keys, then measures on-disk
+//! size and `kv_get` latency on the surviving keys before and after a
+//! `compact()`. Reports space reclaimed, compaction time and the latency
+//! change per durability mode — i.e. whether compaction pulls its weight.
+//!
+//! Deleted keys are spread evenly across the keyspace rather than taken
+//! from one end, so compaction cannot get away with dropping whole files.
+//! Cache mode has no data directory; its size columns print `-`.
+//!
+//! Uses a custom harness (like fill_level.rs): the interesting output is a
+//! before/after comparison, not statistical convergence of one operation.
+//!
+//! Run:    `cargo bench --bench compact`
+//! Custom: `cargo bench --bench compact -- --fill 250000 --delete-fraction 0.9`
+//! Single: `cargo bench --bench compact -- --durability standard`
+//! CSV:    `cargo bench --bench compact -- --csv`
+//! Help:   `cargo bench --bench compact -- --help`
+
+#[allow(unused)]
+#[path = "harness/mod.rs"]
+mod harness;
+
+use harness::metrics::fmt_mb;
+use harness::scaling::{fmt_duration, fmt_num};
+use harness::{
+    check_unknown_args, create_db, dir_size_bytes, kv_value, measure_percentiles,
+    print_hardware_info, BenchDb, DurabilityConfig, Percentiles,
+};
+use std::time::{Duration, Instant};
+
+// ---------------------------------------------------------------------------
+// Parameters
+// ---------------------------------------------------------------------------
+
+const DEFAULT_FILL: usize = 100_000;
+const DEFAULT_DELETE_FRACTION: f64 = 0.5;
+const DEFAULT_OPS: usize = 10_000;
+
+fn fill_key(i: usize) -> String {
+    format!("fill:{:012}", i)
+}
+
+/// Whether key `i` is one of the `fraction` of keys to delete. Spreads the
+/// deletions evenly: exactly `floor(fill * fraction)` of `0..fill` match.
+fn is_deleted(i: usize, fraction: f64) -> bool {
+    ((i + 1) as f64 * fraction).floor() > (i as f64 * fraction).floor()
+}
+
+// ---------------------------------------------------------------------------
+// Result type
+// ---------------------------------------------------------------------------
+
+struct CompactResult {
+    mode: DurabilityConfig,
+    fill: usize,
+    deleted: usize,
+    /// Data directory size after the deletes and after `compact()`;
+    /// `None` in cache mode.
+    size_before: Option<u64>,
+    size_after: Option<u64>,
+    compact_time: Duration,
+    get_before: Percentiles,
+    get_after: Percentiles,
+}
+
+impl CompactResult {
+    fn reclaimed(&self) -> Option<(u64, f64)> {
+        let (before, after) = (self.size_before?, self.size_after?);
+        let reclaimed = before.saturating_sub(after);
+        let pct = if before == 0 { 0.0 } else { reclaimed as f64 / before as f64 * 100.0 };
+        Some((reclaimed, pct))
+    }
+
+    /// p50 change in percent; negative means compaction made reads faster.
+    fn p50_change_pct(&self) -> f64 {
+        let before = self.get_before.p50.as_nanos() as f64;
+        if before == 0.0 {
+            return 0.0;
+        }
+        (self.get_after.p50.as_nanos() as f64 - before) / before * 100.0
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Benchmark
+// ---------------------------------------------------------------------------
+
+fn on_disk_size(db: &BenchDb) -> Option<u64> {
+    db.db.flush().unwrap();
+    db.path().map(dir_size_bytes)
+}
+
+/// Time `n` reads cycling over the surviving keys.
+fn measure_gets(db: &BenchDb, survivors: &[usize], n: usize) -> Percentiles {
+    let mut i = 0;
+    measure_percentiles(n, || {
+        let key = fill_key(survivors[i % survivors.len()]);
+        std::hint::black_box(db.db.kv_get(&key).unwrap());
+        i += 1;
+    })
+}
+
+fn run_mode(mode: DurabilityConfig, config: &Config) -> CompactResult {
+    let db = create_db(mode);
+    let value = kv_value();
+    for i in 0..config.fill {
+        db.db.kv_put(&fill_key(i), value.clone()).unwrap();
+    }
+
+    let mut survivors = Vec::with_capacity(config.fill);
+    let mut deleted = 0;
+    for i in 0..config.fill {
+        if is_deleted(i, config.delete_fraction) {
+            db.db.kv_delete(&fill_key(i)).unwrap();
+            deleted += 1;
+        } else {
+            survivors.push(i);
+        }
+    }
+    if survivors.is_empty() {
+        // Everything deleted: read a missing key so the latency pass still runs
+        survivors.push(0);
+    }
+
+    let size_before = on_disk_size(&db);
+    let get_before = measure_gets(&db, &survivors, config.ops);
+
+    let start = Instant::now();
+    db.db.compact().unwrap();
+    let compact_time = start.elapsed();
+
+    let size_after = on_disk_size(&db);
+    let get_after = measure_gets(&db, &survivors, config.ops);
+
+    CompactResult {
+        mode,
+        fill: config.fill,
+        deleted,
+        size_before,
+        size_after,
+        compact_time,
+        get_before,
+        get_after,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+fn fmt_size(bytes: Option<u64>) -> String {
+    bytes.map_or_else(|| "-".to_string(), fmt_mb)
+}
+
+fn print_table_header() {
+    eprintln!(
+        "{:<10} {:>11} {:>11} {:>11} {:>18} {:>11} {:>11} {:>11} {:>9}",
+        "mode",
+        "size_before",
+        "size_after",
+        "reclaimed",
+        "",
+        "compact",
+        "get_p50",
+        "get_p50'",
+        "p50_chg"
+    );
+    eprintln!("{}", "-".repeat(112));
+}
+
+fn print_table_row(r: &CompactResult) {
+    let (reclaimed, pct) = match r.reclaimed() {
+        Some((bytes, pct)) => (fmt_mb(bytes), format!("({:.1}%)", pct)),
+        None => ("-".to_string(), String::new()),
+    };
+    eprintln!(
+        "{:<10} {:>11} {:>11} {:>11} {:>18} {:>11} {:>11} {:>11} {:>+8.1}%",
+        r.mode.label(),
+        fmt_size(r.size_before),
+        fmt_size(r.size_after),
+        reclaimed,
+        pct,
+        fmt_duration(r.compact_time),
+        fmt_duration(r.get_before.p50),
+        fmt_duration(r.get_after.p50),
+        r.p50_change_pct()
+    );
+}
+
+fn print_csv_header() {
+    println!(
+        "mode,fill,deleted,size_before_bytes,size_after_bytes,reclaimed_bytes,compact_ns,\
+get_p50_before_ns,get_p99_before_ns,get_p50_after_ns,get_p99_after_ns"
+    );
+}
+
+fn print_csv_row(r: &CompactResult) {
+    let opt = |v: Option<u64>| v.map_or_else(String::new, |v| v.to_string());
+    println!(
+        "{},{},{},{},{},{},{},{},{},{},{}",
+        r.mode.label(),
+        r.fill,
+        r.deleted,
+        opt(r.size_before),
+        opt(r.size_after),
+        opt(r.reclaimed().map(|(bytes, _)| bytes)),
+        r.compact_time.as_nanos(),
+        r.get_before.p50.as_nanos(),
+        r.get_before.p99.as_nanos(),
+        r.get_after.p50.as_nanos(),
+        r.get_after.p99.as_nanos()
+    );
+}
+
+// ---------------------------------------------------------------------------
+// CLI
+// ---------------------------------------------------------------------------
+
+struct Config {
+    fill: usize,
+    delete_fraction: f64,
+    ops: usize,
+    modes: Vec<DurabilityConfig>,
+    csv: bool,
+}
+
+fn print_usage() {
+    println!(
+        "\
+Compaction benchmark: space reclaimed and read latency before/after compact().
+
+Usage: cargo bench --bench compact -- [OPTIONS]
+
+Options:
+  --fill <n>              Keys written before deleting [default: {fill}]
+  --delete-fraction <f>   Fraction of keys to delete, 0.0-1.0 [default: {fraction}]
+  -n <ops>                kv_get samples before and after compaction [default: {ops}]
+  --durability <list>     Comma-separated: cache, standard, always [default: all]
+  --csv                   Print results as CSV on stdout
+  --strict                Exit with an error on unknown arguments instead of warning
+  -h, --help              Print this help and exit
+
+Examples:
+  cargo bench --bench compact -- --fill 250000 --delete-fraction 0.9
+  cargo bench --bench compact -- --durability standard,always --csv",
+        fill = DEFAULT_FILL,
+        fraction = DEFAULT_DELETE_FRACTION,
+        ops = DEFAULT_OPS,
+    );
+}
+
+fn parse_mode(s: &str) -> DurabilityConfig {
+    match s.trim() {
+        "cache" => DurabilityConfig::Cache,
+        "standard" => DurabilityConfig::Standard,
+        "always" => DurabilityConfig::Always,
+        other => {
+            eprintln!("error: unknown durability mode {:?} (cache, standard, always)", other);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn parse_args() -> Config {
+    let args: Vec<String> = std::env::args().collect();
+    let mut config = Config {
+        fill: DEFAULT_FILL,
+        delete_fraction: DEFAULT_DELETE_FRACTION,
+        ops: DEFAULT_OPS,
+        modes: DurabilityConfig::ALL.to_vec(),
+        csv: false,
+    };
+
+    let mut unknown = Vec::new();
+    let mut strict = false;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--fill" => {
+                i += 1;
+                config.fill = args[i].parse().unwrap_or(DEFAULT_FILL);
+            }
+            "--delete-fraction" => {
+                i += 1;
+                config.delete_fraction = match args[i].parse::<f64>() {
+                    Ok(f) if (0.0..=1.0).contains(&f) => f,
+                    _ => {
+                        eprintln!("error: --delete-fraction must be between 0.0 and 1.0");
+                        std::process::exit(2);
+                    }
+                };
+            }
+            "-n" => {
+                i += 1;
+                config.ops = args[i].parse().unwrap_or(DEFAULT_OPS);
+            }
+            "--durability" => {
+                i += 1;
+                config.modes = args[i].split(',').map(parse_mode).collect();
+            }
+            "--csv" => config.csv = true,
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
+        }
+        i += 1;
+    }
+    check_unknown_args(&unknown, strict);
+
+    config
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() {
+    let config = parse_args();
+    print_hardware_info();
+
+    if config.csv {
+        print_csv_header();
+    } else {
+        eprintln!("=== StrataDB Compaction Benchmark ===");
+        eprintln!(
+            "Parameters: {} keys x 1KB, {:.0}% deleted, {} kv_get samples per pass",
+            fmt_num(config.fill as u64),
+            config.delete_fraction * 100.0,
+            fmt_num(config.ops as u64)
+        );
+        eprintln!("get_p50' is the p50 after compact(); p50_chg < 0 means reads got faster.");
+        eprintln!();
+    }
+
+    let mut results = Vec::new();
+    for &mode in &config.modes {
+        if !config.csv {
+            eprint!("  {}: filling, deleting, compacting...", mode.label());
+        }
+        let result = run_mode(mode, &config);
+        if config.csv {
+            print_csv_row(&result);
+        } else {
+            eprintln!(" done");
+        }
+        results.push(result);
+    }
+
+    if !config.csv {
+        eprintln!();
+        print_table_header();
+        for r in &results {
+            print_table_row(r);
+        }
+        eprintln!();
+        eprintln!("=== Benchmark complete ===");
+    }
+}
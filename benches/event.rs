@@ -1,4 +1,14 @@
-//! Event primitive benchmarks: append, read, read_by_type
+//! Event primitive benchmarks: append, read, read_by_type, read_range
+//!
+//! read_by_type also runs a cardinality sweep: the same total number of
+//! events spread over 2 to 10,000 distinct types, to show whether the type
+//! index lookup grows with the number of types. Each row reports the
+//! per-type result-set size, which shrinks as cardinality grows.
+//!
+//! read_range is the streaming-consumer (and recovery replay) pattern: there
+//! is no range API, so each sample reads `RANGE_WINDOW` consecutive sequences
+//! with `event_read(seq)`, sliding the window across a pre-populated stream.
+//! It reports per-window latency and events/s.
 //!
 //! All benchmarks report latency percentiles.
 
@@ -10,10 +20,19 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use harness::{
-    create_db, event_payload, measure_with_counters, report_counters, report_percentiles,
-    DurabilityConfig, PERCENTILE_SAMPLES, WARMUP_COUNT,
+    approx_value_bytes, create_db, event_payload, measure_with_counters, percentile_samples,
+    report_counters, report_percentiles, report_scan_throughput, warmup_count, DurabilityConfig,
+    ModeComparison,
 };
 
+/// Distinct event types for the read_by_type cardinality sweep.
+const TYPE_CARDINALITIES: [u64; 4] = [2, 50, 1_000, 10_000];
+/// Events appended per cardinality point, held constant across the sweep.
+const CARDINALITY_TOTAL_EVENTS: u64 = 20_000;
+
+/// Consecutive sequences read per read_range sample.
+const RANGE_WINDOW: u64 = 1000;
+
 fn event_append(c: &mut Criterion) {
     let mut group = c.benchmark_group("event/append");
     group.throughput(Throughput::Elements(1));
@@ -31,26 +50,28 @@ fn event_append(c: &mut Criterion) {
         });
 
         let label = format!("event/append/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             bench_db
                 .db
                 .event_append("bench_event", event_payload())
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
 
 fn event_read(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("event/read");
     group.throughput(Throughput::Elements(1));
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: event/read ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
-        for _ in 0..WARMUP_COUNT {
+        for _ in 0..warmup {
             bench_db
                 .db
                 .event_append("bench_event", event_payload())
@@ -59,21 +80,23 @@ fn event_read(c: &mut Criterion) {
         let counter = AtomicU64::new(0);
         group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
             b.iter(|| {
-                let seq = counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+                let seq = counter.fetch_add(1, Ordering::Relaxed) % warmup;
                 bench_db.db.event_read(seq).unwrap();
             });
         });
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("event/read/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-            let seq = pct_counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            let seq = pct_counter.fetch_add(1, Ordering::Relaxed) % warmup;
             bench_db.db.event_read(seq).unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        comparison.record("event/read", mode, &p);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
+    comparison.report("event/read");
 }
 
 fn event_read_by_type(c: &mut Criterion) {
@@ -97,14 +120,119 @@ fn event_read_by_type(c: &mut Criterion) {
         });
 
         let label = format!("event/read_by_type/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             bench_db.db.event_read_by_type("type_a").unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
+    }
+    group.finish();
+}
+
+fn event_read_by_type_cardinality(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event/read_by_type/cardinality");
+    group.throughput(Throughput::Elements(1));
+
+    eprintln!(
+        "\n--- Latency Percentiles: event/read_by_type by type cardinality ({} events) ---",
+        CARDINALITY_TOTAL_EVENTS
+    );
+    for types in TYPE_CARDINALITIES {
+        let type_names: Vec<String> = (0..types).map(|t| format!("type_{}", t)).collect();
+        for mode in DurabilityConfig::ALL {
+            let bench_db = create_db(mode);
+            for i in 0..CARDINALITY_TOTAL_EVENTS {
+                bench_db
+                    .db
+                    .event_append(&type_names[(i % types) as usize], event_payload())
+                    .unwrap();
+            }
+            // Round-robin population gives every type the same result-set size
+            let per_type = bench_db.db.event_read_by_type(&type_names[0]).unwrap().len();
+
+            let id = format!("{}/{}", types, mode.label());
+            let counter = AtomicU64::new(0);
+            group.bench_function(BenchmarkId::new("types", &id), |b| {
+                b.iter(|| {
+                    let t = counter.fetch_add(1, Ordering::Relaxed) % types;
+                    bench_db.db.event_read_by_type(&type_names[t as usize]).unwrap();
+                });
+            });
+
+            let pct_counter = AtomicU64::new(0);
+            let label = format!("event/read_by_type/types={}/{}", types, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                let t = pct_counter.fetch_add(1, Ordering::Relaxed) % types;
+                bench_db.db.event_read_by_type(&type_names[t as usize]).unwrap();
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+            eprintln!("  {:<45} types={}  result_set={} events/type", label, types, per_type);
+        }
+    }
+    group.finish();
+}
+
+fn event_read_range(c: &mut Criterion) {
+    // At least ten windows, so consecutive samples read different events
+    let stream_len = warmup_count().max(RANGE_WINDOW * 10);
+    let windows = stream_len - RANGE_WINDOW + 1;
+    let mut group = c.benchmark_group("event/read_range");
+    group.throughput(Throughput::Elements(RANGE_WINDOW));
+
+    let mut comparison = ModeComparison::new();
+    eprintln!("\n--- Latency Percentiles: event/read_range ({} events) ---", RANGE_WINDOW);
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let first = bench_db
+            .db
+            .event_append("bench_event", event_payload())
+            .unwrap();
+        for _ in 1..stream_len {
+            bench_db
+                .db
+                .event_append("bench_event", event_payload())
+                .unwrap();
+        }
+
+        let read_window = |w: u64| {
+            let start = first + (w * RANGE_WINDOW) % windows;
+            for seq in start..start + RANGE_WINDOW {
+                bench_db.db.event_read(seq).unwrap();
+            }
+        };
+
+        let counter = AtomicU64::new(0);
+        group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
+            b.iter(|| read_window(counter.fetch_add(1, Ordering::Relaxed)));
+        });
+
+        let pct_counter = AtomicU64::new(0);
+        let label = format!("event/read_range/{}", mode.label());
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            read_window(pct_counter.fetch_add(1, Ordering::Relaxed));
+        });
+        report_percentiles(&label, &p);
+        comparison.record("event/read_range", mode, &p);
+        report_counters(&label, &counters, percentile_samples() as u64);
+        let event_bytes = approx_value_bytes(&event_payload()) as u64;
+        report_scan_throughput(&label, &p, RANGE_WINDOW, event_bytes);
+        eprintln!(
+            "  {:<45} {:.0} events/s (at p50)",
+            label,
+            RANGE_WINDOW as f64 / p.p50.as_secs_f64()
+        );
     }
     group.finish();
+    comparison.report("event/read_range");
 }
 
-criterion_group!(benches, event_append, event_read, event_read_by_type);
-criterion_main!(benches);
+criterion_group!(
+    benches,
+    event_append,
+    event_read,
+    event_read_by_type,
+    event_read_by_type_cardinality,
+    event_read_range
+);
+criterion_main!(benches, harness::baseline::check_baseline);
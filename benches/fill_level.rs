@@ -2,7 +2,16 @@
 //!
 //! Measures how operation latency and throughput degrade as database size grows.
 //! Tests run at fill levels of 0, 10K, 50K, 100K, and 250K pre-existing keys,
-//! showing the performance curve for each operation.
+//! showing the performance curve for each operation. Vector tests instead fill
+//! the searched collection itself (0 to 100K 128-dim vectors by default), since
+//! `vector_search` is a linear scan and its cost curve is the point. JSON
+//! tests fill with `json_document`s rather than KV entries.
+//!
+//! `deleted_kv_get` deletes every other fill key, then reads the same 100
+//! hot keys as `kv_get`, so set against `kv_get` it shows the cost of
+//! accumulated tombstones with the working set held fixed;
+//! `compacted_kv_get` does the same after a `compact()`. At fill level 0
+//! there is nothing to delete and both match `kv_get`.
 //!
 //! Uses a custom harness (like redis_compare.rs) instead of Criterion because:
 //! - Clean table output showing fill level vs latency per operation
@@ -12,17 +21,43 @@
 //! Run:    `cargo bench --bench fill_level`
 //! Quick:  `cargo bench --bench fill_level -- -q`
 //! CSV:    `cargo bench --bench fill_level -- --csv`
+//! MD:     `cargo bench --bench fill_level -- --markdown > results.md`
 //! Custom: `cargo bench --bench fill_level -- --levels 0,1000,5000,10000`
+//! Vector: `cargo bench --bench fill_level -- -t vector --vector-levels 0,50000,200000`
 //! Single: `cargo bench --bench fill_level -- -t kv_put`
+//! Soak:   `cargo bench --bench fill_level -- --forever -t kv_put --levels 100000`
+//! WAL:    `cargo bench --bench fill_level -- --wal-focus --durability standard`
+//! cgroup: `systemd-run --user --scope -p MemoryMax=2G cargo bench --bench fill_level -- --cgroup`
+//! Help:   `cargo bench --bench fill_level -- --help`
+//! Store:  `cargo bench --bench fill_level --features sqlite-compare -- --store results.db`
+//!
+//! `--forever` runs one operation at the first fill level in a tight loop
+//! until Ctrl-C, printing throughput every second. Use it as a steady load
+//! generator when attaching `perf`, `strace`, or a memory profiler.
+//!
+//! `--cgroup` reports memory usage against the cgroup limit after each fill
+//! and warns when the largest fill level is projected to exceed it (Linux).
 
 #[allow(unused)]
 #[path = "harness/mod.rs"]
 mod harness;
 
-use harness::{create_db, kv_value, print_hardware_info, BenchDb, DurabilityConfig};
+use harness::metrics::{
+    cgroup_memory, fmt_mb, peak_rss_bytes, report_cgroup_memory, snapshot_process_metrics,
+    warn_if_projected_over_cgroup, CgroupMemory,
+};
+use harness::scaling::ReservoirSampler;
+#[cfg(feature = "sqlite-compare")]
+use harness::{hardware_summary, store::ResultStore};
+use harness::{
+    check_unknown_args, counter_delta, create_db, json_document, kv_value, print_hardware_info,
+    print_hardware_info_markdown, print_markdown_table, print_wal_focus_table, snapshot_counters,
+    vector_128d, BenchDb, DurabilityConfig, WalRow,
+};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use stratadb::Value;
+use stratadb::{DistanceMetric, Value, WalCounters};
 
 // ---------------------------------------------------------------------------
 // Parameters
@@ -31,6 +66,10 @@ use stratadb::Value;
 const DEFAULT_OPS: usize = 10_000;
 const DEFAULT_LEVELS: &[usize] = &[0, 10_000, 50_000, 100_000, 250_000];
 const BENCH_VALUE_SIZE: usize = 64; // smaller bench values to focus on engine overhead
+/// Collection sizes for the vector tests. Search is O(n), so these go high
+/// enough for the linear scan to dominate.
+const DEFAULT_VECTOR_LEVELS: &[usize] = &[0, 1_000, 10_000, 50_000, 100_000];
+const VECTOR_COLLECTION: &str = "fill_vectors";
 
 // ---------------------------------------------------------------------------
 // Result type
@@ -49,6 +88,119 @@ struct FillResult {
     p99: Duration,
     min: Duration,
     max: Duration,
+    /// WAL counter delta over the timed loop (zero in cache mode).
+    wal: WalCounters,
+    /// `--cgroup`: cgroup memory (before, after) populating the fill keys.
+    cgroup_fill: Option<(CgroupMemory, CgroupMemory)>,
+    /// Process peak RSS in MB once the fill completed. Absolute, not a delta:
+    /// the high-water mark can't be reset, so it includes earlier runs.
+    rss_mb: f64,
+}
+
+// ---------------------------------------------------------------------------
+// Forever mode
+// ---------------------------------------------------------------------------
+
+/// Set by `--forever`: `run_bench` loops until interrupted instead of
+/// stopping after `total_ops`.
+static FOREVER: AtomicBool = AtomicBool::new(false);
+
+/// Set from the SIGINT handler; polled by the forever loop.
+static STOP: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--cgroup`: the forever loop appends cgroup usage to each line.
+static CGROUP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigint(_signum: i32) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+/// Route SIGINT to `on_sigint` without depending on libc crate.
+///
+/// SIGINT = 2 on Linux and macOS.
+fn install_sigint_handler() {
+    const SIGINT: i32 = 2;
+    unsafe extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+    }
+    // SAFETY: `on_sigint` only performs an atomic store, which is async-signal-safe.
+    unsafe {
+        signal(SIGINT, on_sigint);
+    }
+}
+
+/// Run `bench_fn` until SIGINT, printing throughput once per second.
+///
+/// Latencies are reservoir-sampled so memory stays bounded however long
+/// the run lasts.
+fn run_forever(
+    db: &BenchDb,
+    name: &str,
+    fill_level: usize,
+    mut bench_fn: impl FnMut(),
+) -> FillResult {
+    let mut sampler = ReservoirSampler::new();
+    let mut total_ops = 0usize;
+    let mut sum = Duration::ZERO;
+    let mut window_ops = 0u64;
+    let wal_before = snapshot_counters(db);
+    let wall_start = Instant::now();
+    let mut window_start = wall_start;
+
+    eprintln!("  running {} until Ctrl-C...", name);
+    while !STOP.load(Ordering::Relaxed) {
+        let op_start = Instant::now();
+        bench_fn();
+        let latency = op_start.elapsed();
+        sampler.record(latency);
+        sum += latency;
+        total_ops += 1;
+        window_ops += 1;
+
+        let window = window_start.elapsed();
+        if window >= Duration::from_secs(1) {
+            let cgroup = match cgroup_memory() {
+                Some(cg) if CGROUP.load(Ordering::Relaxed) => {
+                    format!(", cgroup {:.0}% of {}", cg.used_pct(), fmt_mb(cg.limit_bytes))
+                }
+                _ => String::new(),
+            };
+            eprintln!(
+                "  [{:>6.0}s] {}: {} ops/sec ({} total, peak RSS {}{})",
+                wall_start.elapsed().as_secs_f64(),
+                name,
+                fmt_num((window_ops as f64 / window.as_secs_f64()) as u64),
+                fmt_num(total_ops as u64),
+                fmt_mb(peak_rss_bytes()),
+                cgroup,
+            );
+            window_ops = 0;
+            window_start = Instant::now();
+        }
+    }
+
+    let elapsed = wall_start.elapsed();
+    let mut latencies = sampler.into_samples();
+    latencies.sort_unstable();
+    let len = latencies.len().max(1);
+    let at = |i: usize| latencies.get(i.min(len - 1)).copied().unwrap_or_default();
+
+    FillResult {
+        name: name.to_string(),
+        fill_level,
+        total_ops,
+        elapsed,
+        ops_per_sec: total_ops as f64 / elapsed.as_secs_f64(),
+        avg: Duration::from_nanos((sum.as_nanos() / total_ops.max(1) as u128) as u64),
+        p50: at(len * 50 / 100),
+        p95: at(len * 95 / 100),
+        p99: at(len * 99 / 100),
+        min: at(0),
+        max: at(len - 1),
+        wal: counter_delta(&wal_before, &snapshot_counters(db)),
+        cgroup_fill: None,
+        rss_mb: 0.0,
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -56,12 +208,18 @@ struct FillResult {
 // ---------------------------------------------------------------------------
 
 fn run_bench(
+    db: &BenchDb,
     name: &str,
     fill_level: usize,
     total_ops: usize,
     mut bench_fn: impl FnMut(),
 ) -> FillResult {
+    if FOREVER.load(Ordering::Relaxed) {
+        return run_forever(db, name, fill_level, bench_fn);
+    }
+
     let mut latencies = Vec::with_capacity(total_ops);
+    let wal_before = snapshot_counters(db);
     let wall_start = Instant::now();
 
     for _ in 0..total_ops {
@@ -71,6 +229,7 @@ fn run_bench(
     }
 
     let elapsed = wall_start.elapsed();
+    let wal = counter_delta(&wal_before, &snapshot_counters(db));
     latencies.sort_unstable();
     let len = latencies.len();
     let sum: Duration = latencies.iter().sum();
@@ -87,6 +246,9 @@ fn run_bench(
         p99: latencies[(len * 99 / 100).min(len - 1)],
         min: latencies[0],
         max: latencies[len - 1],
+        wal,
+        cgroup_fill: None,
+        rss_mb: 0.0,
     }
 }
 
@@ -105,6 +267,53 @@ fn fill_database(db: &BenchDb, count: usize) {
     }
 }
 
+/// Create the vector collection and fill it with `count` 128-dim vectors.
+fn fill_vectors(db: &BenchDb, count: usize) {
+    db.db
+        .vector_create_collection(VECTOR_COLLECTION, 128, DistanceMetric::Cosine)
+        .unwrap();
+    for i in 0..count as u64 {
+        db.db
+            .vector_upsert(VECTOR_COLLECTION, &format!("fill:{:012}", i), vector_128d(i), None)
+            .unwrap();
+        if count >= 50_000 && (i + 1) % 50_000 == 0 {
+            eprintln!("  filled {}/{} vectors...", i + 1, count);
+        }
+    }
+}
+
+/// Fill the document store with `count` 10-field nested JSON documents.
+fn fill_json_documents(db: &BenchDb, count: usize) {
+    for i in 0..count as u64 {
+        db.db
+            .json_set(&format!("fill:{:012}", i), "$", json_document(i))
+            .unwrap();
+        if count >= 50_000 && (i + 1) % 50_000 == 0 {
+            eprintln!("  filled {}/{} documents...", i + 1, count);
+        }
+    }
+}
+
+/// Process peak RSS in MB, taken right after a fill.
+fn rss_after_fill_mb() -> f64 {
+    snapshot_process_metrics().peak_rss_kb as f64 / 1024.0
+}
+
+fn is_vector_test(test_name: &str) -> bool {
+    test_name.starts_with("vector_")
+}
+
+/// Populate `db` to `level` with the data `test_name` runs against.
+fn fill_for_test(db: &BenchDb, test_name: &str, level: usize) {
+    if is_vector_test(test_name) {
+        fill_vectors(db, level);
+    } else if test_name.starts_with("json_") {
+        fill_json_documents(db, level);
+    } else {
+        fill_database(db, level);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Benchmark functions
 // ---------------------------------------------------------------------------
@@ -112,29 +321,37 @@ fn fill_database(db: &BenchDb, count: usize) {
 fn bench_kv_put(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     let val = Value::Bytes(vec![0x42; BENCH_VALUE_SIZE]);
     let mut i = 0u64;
-    run_bench("kv_put", fill_level, n, || {
+    run_bench(db, "kv_put", fill_level, n, || {
         let key = format!("bench:{:012}", i);
         db.db.kv_put(&key, val.clone()).unwrap();
         i += 1;
     })
 }
 
-fn bench_kv_get(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
-    // Pre-populate 100 read-target keys
+/// Pre-populate the 100 read-target keys shared by the kv_get variants.
+fn populate_read_keys(db: &BenchDb) {
     let val = Value::Bytes(vec![0x42; BENCH_VALUE_SIZE]);
     for i in 0..100u64 {
         let key = format!("read:{:012}", i);
         db.db.kv_put(&key, val.clone()).unwrap();
     }
+}
 
+/// Time `kv_get` cycling over the 100 read-target keys.
+fn run_read_keys(db: &BenchDb, name: &str, n: usize, fill_level: usize) -> FillResult {
     let mut i = 0u64;
-    run_bench("kv_get", fill_level, n, || {
+    run_bench(db, name, fill_level, n, || {
         let key = format!("read:{:012}", i % 100);
         let _ = db.db.kv_get(&key);
         i += 1;
     })
 }
 
+fn bench_kv_get(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
+    populate_read_keys(db);
+    run_read_keys(db, "kv_get", n, fill_level)
+}
+
 fn bench_kv_delete(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     // Delete from fill keys (they exist from fill_database)
     // If fill_level is 0, pre-populate some keys to delete
@@ -148,17 +365,38 @@ fn bench_kv_delete(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     }
 
     let mut i = 0u64;
-    run_bench("kv_delete", fill_level, n, || {
+    run_bench(db, "kv_delete", fill_level, n, || {
         let key = format!("fill:{:012}", i % delete_count as u64);
         let _ = db.db.kv_delete(&key);
         i += 1;
     })
 }
 
+/// Delete every other fill key (optionally compacting afterwards), then time
+/// `kv_get` on the same 100 read-target keys as `bench_kv_get`, so only the
+/// tombstones differ. Level 0 has no fill keys, so nothing is deleted.
+fn bench_deleted_kv_get(
+    db: &BenchDb,
+    name: &str,
+    n: usize,
+    fill_level: usize,
+    compact: bool,
+) -> FillResult {
+    populate_read_keys(db);
+    for i in (0..fill_level).step_by(2) {
+        db.db.kv_delete(&format!("fill:{:012}", i)).unwrap();
+    }
+    if compact {
+        db.db.compact().unwrap();
+    }
+    run_read_keys(db, name, n, fill_level)
+}
+
 fn bench_kv_list(mode: DurabilityConfig, n: usize, fill_level: usize) -> FillResult {
     // Fresh database per fill level (same pattern as LRANGE_100 in redis_compare)
     let db = create_db(mode);
     fill_database(&db, fill_level);
+    let rss_mb = rss_after_fill_mb();
 
     // Pre-populate 100 keys with scan: prefix
     let val = Value::Bytes(vec![0x42; BENCH_VALUE_SIZE]);
@@ -167,15 +405,17 @@ fn bench_kv_list(mode: DurabilityConfig, n: usize, fill_level: usize) -> FillRes
         db.db.kv_put(&key, val.clone()).unwrap();
     }
 
-    run_bench("kv_list", fill_level, n, || {
+    let mut result = run_bench(&db, "kv_list", fill_level, n, || {
         let _ = db.db.kv_list(Some("scan:")).unwrap();
-    })
+    });
+    result.rss_mb = rss_mb;
+    result
 }
 
 fn bench_state_set(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     let val = Value::Bytes(vec![0x53; BENCH_VALUE_SIZE]);
     let mut i = 0u64;
-    run_bench("state_set", fill_level, n, || {
+    run_bench(db, "state_set", fill_level, n, || {
         let cell = format!("cell:{:012}", i);
         db.db.state_set(&cell, val.clone()).unwrap();
         i += 1;
@@ -191,7 +431,7 @@ fn bench_state_read(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     }
 
     let mut i = 0u64;
-    run_bench("state_read", fill_level, n, || {
+    run_bench(db, "state_read", fill_level, n, || {
         let cell = format!("rcell:{:012}", i % 100);
         let _ = db.db.state_read(&cell).unwrap();
         i += 1;
@@ -206,7 +446,7 @@ fn bench_event_append(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     );
     let payload = Value::Object(payload_map);
 
-    run_bench("event_append", fill_level, n, || {
+    run_bench(db, "event_append", fill_level, n, || {
         db.db.event_append("bench_stream", payload.clone()).unwrap();
     })
 }
@@ -223,7 +463,7 @@ fn bench_event_read(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     }
 
     let mut rng: u64 = 0xdeadbeef;
-    run_bench("event_read", fill_level, n, || {
+    run_bench(db, "event_read", fill_level, n, || {
         // Simple LCG for sequence selection
         rng = rng
             .wrapping_mul(6364136223846793005)
@@ -233,6 +473,86 @@ fn bench_event_read(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
     })
 }
 
+fn bench_json_get(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
+    // Pre-populate 100 read-target documents
+    for i in 0..100u64 {
+        db.db
+            .json_set(&format!("jread:{:012}", i), "$", json_document(i))
+            .unwrap();
+    }
+
+    let mut i = 0u64;
+    run_bench(db, "json_get", fill_level, n, || {
+        let key = format!("jread:{:012}", i % 100);
+        let _ = db.db.json_get(&key, "$").unwrap();
+        i += 1;
+    })
+}
+
+fn bench_json_set_path(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
+    // Pre-populate 100 documents whose nested field is rewritten
+    for i in 0..100u64 {
+        db.db
+            .json_set(&format!("jdoc:{:012}", i), "$", json_document(i))
+            .unwrap();
+    }
+
+    let mut i = 0u64;
+    run_bench(db, "json_set_path", fill_level, n, || {
+        let key = format!("jdoc:{:012}", i % 100);
+        db.db
+            .json_set(&key, "$.metadata.mid_score", Value::Float(i as f64 * 2.5))
+            .unwrap();
+        i += 1;
+    })
+}
+
+fn bench_json_list(mode: DurabilityConfig, n: usize, fill_level: usize) -> FillResult {
+    // Fresh database per fill level (same pattern as kv_list)
+    let db = create_db(mode);
+    fill_json_documents(&db, fill_level);
+    let rss_mb = rss_after_fill_mb();
+
+    // Pre-populate 100 documents with scan: prefix
+    for i in 0..100u64 {
+        db.db
+            .json_set(&format!("scan:{:012}", i), "$", json_document(i))
+            .unwrap();
+    }
+
+    let mut result = run_bench(&db, "json_list", fill_level, n, || {
+        let _ = db.db.json_list(Some("scan:".to_string()), None, 100).unwrap();
+    });
+    result.rss_mb = rss_mb;
+    result
+}
+
+fn bench_vector_upsert(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
+    let mut i = 0u64;
+    run_bench(db, "vector_upsert", fill_level, n, || {
+        let key = format!("bench:{:012}", i);
+        // Offset past the fill vectors so the bench inserts distinct embeddings
+        let embedding = vector_128d(fill_level as u64 + i);
+        db.db
+            .vector_upsert(VECTOR_COLLECTION, &key, embedding, None)
+            .unwrap();
+        i += 1;
+    })
+}
+
+fn bench_vector_search(db: &BenchDb, n: usize, fill_level: usize) -> FillResult {
+    // Queries are vectors not in the collection, so every search scores all entries
+    let queries: Vec<Vec<f32>> = (0..100u64)
+        .map(|q| vector_128d(fill_level as u64 + 1_000_000 + q))
+        .collect();
+    let mut i = 0usize;
+    run_bench(db, "vector_search", fill_level, n, || {
+        let query = queries[i % queries.len()].clone();
+        let _ = db.db.vector_search(VECTOR_COLLECTION, query, 10).unwrap();
+        i += 1;
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Output formatters
 // ---------------------------------------------------------------------------
@@ -255,14 +575,14 @@ fn fmt_num(n: u64) -> String {
 
 fn print_table_header() {
     eprintln!(
-        "  {:>10}  {:>11}  {:>9}  {:>9}  {:>9}  {:>9}  {:>9}",
-        "fill_level", "ops/sec", "avg", "p50", "p95", "p99", "max"
+        "  {:>10}  {:>11}  {:>9}  {:>9}  {:>9}  {:>9}  {:>9}  {:>12}",
+        "fill_level", "ops/sec", "avg", "p50", "p95", "p99", "max", "rss_mb(abs)"
     );
 }
 
 fn print_table_row(r: &FillResult) {
     eprintln!(
-        "  {:>10}  {:>11}  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms",
+        "  {:>10}  {:>11}  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>12.1}",
         fmt_num(r.fill_level as u64),
         fmt_num(r.ops_per_sec as u64),
         duration_ms(r.avg),
@@ -270,6 +590,7 @@ fn print_table_row(r: &FillResult) {
         duration_ms(r.p95),
         duration_ms(r.p99),
         duration_ms(r.max),
+        r.rss_mb,
     );
 }
 
@@ -285,13 +606,13 @@ fn print_quiet(r: &FillResult) {
 
 fn print_csv_header() {
     println!(
-        "\"test\",\"fill_level\",\"ops_sec\",\"avg_ms\",\"p50_ms\",\"p95_ms\",\"p99_ms\",\"max_ms\""
+        "\"test\",\"fill_level\",\"ops_sec\",\"avg_ms\",\"p50_ms\",\"p95_ms\",\"p99_ms\",\"max_ms\",\"rss_mb_after_fill\""
     );
 }
 
 fn print_csv_row(r: &FillResult) {
     println!(
-        "\"{}\",{},{:.2},{:.3},{:.3},{:.3},{:.3},{:.3}",
+        "\"{}\",{},{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{:.1}",
         r.name,
         r.fill_level,
         r.ops_per_sec,
@@ -300,9 +621,68 @@ fn print_csv_row(r: &FillResult) {
         duration_ms(r.p95),
         duration_ms(r.p99),
         duration_ms(r.max),
+        r.rss_mb,
     );
 }
 
+/// Same columns as `print_table_header`, one table per test.
+const MARKDOWN_HEADERS: &[&str] = &[
+    "fill_level", "ops/sec", "avg_ms", "p50_ms", "p95_ms", "p99_ms", "max_ms", "rss_mb(abs)",
+];
+
+fn markdown_row(r: &FillResult) -> Vec<String> {
+    vec![
+        fmt_num(r.fill_level as u64),
+        fmt_num(r.ops_per_sec as u64),
+        format!("{:.3}", duration_ms(r.avg)),
+        format!("{:.3}", duration_ms(r.p50)),
+        format!("{:.3}", duration_ms(r.p95)),
+        format!("{:.3}", duration_ms(r.p99)),
+        format!("{:.3}", duration_ms(r.max)),
+        format!("{:.1}", r.rss_mb),
+    ]
+}
+
+/// `--store`: append one result's metrics to the SQLite history, as
+/// operation `<test>@<fill_level>`. A failed insert does not abort the run.
+#[cfg(feature = "sqlite-compare")]
+fn store_result(store: &ResultStore, r: &FillResult, mode: DurabilityConfig) {
+    let metrics = [
+        ("ops_sec", r.ops_per_sec),
+        ("avg_ms", duration_ms(r.avg)),
+        ("p50_ms", duration_ms(r.p50)),
+        ("p95_ms", duration_ms(r.p95)),
+        ("p99_ms", duration_ms(r.p99)),
+        ("max_ms", duration_ms(r.max)),
+    ];
+    let operation = format!("{}@{}", r.name, r.fill_level);
+    if let Err(e) = store.record(&operation, mode.label(), &metrics) {
+        eprintln!("warning: {}", e);
+    }
+}
+
+/// `--markdown`: hardware and parameters as a preamble, then one GFM table
+/// per test with a row per fill level, mirroring the human-readable tables.
+fn print_markdown(config: &Config, tables: &[(&str, Vec<Vec<String>>)]) {
+    println!("## StrataDB Fill-Level Benchmark");
+    println!();
+    print_hardware_info_markdown();
+    println!(
+        "{} ops per measurement, {} bytes payload, {} mode",
+        config.ops,
+        BENCH_VALUE_SIZE,
+        config.durability.label()
+    );
+    println!();
+    println!("rss_mb(abs) is process peak RSS after each fill: a high-water mark, not a delta.");
+    println!();
+    for (test_name, rows) in tables {
+        println!("### {}", test_name);
+        println!();
+        print_markdown_table(MARKDOWN_HEADERS, rows);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // CLI parsing
 // ---------------------------------------------------------------------------
@@ -313,8 +693,58 @@ struct Config {
     durability: DurabilityConfig,
     tests: Option<Vec<String>>,
     levels: Vec<usize>,
+    /// Collection sizes for the vector tests.
+    vector_levels: Vec<usize>,
     csv: bool,
+    markdown: bool,
     quiet: bool,
+    forever: bool,
+    wal_focus: bool,
+    cgroup: bool,
+    /// Path to a SQLite results history (requires `sqlite-compare`).
+    store: Option<String>,
+}
+
+fn print_usage() {
+    let levels: Vec<String> = DEFAULT_LEVELS.iter().map(|l| l.to_string()).collect();
+    let vector_levels: Vec<String> = DEFAULT_VECTOR_LEVELS.iter().map(|l| l.to_string()).collect();
+    println!(
+        "\
+Fill-level benchmark: operation latency as the database grows.
+
+Usage: cargo bench --bench fill_level -- [OPTIONS]
+
+Options:
+  -n <ops>              Operations per measurement [default: {ops}]
+  -t <tests>            Comma-separated test name prefixes [default: all]
+                        Tests: {tests}
+  --levels <list>       Comma-separated fill levels [default: {levels}]
+  --vector-levels <list>
+                        Comma-separated collection sizes for vector_* tests
+                        [default: {vector_levels}]
+  --durability <mode>   cache, standard, or always [default: cache]
+  --csv                 Print results as CSV on stdout
+  --markdown            Print results as GitHub-flavored Markdown tables (one per test)
+                        on stdout
+  -q                    One line per (test, fill level)
+  --wal-focus           Print a WAL appends/syncs per-op table instead of latencies
+  --forever             Run one test (-t) at the first fill level until Ctrl-C
+  --cgroup              Report memory against the cgroup limit after each fill (Linux)
+  --store <file>        Append results to a SQLite history (needs --features sqlite-compare)
+  --strict              Exit with an error on unknown arguments instead of warning
+  -h, --help            Print this help and exit
+
+Examples:
+  cargo bench --bench fill_level -- -q
+  cargo bench --bench fill_level -- --levels 0,1000,5000,10000 -t kv_put
+  cargo bench --bench fill_level -- -t vector_search --vector-levels 0,100000,500000
+  cargo bench --bench fill_level -- --forever -t kv_put --levels 100000
+  cargo bench --bench fill_level -- --wal-focus --durability standard",
+        ops = DEFAULT_OPS,
+        tests = ALL_TESTS.join(", "),
+        levels = levels.join(","),
+        vector_levels = vector_levels.join(","),
+    );
 }
 
 fn parse_args() -> Config {
@@ -324,10 +754,18 @@ fn parse_args() -> Config {
         durability: DurabilityConfig::Cache,
         tests: None,
         levels: DEFAULT_LEVELS.to_vec(),
+        vector_levels: DEFAULT_VECTOR_LEVELS.to_vec(),
         csv: false,
+        markdown: false,
         quiet: false,
+        forever: false,
+        wal_focus: false,
+        cgroup: false,
+        store: None,
     };
 
+    let mut unknown = Vec::new();
+    let mut strict = false;
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
@@ -359,12 +797,33 @@ fn parse_args() -> Config {
                     .filter_map(|s| s.trim().parse().ok())
                     .collect();
             }
+            "--vector-levels" => {
+                i += 1;
+                config.vector_levels = args[i]
+                    .split(',')
+                    .filter_map(|s| s.trim().parse().ok())
+                    .collect();
+            }
             "--csv" => config.csv = true,
+            "--markdown" => config.markdown = true,
             "-q" => config.quiet = true,
-            _ => {}
+            "--forever" => config.forever = true,
+            "--wal-focus" => config.wal_focus = true,
+            "--cgroup" => config.cgroup = true,
+            "--store" => {
+                i += 1;
+                config.store = args.get(i).cloned();
+            }
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
         }
         i += 1;
     }
+    check_unknown_args(&unknown, strict);
 
     config
 }
@@ -385,22 +844,168 @@ fn test_is_selected(name: &str, filter: &Option<Vec<String>>) -> bool {
 const ALL_TESTS: &[&str] = &[
     "kv_put",
     "kv_get",
+    "deleted_kv_get",
+    "compacted_kv_get",
     "kv_delete",
     "kv_list",
     "state_set",
     "state_read",
     "event_append",
     "event_read",
+    "json_get",
+    "json_set_path",
+    "json_list",
+    "vector_upsert",
+    "vector_search",
 ];
 
+/// Fill levels for `test_name`: vector tests use `--vector-levels`.
+fn levels_for<'a>(config: &'a Config, test_name: &str) -> &'a [usize] {
+    if is_vector_test(test_name) {
+        &config.vector_levels
+    } else {
+        &config.levels
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
 
+/// Run one test at one fill level.
+fn run_test(test_name: &str, config: &Config, level: usize) -> FillResult {
+    // kv_list and json_list use a fresh database per fill level
+    match test_name {
+        "kv_list" => return bench_kv_list(config.durability, config.ops, level),
+        "json_list" => return bench_json_list(config.durability, config.ops, level),
+        _ => {}
+    }
+
+    let db = create_db(config.durability);
+    let cgroup_before = if config.cgroup { cgroup_memory() } else { None };
+    fill_for_test(&db, test_name, level);
+    let cgroup_after = if config.cgroup { cgroup_memory() } else { None };
+    let rss_mb = rss_after_fill_mb();
+
+    let mut result = match test_name {
+        "kv_put" => bench_kv_put(&db, config.ops, level),
+        "kv_get" => bench_kv_get(&db, config.ops, level),
+        "deleted_kv_get" => bench_deleted_kv_get(&db, test_name, config.ops, level, false),
+        "compacted_kv_get" => bench_deleted_kv_get(&db, test_name, config.ops, level, true),
+        "kv_delete" => bench_kv_delete(&db, config.ops, level),
+        "state_set" => bench_state_set(&db, config.ops, level),
+        "state_read" => bench_state_read(&db, config.ops, level),
+        "event_append" => bench_event_append(&db, config.ops, level),
+        "event_read" => bench_event_read(&db, config.ops, level),
+        "json_get" => bench_json_get(&db, config.ops, level),
+        "json_set_path" => bench_json_set_path(&db, config.ops, level),
+        "vector_upsert" => bench_vector_upsert(&db, config.ops, level),
+        "vector_search" => bench_vector_search(&db, config.ops, level),
+        _ => unreachable!(),
+    };
+    result.cgroup_fill = cgroup_before.zip(cgroup_after);
+    result.rss_mb = rss_mb;
+    result
+}
+
+/// `--cgroup`: report usage after a fill, and project the largest configured
+/// fill level from the per-key memory growth of this one.
+fn report_cgroup_after_fill(config: &Config, r: &FillResult) {
+    report_cgroup_memory(&format!("{} @ {}", r.name, fmt_num(r.fill_level as u64)));
+
+    let Some((before, after)) = r.cgroup_fill else {
+        return;
+    };
+    let max_level = levels_for(config, &r.name).iter().copied().max().unwrap_or(0);
+    if r.fill_level == 0 || max_level <= r.fill_level {
+        return;
+    }
+    let per_key = after.usage_bytes.saturating_sub(before.usage_bytes) / r.fill_level as u64;
+    let projected = after.usage_bytes + per_key * (max_level - r.fill_level) as u64;
+    warn_if_projected_over_cgroup(&format!("fill level {}", fmt_num(max_level as u64)), projected);
+}
+
+/// `--cgroup` with no limit in effect: say so once instead of staying silent.
+fn note_missing_cgroup_limit(config: &Config) {
+    if config.cgroup && cgroup_memory().is_none() {
+        eprintln!("--cgroup: no cgroup memory limit detected; usage will not be reported");
+        eprintln!();
+    }
+}
+
+/// `--forever`: hammer the single selected test until Ctrl-C.
+fn run_forever_mode(config: &Config) {
+    let selected: Vec<&str> = ALL_TESTS
+        .iter()
+        .copied()
+        .filter(|t| test_is_selected(t, &config.tests))
+        .collect();
+    if config.tests.is_none() || selected.len() != 1 {
+        eprintln!(
+            "--forever requires -t selecting exactly one test (one of: {})",
+            ALL_TESTS.join(", ")
+        );
+        std::process::exit(2);
+    }
+    let test_name = selected[0];
+    let level = levels_for(config, test_name).first().copied().unwrap_or(0);
+
+    eprintln!("=== StrataDB Fill-Level Benchmark (forever) ===");
+    eprintln!(
+        "Test: {}, fill level {}, {} bytes payload, {} mode",
+        test_name,
+        fmt_num(level as u64),
+        BENCH_VALUE_SIZE,
+        config.durability.label()
+    );
+    eprintln!("PID {} — press Ctrl-C to stop.", std::process::id());
+    eprintln!();
+
+    FOREVER.store(true, Ordering::SeqCst);
+    CGROUP.store(config.cgroup, Ordering::SeqCst);
+    install_sigint_handler();
+    note_missing_cgroup_limit(config);
+
+    let result = run_test(test_name, config, level);
+
+    eprintln!();
+    eprintln!("--- {} ---", test_name);
+    print_table_header();
+    print_table_row(&result);
+    eprintln!();
+    eprintln!(
+        "{} ops in {:.1}s, peak RSS {}",
+        fmt_num(result.total_ops as u64),
+        result.elapsed.as_secs_f64(),
+        fmt_mb(peak_rss_bytes())
+    );
+    if config.cgroup {
+        report_cgroup_memory(test_name);
+    }
+}
+
 fn main() {
     let config = parse_args();
     print_hardware_info();
 
+    if config.forever {
+        run_forever_mode(&config);
+        return;
+    }
+
+    #[cfg(feature = "sqlite-compare")]
+    let store = config.store.as_ref().map(|path| {
+        ResultStore::open(path, "fill_level", &hardware_summary()).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        })
+    });
+    #[cfg(not(feature = "sqlite-compare"))]
+    if config.store.is_some() {
+        eprintln!("error: --store requires building with --features sqlite-compare");
+        std::process::exit(2);
+    }
+
     if !config.csv {
         eprintln!("=== StrataDB Fill-Level Benchmark ===");
         eprintln!("Measures operation latency as database size grows.");
@@ -413,12 +1018,23 @@ fn main() {
             "Fill levels: {:?}",
             config.levels
         );
+        if ALL_TESTS
+            .iter()
+            .any(|t| is_vector_test(t) && test_is_selected(t, &config.tests))
+        {
+            eprintln!("Vector collection sizes: {:?}", config.vector_levels);
+        }
+        eprintln!("rss_mb(abs) is process peak RSS after each fill: a high-water mark, not a delta.");
         eprintln!();
     }
 
-    if config.csv {
+    if config.csv && !config.wal_focus {
         print_csv_header();
     }
+    note_missing_cgroup_limit(&config);
+
+    let mut wal_rows = Vec::new();
+    let mut markdown_tables = Vec::new();
 
     for test_name in ALL_TESTS {
         if !test_is_selected(test_name, &config.tests) {
@@ -427,44 +1043,38 @@ fn main() {
 
         let mut results = Vec::new();
 
-        for &level in &config.levels {
+        for &level in levels_for(&config, test_name) {
             if !config.csv && !config.quiet {
                 eprint!("  populating {} fill keys for {}...", fmt_num(level as u64), test_name);
             }
 
-            // kv_list uses a fresh database per fill level
-            if *test_name == "kv_list" {
-                let result = bench_kv_list(config.durability, config.ops, level);
-                if !config.csv && !config.quiet {
-                    eprintln!(" done");
-                }
-                results.push(result);
-                continue;
-            }
-
-            let db = create_db(config.durability);
-            fill_database(&db, level);
-
+            let result = run_test(test_name, &config, level);
             if !config.csv && !config.quiet {
                 eprintln!(" done");
             }
+            if config.cgroup {
+                report_cgroup_after_fill(&config, &result);
+            }
 
-            let result = match *test_name {
-                "kv_put" => bench_kv_put(&db, config.ops, level),
-                "kv_get" => bench_kv_get(&db, config.ops, level),
-                "kv_delete" => bench_kv_delete(&db, config.ops, level),
-                "state_set" => bench_state_set(&db, config.ops, level),
-                "state_read" => bench_state_read(&db, config.ops, level),
-                "event_append" => bench_event_append(&db, config.ops, level),
-                "event_read" => bench_event_read(&db, config.ops, level),
-                _ => unreachable!(),
-            };
+            #[cfg(feature = "sqlite-compare")]
+            if let Some(store) = store.as_ref() {
+                store_result(store, &result, config.durability);
+            }
 
             results.push(result);
         }
 
         // Output results
-        if config.csv {
+        if config.wal_focus {
+            wal_rows.extend(results.into_iter().map(|r| WalRow {
+                op: format!("{} @ {}", r.name, fmt_num(r.fill_level as u64)),
+                mode: config.durability,
+                iterations: r.total_ops as u64,
+                delta: r.wal,
+            }));
+        } else if config.markdown {
+            markdown_tables.push((*test_name, results.iter().map(markdown_row).collect()));
+        } else if config.csv {
             for r in &results {
                 print_csv_row(r);
             }
@@ -483,6 +1093,12 @@ fn main() {
         }
     }
 
+    if config.wal_focus {
+        print_wal_focus_table(&mut wal_rows);
+    } else if config.markdown {
+        print_markdown(&config, &markdown_tables);
+    }
+
     if !config.csv {
         eprintln!("=== Benchmark complete ===");
     }
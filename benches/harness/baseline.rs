@@ -0,0 +1,259 @@
+//! Baseline comparison for the Criterion benches' percentile reports.
+//!
+//! Record a baseline with the NDJSON reporter, then point a later run at it:
+//!
+//! ```text
+//! STRATA_BENCH_JSON=1 cargo bench --bench kv > baseline.json
+//! STRATA_BENCH_BASELINE=baseline.json cargo bench --bench kv
+//! ```
+//!
+//! Every `report_percentiles` call is compared by label against the baseline
+//! and prints the p50/p99 deltas, marked `[REGRESSION]` or `[IMPROVED]` when
+//! either moves by more than `STRATA_BENCH_REGRESSION_THRESHOLD` percent
+//! (default 10). `check_baseline`, run as the last group in `criterion_main!`,
+//! exits with status 1 if anything regressed. Without `STRATA_BENCH_BASELINE`
+//! all of this is a no-op.
+//!
+//! This is an environment variable rather than a `--baseline` flag because
+//! Criterion parses the command line and already owns `--baseline` (a saved
+//! Criterion baseline name).
+//!
+//! Lines in the baseline file that do not start with `{` are skipped, so
+//! Criterion's own stdout can be captured along with the NDJSON.
+
+use super::Percentiles;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Default percent change in p50 or p99 that counts as a regression or
+/// improvement.
+pub const DEFAULT_REGRESSION_THRESHOLD_PCT: f64 = 10.0;
+
+/// The fields of a `STRATA_BENCH_JSON` line that the comparison needs.
+#[derive(serde::Deserialize)]
+struct BaselineRecord {
+    label: String,
+    p50_ns: u64,
+    p99_ns: u64,
+}
+
+/// Recorded p50/p99 per label.
+pub struct Baseline {
+    path: String,
+    /// label -> (p50, p99)
+    labels: HashMap<String, (Duration, Duration)>,
+}
+
+impl Baseline {
+    /// Load a baseline recorded with `STRATA_BENCH_JSON=1`.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read baseline file {}: {}", path, e))?;
+        Self::parse(path, &text)
+    }
+
+    fn parse(path: &str, text: &str) -> Result<Self, String> {
+        let mut labels = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if !line.starts_with('{') {
+                continue;
+            }
+            let record: BaselineRecord = serde_json::from_str(line)
+                .map_err(|e| format!("invalid baseline file {} line {}: {}", path, i + 1, e))?;
+            // A label recorded twice keeps its last measurement
+            labels.insert(
+                record.label,
+                (Duration::from_nanos(record.p50_ns), Duration::from_nanos(record.p99_ns)),
+            );
+        }
+        if labels.is_empty() {
+            return Err(format!(
+                "baseline file {} has no percentile records (record it with STRATA_BENCH_JSON=1)",
+                path
+            ));
+        }
+        Ok(Self { path: path.to_string(), labels })
+    }
+}
+
+/// Whether a label moved past the threshold, judged on p50 and p99.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regression,
+    Improved,
+    Unchanged,
+}
+
+/// Percent change from `base` to `now`; zero when there is no base to
+/// compare against.
+fn pct_change(base: Duration, now: Duration) -> f64 {
+    let base = base.as_nanos() as f64;
+    if base == 0.0 {
+        return 0.0;
+    }
+    (now.as_nanos() as f64 - base) / base * 100.0
+}
+
+/// A regression on either percentile wins over an improvement on the other.
+fn verdict(p50_pct: f64, p99_pct: f64, threshold_pct: f64) -> Verdict {
+    if p50_pct > threshold_pct || p99_pct > threshold_pct {
+        Verdict::Regression
+    } else if p50_pct < -threshold_pct || p99_pct < -threshold_pct {
+        Verdict::Improved
+    } else {
+        Verdict::Unchanged
+    }
+}
+
+/// Regression threshold: `STRATA_BENCH_REGRESSION_THRESHOLD` (percent) if
+/// set and valid, else `DEFAULT_REGRESSION_THRESHOLD_PCT`.
+pub fn regression_threshold_pct() -> f64 {
+    std::env::var("STRATA_BENCH_REGRESSION_THRESHOLD")
+        .ok()
+        .and_then(|s| s.trim().trim_end_matches('%').parse::<f64>().ok())
+        .filter(|t| t.is_finite() && *t >= 0.0)
+        .unwrap_or(DEFAULT_REGRESSION_THRESHOLD_PCT)
+}
+
+/// The loaded baseline plus what this run has compared against it.
+struct BaselineState {
+    baseline: Baseline,
+    threshold_pct: f64,
+    compared: usize,
+    /// Labels measured this run that the baseline does not have.
+    missing: usize,
+    regressions: Vec<String>,
+    improvements: usize,
+}
+
+/// `STRATA_BENCH_BASELINE`, loaded once per process. A baseline that cannot
+/// be loaded is fatal: silently skipping the gate would let CI pass.
+fn state() -> Option<&'static Mutex<BaselineState>> {
+    static STATE: OnceLock<Option<Mutex<BaselineState>>> = OnceLock::new();
+    STATE
+        .get_or_init(|| {
+            let path = std::env::var("STRATA_BENCH_BASELINE").ok()?;
+            let baseline = Baseline::load(path.trim()).unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                std::process::exit(2);
+            });
+            Some(Mutex::new(BaselineState {
+                baseline,
+                threshold_pct: regression_threshold_pct(),
+                compared: 0,
+                missing: 0,
+                regressions: Vec::new(),
+                improvements: 0,
+            }))
+        })
+        .as_ref()
+}
+
+/// Compare one measurement against the baseline and print the deltas.
+/// A no-op without `STRATA_BENCH_BASELINE`. `report_percentiles` calls this.
+pub fn report_baseline_delta(label: &str, p: &Percentiles) {
+    let Some(state) = state() else {
+        return;
+    };
+    let mut state = state.lock().unwrap();
+    let Some(&(base_p50, base_p99)) = state.baseline.labels.get(label) else {
+        state.missing += 1;
+        eprintln!("  {:<45} vs baseline: (not in baseline)", label);
+        return;
+    };
+
+    let p50_pct = pct_change(base_p50, p.p50);
+    let p99_pct = pct_change(base_p99, p.p99);
+    let verdict = verdict(p50_pct, p99_pct, state.threshold_pct);
+    state.compared += 1;
+    let marker = match verdict {
+        Verdict::Regression => {
+            state.regressions.push(format!(
+                "{} (p50 {:+.1}%, p99 {:+.1}%)",
+                label, p50_pct, p99_pct
+            ));
+            " [REGRESSION]"
+        }
+        Verdict::Improved => {
+            state.improvements += 1;
+            " [IMPROVED]"
+        }
+        Verdict::Unchanged => "",
+    };
+    eprintln!(
+        "  {:<45} vs baseline: p50 {:+7.1}%  p99 {:+7.1}%{}",
+        label, p50_pct, p99_pct, marker
+    );
+}
+
+/// Print the baseline summary and exit with status 1 if any label regressed.
+/// Meant as the last group in `criterion_main!`; a no-op without
+/// `STRATA_BENCH_BASELINE`.
+pub fn check_baseline() {
+    let Some(state) = state() else {
+        return;
+    };
+    let state = state.lock().unwrap();
+    eprintln!();
+    eprintln!(
+        "--- baseline comparison vs {} (threshold {}%) ---",
+        state.baseline.path, state.threshold_pct
+    );
+    eprintln!(
+        "{} compared, {} improved, {} regressed, {} not in baseline",
+        state.compared,
+        state.improvements,
+        state.regressions.len(),
+        state.missing
+    );
+    if state.regressions.is_empty() {
+        eprintln!();
+        return;
+    }
+    eprintln!("FAIL: {} regression(s):", state.regressions.len());
+    for r in &state.regressions {
+        eprintln!("  {}", r);
+    }
+    eprintln!();
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pct_change, verdict, Baseline, Verdict};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_skips_non_json_lines() {
+        let text = "\
+Benchmarking kv/get: Warming up
+{\"label\":\"kv/get/cache\",\"samples\":1000,\"p50_ns\":100,\"p99_ns\":400}
+kv/get time: [1.0 us 1.1 us 1.2 us]
+{\"label\":\"kv/put/cache\",\"samples\":1000,\"p50_ns\":200,\"p99_ns\":900}
+";
+        let b = Baseline::parse("test.json", text).unwrap();
+        assert_eq!(b.labels.len(), 2);
+        assert_eq!(
+            b.labels["kv/get/cache"],
+            (Duration::from_nanos(100), Duration::from_nanos(400))
+        );
+        assert!(Baseline::parse("empty.json", "no records here\n").is_err());
+        assert!(Baseline::parse("bad.json", "{\"label\": 3}\n").is_err());
+    }
+
+    #[test]
+    fn test_verdict_thresholds() {
+        let us = Duration::from_micros;
+        assert_eq!(pct_change(us(100), us(125)), 25.0);
+        assert_eq!(pct_change(Duration::ZERO, us(5)), 0.0);
+
+        assert_eq!(verdict(5.0, -5.0, 10.0), Verdict::Unchanged);
+        assert_eq!(verdict(12.0, 0.0, 10.0), Verdict::Regression);
+        assert_eq!(verdict(0.0, 12.0, 10.0), Verdict::Regression);
+        assert_eq!(verdict(-15.0, 0.0, 10.0), Verdict::Improved);
+        // A p99 regression is not hidden by a p50 improvement
+        assert_eq!(verdict(-30.0, 11.0, 10.0), Verdict::Regression);
+    }
+}
@@ -0,0 +1,146 @@
+//! Absolute p99 latency budgets for SLA-style regression gates.
+//!
+//! A budget file is TOML with one table per durability mode, mapping an
+//! operation name to its p99 budget in microseconds:
+//!
+//! ```toml
+//! [cache]
+//! GET = 50
+//! SET = 80
+//!
+//! [always]
+//! SET = 2500.0
+//! ```
+//!
+//! Operation names must match the benchmark's result names exactly.
+
+use super::scaling::fmt_duration;
+use super::DurabilityConfig;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Per-(mode, operation) p99 budgets loaded from a TOML file.
+pub struct LatencyBudgets {
+    /// mode label -> operation -> p99 budget in microseconds
+    budgets: HashMap<String, HashMap<String, f64>>,
+}
+
+impl LatencyBudgets {
+    /// Load and validate a budget file.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read budget file {}: {}", path, e))?;
+        let budgets: HashMap<String, HashMap<String, f64>> = toml::from_str(&text)
+            .map_err(|e| format!("invalid budget file {}: {}", path, e))?;
+
+        for (mode, ops) in &budgets {
+            if !DurabilityConfig::ALL.iter().any(|m| m.label() == mode) {
+                return Err(format!(
+                    "invalid budget file {}: unknown mode [{}] (expected cache, standard or always)",
+                    path, mode
+                ));
+            }
+            for (op, us) in ops {
+                if !us.is_finite() || *us <= 0.0 {
+                    return Err(format!(
+                        "invalid budget file {}: budget for {}/{} must be a positive number",
+                        path, mode, op
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { budgets })
+    }
+
+    /// The p99 budget for `op` in `mode`, if one is configured.
+    pub fn budget_for(&self, op: &str, mode: DurabilityConfig) -> Option<Duration> {
+        self.budgets
+            .get(mode.label())
+            .and_then(|ops| ops.get(op))
+            .map(|us| Duration::from_secs_f64(us / 1e6))
+    }
+
+    /// Number of budgeted (mode, operation) pairs.
+    pub fn count(&self) -> usize {
+        self.budgets.values().map(|ops| ops.len()).sum()
+    }
+}
+
+/// Collects measured p99s and compares them against their budgets.
+pub struct BudgetChecker {
+    budgets: LatencyBudgets,
+    /// (op, mode, actual p99, budget)
+    checked: Vec<(String, DurabilityConfig, Duration, Duration)>,
+}
+
+impl BudgetChecker {
+    pub fn new(budgets: LatencyBudgets) -> Self {
+        Self {
+            budgets,
+            checked: Vec::new(),
+        }
+    }
+
+    /// Record a measured p99. Operations without a budget are ignored.
+    pub fn record(&mut self, op: &str, mode: DurabilityConfig, p99: Duration) {
+        if let Some(budget) = self.budgets.budget_for(op, mode) {
+            self.checked.push((op.to_string(), mode, p99, budget));
+        }
+    }
+
+    /// Print actual-vs-budget for every budgeted operation that ran, list any
+    /// budgets that were never exercised, and return `true` if all passed.
+    pub fn report(&self) -> bool {
+        eprintln!("--- p99 latency budgets ---");
+        eprintln!(
+            "{:<24} {:<10} {:>12} {:>12} {:>8}  status",
+            "operation", "mode", "actual", "budget", "used"
+        );
+
+        let mut failed = Vec::new();
+        for (op, mode, actual, budget) in &self.checked {
+            let used = actual.as_secs_f64() / budget.as_secs_f64() * 100.0;
+            let ok = actual <= budget;
+            eprintln!(
+                "{:<24} {:<10} {:>12} {:>12} {:>7.0}%  {}",
+                op,
+                mode.label(),
+                fmt_duration(*actual),
+                fmt_duration(*budget),
+                used,
+                if ok { "ok" } else { "OVER" }
+            );
+            if !ok {
+                failed.push(format!("{}/{}", op, mode.label()));
+            }
+        }
+
+        // An op measured more than once still covers one budget
+        let ran: HashSet<(&str, &str)> = self
+            .checked
+            .iter()
+            .map(|(op, mode, _, _)| (op.as_str(), mode.label()))
+            .collect();
+        let unchecked = self.budgets.count().saturating_sub(ran.len());
+        if unchecked > 0 {
+            eprintln!(
+                "({} budgeted operation(s) did not run; check names and -t/--durability filters)",
+                unchecked
+            );
+        }
+        eprintln!();
+
+        if failed.is_empty() {
+            eprintln!("All {} budgeted operation(s) within p99 budget", self.checked.len());
+        } else {
+            eprintln!("FAIL: {} operation(s) over p99 budget:", failed.len());
+            for name in &failed {
+                eprintln!("  {}", name);
+            }
+        }
+        eprintln!();
+
+        failed.is_empty()
+    }
+}
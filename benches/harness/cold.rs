@@ -0,0 +1,157 @@
+//! Cold-cache reads: reopen a disk database and then evict its files from
+//! the OS page cache, so the next reads can only come from memory the engine
+//! kept itself or from the disk.
+//!
+//! Enabled with `STRATA_BENCH_COLD=1` (Criterion owns the command line, so
+//! this cannot be a `--cold` flag). Eviction happens after the reopen, since
+//! opening replays and reads the same files and would pull them straight back
+//! into the cache. On 64-bit Linux each file under the data directory is
+//! fsynced and dropped with `posix_fadvise(POSIX_FADV_DONTNEED)`; elsewhere,
+//! or if that fails, a large dummy allocation is touched to push the cache
+//! out, which is best effort. Cache-mode databases have no files and are
+//! never cold.
+//!
+//! Evicting the page cache does not evict whatever the engine loaded into its
+//! own memory during recovery, so a cold pass is only cold if it actually
+//! reads from disk. [`disk_read_bytes`] lets the caller check.
+
+use super::{read_total_ram_gb, BenchDb};
+use std::path::Path;
+use std::sync::OnceLock;
+use stratadb::Strata;
+
+/// Upper bound for the memory-pressure fallback, so it cannot push a large
+/// machine into swap.
+const EVICT_PRESSURE_MAX_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Whether `STRATA_BENCH_COLD=1` asks for cold-cache measurement passes.
+pub fn cold_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("STRATA_BENCH_COLD").is_ok_and(|v| v.trim() == "1"))
+}
+
+/// How the page cache was evicted for a cold pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eviction {
+    /// `posix_fadvise(DONTNEED)` succeeded on this many files.
+    Fadvise(usize),
+    /// This many bytes of anonymous memory were touched.
+    Pressure(u64),
+}
+
+impl std::fmt::Display for Eviction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fadvise(files) => write!(f, "posix_fadvise(DONTNEED) on {} file(s)", files),
+            Self::Pressure(bytes) => write!(
+                f,
+                "{} MB memory-pressure fallback (best effort)",
+                bytes / (1024 * 1024)
+            ),
+        }
+    }
+}
+
+/// Flush and close `db`, open it again, then evict its files from the page
+/// cache. A cache-mode database has nothing to evict and is handed back
+/// unchanged as the `Err`.
+pub fn reopen_cold(db: BenchDb) -> Result<(BenchDb, Eviction), BenchDb> {
+    if db.temp_dir.is_none() {
+        return Err(db);
+    }
+    db.db.flush().expect("flush before cold reopen failed");
+    let BenchDb { db: strata, temp_dir } = db;
+    // Close first, so the caches of the previous instance are gone
+    drop(strata);
+
+    let dir = temp_dir.as_ref().expect("checked above").path();
+    let strata = Strata::open(dir).expect("failed to reopen database cold");
+    // Evict only now: recovery read these files back into the page cache
+    let eviction = evict_page_cache(dir);
+    note_eviction(eviction);
+    Ok((BenchDb { db: strata, temp_dir }, eviction))
+}
+
+/// Bytes this process has caused to be read from storage (`read_bytes` in
+/// `/proc/self/io`). Page-cache hits do not count, so a cold pass with no
+/// growth here never touched the disk. `None` where procfs is unavailable.
+pub fn disk_read_bytes() -> Option<u64> {
+    let io = std::fs::read_to_string("/proc/self/io").ok()?;
+    io.lines()
+        .find_map(|line| line.strip_prefix("read_bytes:"))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Print how the cache was evicted once per process, so a run that silently
+/// fell back to memory pressure is visible.
+fn note_eviction(eviction: Eviction) {
+    static NOTED: OnceLock<()> = OnceLock::new();
+    NOTED.get_or_init(|| eprintln!("  cold reads: page cache evicted via {}", eviction));
+}
+
+/// Evict every file under `dir` from the page cache, falling back to memory
+/// pressure when `posix_fadvise` is unavailable or fails.
+fn evict_page_cache(dir: &Path) -> Eviction {
+    match fadvise_dontneed_dir(dir) {
+        Some(files) => Eviction::Fadvise(files),
+        None => Eviction::Pressure(apply_memory_pressure()),
+    }
+}
+
+/// `posix_fadvise(DONTNEED)` each file under `dir`; `None` if any file
+/// could not be dropped.
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+fn fadvise_dontneed_dir(dir: &Path) -> Option<usize> {
+    let mut files = 0;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let meta = entry.metadata().ok()?;
+        if meta.is_dir() {
+            files += fadvise_dontneed_dir(&path)?;
+        } else if meta.is_file() {
+            fadvise_dontneed(&path)?;
+            files += 1;
+        }
+    }
+    Some(files)
+}
+
+#[cfg(not(all(target_os = "linux", target_pointer_width = "64")))]
+fn fadvise_dontneed_dir(_dir: &Path) -> Option<usize> {
+    None
+}
+
+/// Call posix_fadvise(fd, 0, 0, POSIX_FADV_DONTNEED) without depending on
+/// libc crate. Dirty pages are not dropped, so the file is fsynced first.
+///
+/// POSIX_FADV_DONTNEED = 4 on Linux; off_t is 64-bit on 64-bit targets.
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+fn fadvise_dontneed(path: &Path) -> Option<()> {
+    use std::os::unix::io::AsRawFd;
+
+    const POSIX_FADV_DONTNEED: i32 = 4;
+    unsafe extern "C" {
+        fn posix_fadvise(fd: i32, offset: i64, len: i64, advice: i32) -> i32;
+    }
+
+    let file = std::fs::File::open(path).ok()?;
+    file.sync_all().ok()?;
+    // SAFETY: the fd is open for the duration of the call; offset 0 with
+    // len 0 covers the whole file. Returns an error number, 0 on success.
+    let rc = unsafe { posix_fadvise(file.as_raw_fd(), 0, 0, POSIX_FADV_DONTNEED) };
+    (rc == 0).then_some(())
+}
+
+/// Allocate and touch half of physical RAM (capped) so the kernel reclaims
+/// page cache to satisfy it, then release it. Returns the bytes touched.
+fn apply_memory_pressure() -> u64 {
+    let ram = read_total_ram_gb().max(1) * 1024 * 1024 * 1024;
+    let bytes = (ram / 2).min(EVICT_PRESSURE_MAX_BYTES);
+    let mut buf = vec![0u8; bytes as usize];
+    // Zeroed allocations may be lazily mapped; write every page
+    for page in buf.chunks_mut(4096) {
+        page[0] = 1;
+    }
+    std::hint::black_box(&buf);
+    bytes
+}
@@ -1,6 +1,9 @@
 //! Process-level metrics collected from /proc (Linux) for scaling benchmarks.
 //!
-//! On non-Linux platforms, all values are zero (graceful fallback).
+//! On macOS, CPU time, page faults, context switches and peak RSS come from
+//! `getrusage`; disk I/O stays zero. On other platforms, all values are zero
+//! (graceful fallback). cgroup memory limits are Linux-only and reported as
+//! absent elsewhere.
 
 /// Snapshot of process-level metrics at a point in time.
 #[derive(Debug, Clone, Default)]
@@ -9,68 +12,317 @@ pub struct ProcessMetrics {
     pub user_time_ms: u64,
     /// Kernel-mode CPU time in milliseconds (from /proc/self/stat field 15: stime)
     pub system_time_ms: u64,
+    /// Page faults served without disk I/O (from /proc/self/stat field 10: minflt)
+    pub minor_faults: u64,
+    /// Page faults that required disk I/O (from /proc/self/stat field 12: majflt)
+    pub major_faults: u64,
     /// Voluntary context switches (from /proc/self/status)
     pub voluntary_ctx: u64,
     /// Involuntary context switches (from /proc/self/status)
     pub involuntary_ctx: u64,
+    /// Peak resident set size in KiB (from /proc/self/status: VmHWM)
+    pub peak_rss_kb: u64,
+    /// Bytes this process caused to be fetched from storage (from /proc/self/io)
+    pub read_bytes: u64,
+    /// Bytes this process caused to be sent to storage (from /proc/self/io)
+    pub write_bytes: u64,
 }
 
 /// Take a snapshot of current process metrics.
 ///
-/// On Linux, reads `/proc/self/stat` and `/proc/self/status`.
+/// On Linux, reads `/proc/self/stat`, `/proc/self/status` and `/proc/self/io`.
+/// On macOS, calls `getrusage(RUSAGE_SELF)`.
 /// On other platforms, returns zeroed metrics.
 pub fn snapshot_process_metrics() -> ProcessMetrics {
     #[cfg(target_os = "linux")]
     {
-        let (user_time_ms, system_time_ms) = read_proc_stat();
+        let (user_time_ms, system_time_ms, minor_faults, major_faults) = read_proc_stat();
         let (voluntary_ctx, involuntary_ctx) = read_proc_status();
+        let (read_bytes, write_bytes) = read_proc_io();
         ProcessMetrics {
             user_time_ms,
             system_time_ms,
+            minor_faults,
+            major_faults,
             voluntary_ctx,
             involuntary_ctx,
+            peak_rss_kb: read_proc_status_kb("VmHWM:"),
+            read_bytes,
+            write_bytes,
         }
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(target_os = "macos")]
+    {
+        let Some(usage) = macos_rusage() else {
+            return ProcessMetrics::default();
+        };
+        ProcessMetrics {
+            user_time_ms: usage.ru_utime.as_millis(),
+            system_time_ms: usage.ru_stime.as_millis(),
+            minor_faults: usage.ru_minflt.max(0) as u64,
+            major_faults: usage.ru_majflt.max(0) as u64,
+            voluntary_ctx: usage.ru_nvcsw.max(0) as u64,
+            involuntary_ctx: usage.ru_nivcsw.max(0) as u64,
+            peak_rss_kb: usage.ru_maxrss.max(0) as u64 / 1024,
+            ..Default::default()
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
         ProcessMetrics::default()
     }
 }
 
 /// Compute the delta between two snapshots.
+///
+/// Peak RSS is a high-water mark, not a counter, so the result carries the
+/// larger of the two values rather than their difference.
 pub fn delta_process_metrics(before: &ProcessMetrics, after: &ProcessMetrics) -> ProcessMetrics {
     ProcessMetrics {
         user_time_ms: after.user_time_ms.saturating_sub(before.user_time_ms),
         system_time_ms: after.system_time_ms.saturating_sub(before.system_time_ms),
+        minor_faults: after.minor_faults.saturating_sub(before.minor_faults),
+        major_faults: after.major_faults.saturating_sub(before.major_faults),
         voluntary_ctx: after.voluntary_ctx.saturating_sub(before.voluntary_ctx),
         involuntary_ctx: after.involuntary_ctx.saturating_sub(before.involuntary_ctx),
+        peak_rss_kb: after.peak_rss_kb.max(before.peak_rss_kb),
+        read_bytes: after.read_bytes.saturating_sub(before.read_bytes),
+        write_bytes: after.write_bytes.saturating_sub(before.write_bytes),
+    }
+}
+
+/// Current resident set size of this process, in bytes.
+///
+/// Unlike [`peak_rss_bytes`] this can go down, so it shows memory being
+/// released (e.g. after compaction). Linux only (`VmRSS` from
+/// `/proc/self/status`); returns 0 elsewhere or on failure.
+pub fn current_rss_bytes() -> u64 {
+    #[cfg(target_os = "linux")]
+    {
+        read_proc_status_kb("VmRSS:") * 1024
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        0
+    }
+}
+
+/// Peak resident set size (high-water mark) of this process, in bytes.
+///
+/// `ProcessMetrics::peak_rss_kb` from a fresh snapshot, so 0 where that is
+/// unavailable.
+pub fn peak_rss_bytes() -> u64 {
+    snapshot_process_metrics().peak_rss_kb * 1024
+}
+
+/// Memory limit and current usage of the cgroup this process runs in.
+#[derive(Debug, Clone, Copy)]
+pub struct CgroupMemory {
+    pub limit_bytes: u64,
+    pub usage_bytes: u64,
+}
+
+impl CgroupMemory {
+    /// Current usage as a percentage of the limit.
+    pub fn used_pct(&self) -> f64 {
+        self.usage_bytes as f64 / self.limit_bytes as f64 * 100.0
+    }
+}
+
+/// Usage above this fraction of the cgroup limit triggers a warning.
+pub const CGROUP_WARN_PCT: f64 = 80.0;
+
+/// Read the cgroup memory limit and usage.
+///
+/// Returns `None` when no limit is set, on non-Linux platforms, or when the
+/// cgroup files can't be read. Supports cgroup v1 (`memory` controller) and
+/// v2 (`memory.max`). This only observes limits; set them with
+/// `systemd-run -p MemoryMax=...` or `docker run --memory ...`.
+pub fn cgroup_memory() -> Option<CgroupMemory> {
+    #[cfg(target_os = "linux")]
+    {
+        read_cgroup_memory()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Print cgroup usage against its limit, warning when close to it.
+pub fn report_cgroup_memory(label: &str) {
+    let Some(cg) = cgroup_memory() else {
+        return;
+    };
+    eprintln!(
+        "  {:<45} cgroup memory: {} of {} ({:.0}%){}",
+        label,
+        fmt_mb(cg.usage_bytes),
+        fmt_mb(cg.limit_bytes),
+        cg.used_pct(),
+        if cg.used_pct() >= CGROUP_WARN_PCT { "  WARNING: near cgroup limit" } else { "" }
+    );
+}
+
+/// Warn if `projected_bytes` of cgroup usage would exceed the limit, i.e.
+/// the workload is on track to be OOM-killed.
+pub fn warn_if_projected_over_cgroup(label: &str, projected_bytes: u64) {
+    let Some(cg) = cgroup_memory() else {
+        return;
+    };
+    if projected_bytes > cg.limit_bytes {
+        eprintln!(
+            "  WARNING: {} projected to need {} but the cgroup limit is {}; expect OOM",
+            label,
+            fmt_mb(projected_bytes),
+            fmt_mb(cg.limit_bytes)
+        );
+    }
+}
+
+/// cgroup v1 reports "no limit" as a huge page-aligned value near i64::MAX.
+#[cfg(target_os = "linux")]
+const CGROUP_V1_UNLIMITED: u64 = 1 << 60;
+
+#[cfg(target_os = "linux")]
+fn read_cgroup_memory() -> Option<CgroupMemory> {
+    let cgroups = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let read_u64 = |path: String| -> Option<u64> {
+        std::fs::read_to_string(path).ok()?.trim().parse().ok()
+    };
+
+    // cgroup v1: "N:memory:/path" (also present on hybrid v1/v2 hosts)
+    if let Some(path) = cgroups.lines().find_map(|l| {
+        let mut parts = l.splitn(3, ':');
+        let (_, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+        controllers.split(',').any(|c| c == "memory").then_some(path)
+    }) {
+        // Inside a container the host path is usually not mounted; fall back to the root
+        for dir in [format!("/sys/fs/cgroup/memory{}", path), "/sys/fs/cgroup/memory".into()] {
+            if let Some(limit) = read_u64(format!("{}/memory.limit_in_bytes", dir)) {
+                if limit >= CGROUP_V1_UNLIMITED {
+                    return None;
+                }
+                let usage = read_u64(format!("{}/memory.usage_in_bytes", dir)).unwrap_or(0);
+                return Some(CgroupMemory { limit_bytes: limit, usage_bytes: usage });
+            }
+        }
+        return None;
+    }
+
+    // cgroup v2: "0::/path"; "max" means no limit
+    let path = cgroups.lines().find_map(|l| l.strip_prefix("0::"))?;
+    let dir = format!("/sys/fs/cgroup{}", path.trim_end_matches('/'));
+    let limit = read_u64(format!("{}/memory.max", dir))?;
+    let usage = read_u64(format!("{}/memory.current", dir)).unwrap_or(0);
+    Some(CgroupMemory { limit_bytes: limit, usage_bytes: usage })
+}
+
+/// Format a byte count as MB for table display.
+pub fn fmt_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Read a `kB`-valued field (e.g. `VmHWM:`) from /proc/self/status.
+#[cfg(target_os = "linux")]
+fn read_proc_status_kb(field: &str) -> u64 {
+    let Ok(contents) = std::fs::read_to_string("/proc/self/status") else {
+        return 0;
+    };
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|val| val.trim().trim_end_matches("kB").trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// `struct timeval` on macOS (64-bit seconds, 32-bit microseconds).
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct TimeVal {
+    tv_sec: i64,
+    tv_usec: i32,
+}
+
+#[cfg(target_os = "macos")]
+impl TimeVal {
+    fn as_millis(&self) -> u64 {
+        (self.tv_sec.max(0) as u64) * 1000 + (self.tv_usec.max(0) as u64) / 1000
     }
 }
 
-/// Parse /proc/self/stat for user and system CPU time.
+/// Layout of `struct rusage` on macOS (two timevals, then 14 longs).
+#[cfg(target_os = "macos")]
+#[repr(C)]
+#[allow(dead_code)]
+struct RUsage {
+    ru_utime: TimeVal,
+    ru_stime: TimeVal,
+    ru_maxrss: i64,
+    ru_ixrss: i64,
+    ru_idrss: i64,
+    ru_isrss: i64,
+    ru_minflt: i64,
+    ru_majflt: i64,
+    ru_nswap: i64,
+    ru_inblock: i64,
+    ru_oublock: i64,
+    ru_msgsnd: i64,
+    ru_msgrcv: i64,
+    ru_nsignals: i64,
+    ru_nvcsw: i64,
+    ru_nivcsw: i64,
+}
+
+/// Call getrusage(RUSAGE_SELF) without depending on libc crate.
+#[cfg(target_os = "macos")]
+fn macos_rusage() -> Option<RUsage> {
+    const RUSAGE_SELF: i32 = 0;
+    unsafe extern "C" {
+        fn getrusage(who: i32, usage: *mut RUsage) -> i32;
+    }
+
+    let mut usage = std::mem::MaybeUninit::<RUsage>::zeroed();
+    // SAFETY: getrusage writes a full `struct rusage` into the pointer on success.
+    let rc = unsafe { getrusage(RUSAGE_SELF, usage.as_mut_ptr()) };
+    if rc != 0 {
+        return None;
+    }
+    // SAFETY: zero-initialised and filled in by a successful getrusage call.
+    Some(unsafe { usage.assume_init() })
+}
+
+/// Parse /proc/self/stat for user and system CPU time and page faults.
 ///
 /// Fields are space-separated. Field 14 (0-indexed 13) = utime, field 15 (0-indexed 14) = stime.
 /// Values are in clock ticks; we convert to milliseconds using sysconf(_SC_CLK_TCK).
+/// Field 10 = minflt, field 12 = majflt (counts).
+///
+/// Returns (user_ms, sys_ms, minor_faults, major_faults).
 #[cfg(target_os = "linux")]
-fn read_proc_stat() -> (u64, u64) {
+fn read_proc_stat() -> (u64, u64, u64, u64) {
     let Ok(contents) = std::fs::read_to_string("/proc/self/stat") else {
-        return (0, 0);
+        return (0, 0, 0, 0);
     };
 
     // The comm field (field 2) is wrapped in parentheses and may contain spaces,
     // so we find the closing ')' and parse fields after it.
     let Some(close_paren) = contents.rfind(')') else {
-        return (0, 0);
+        return (0, 0, 0, 0);
     };
     let rest = &contents[close_paren + 2..]; // skip ") "
     let fields: Vec<&str> = rest.split_whitespace().collect();
 
     // After the comm field, field indices shift:
-    // field 3 (state) is fields[0], field 14 (utime) is fields[11], field 15 (stime) is fields[12]
+    // field 3 (state) is fields[0], field 10 (minflt) is fields[7], field 12 (majflt) is
+    // fields[9], field 14 (utime) is fields[11], field 15 (stime) is fields[12]
     if fields.len() < 13 {
-        return (0, 0);
+        return (0, 0, 0, 0);
     }
 
+    let minflt: u64 = fields[7].parse().unwrap_or(0);
+    let majflt: u64 = fields[9].parse().unwrap_or(0);
+
     let utime: u64 = fields[11].parse().unwrap_or(0);
     let stime: u64 = fields[12].parse().unwrap_or(0);
 
@@ -78,7 +330,7 @@ fn read_proc_stat() -> (u64, u64) {
     let user_ms = utime * 1000 / ticks_per_sec;
     let sys_ms = stime * 1000 / ticks_per_sec;
 
-    (user_ms, sys_ms)
+    (user_ms, sys_ms, minflt, majflt)
 }
 
 /// Get clock ticks per second via libc sysconf.
@@ -130,22 +382,50 @@ fn read_proc_status() -> (u64, u64) {
     (voluntary, involuntary)
 }
 
+/// Parse /proc/self/io for storage-layer read and write bytes.
+///
+/// `read_bytes`/`write_bytes` count I/O that reached the block layer, unlike
+/// `rchar`/`wchar` which include page-cache hits. The file is absent when the
+/// kernel lacks task I/O accounting; that reads as zero.
+#[cfg(target_os = "linux")]
+fn read_proc_io() -> (u64, u64) {
+    let Ok(contents) = std::fs::read_to_string("/proc/self/io") else {
+        return (0, 0);
+    };
+
+    let mut read_bytes = 0u64;
+    let mut write_bytes = 0u64;
+
+    for line in contents.lines() {
+        if let Some(val) = line.strip_prefix("read_bytes:") {
+            read_bytes = val.trim().parse().unwrap_or(0);
+        } else if let Some(val) = line.strip_prefix("write_bytes:") {
+            write_bytes = val.trim().parse().unwrap_or(0);
+        }
+    }
+
+    (read_bytes, write_bytes)
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
-    use super::{delta_process_metrics, snapshot_process_metrics, ProcessMetrics};
+    use super::{
+        current_rss_bytes, delta_process_metrics, fmt_mb, peak_rss_bytes,
+        snapshot_process_metrics, ProcessMetrics,
+    };
 
     #[test]
     fn test_snapshot_returns_something() {
         let m = snapshot_process_metrics();
-        // On Linux, user_time_ms should be > 0 for any running process
-        #[cfg(target_os = "linux")]
+        // On Linux and macOS, CPU time should be > 0 for any running process
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
         assert!(
             m.user_time_ms > 0 || m.system_time_ms > 0,
-            "Expected nonzero CPU time on Linux"
+            "Expected nonzero CPU time on Linux/macOS"
         );
-        // On non-Linux, should be zeros
-        #[cfg(not(target_os = "linux"))]
+        // Elsewhere, should be zeros
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
         {
             assert_eq!(m.user_time_ms, 0);
             assert_eq!(m.system_time_ms, 0);
@@ -158,20 +438,57 @@ mod tests {
         let before = ProcessMetrics {
             user_time_ms: 100,
             system_time_ms: 20,
+            minor_faults: 1_000,
+            major_faults: 2,
             voluntary_ctx: 50,
             involuntary_ctx: 10,
+            peak_rss_kb: 4_096,
+            read_bytes: 1_000,
+            write_bytes: 4_096,
         };
         let after = ProcessMetrics {
             user_time_ms: 250,
             system_time_ms: 35,
+            minor_faults: 1_800,
+            major_faults: 7,
             voluntary_ctx: 120,
             involuntary_ctx: 25,
+            peak_rss_kb: 8_192,
+            read_bytes: 1_500,
+            write_bytes: 16_384,
         };
         let d = delta_process_metrics(&before, &after);
         assert_eq!(d.user_time_ms, 150);
         assert_eq!(d.system_time_ms, 15);
+        assert_eq!(d.minor_faults, 800);
+        assert_eq!(d.major_faults, 5);
         assert_eq!(d.voluntary_ctx, 70);
         assert_eq!(d.involuntary_ctx, 15);
+        assert_eq!(d.peak_rss_kb, 8_192);
+        assert_eq!(d.read_bytes, 500);
+        assert_eq!(d.write_bytes, 12_288);
+    }
+
+    #[test]
+    fn test_delta_keeps_peak_rss_high_water_mark() {
+        let before = ProcessMetrics {
+            peak_rss_kb: 8_192,
+            ..Default::default()
+        };
+        let after = ProcessMetrics {
+            peak_rss_kb: 4_096,
+            ..Default::default()
+        };
+        assert_eq!(delta_process_metrics(&before, &after).peak_rss_kb, 8_192);
+    }
+
+    #[test]
+    fn test_snapshot_peak_rss() {
+        let m = snapshot_process_metrics();
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        assert!(m.peak_rss_kb > 0, "Expected nonzero peak RSS on Linux/macOS");
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        assert_eq!(m.peak_rss_kb, 0);
     }
 
     #[test]
@@ -184,4 +501,41 @@ mod tests {
         let d = delta_process_metrics(&before, &after);
         assert_eq!(d.user_time_ms, 0); // saturating_sub
     }
+
+    #[test]
+    fn test_peak_rss_nonzero_on_supported_platforms() {
+        let peak = peak_rss_bytes();
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        assert!(peak > 0, "Expected nonzero peak RSS");
+        let _ = peak;
+    }
+
+    #[test]
+    fn test_current_rss_within_peak() {
+        let current = current_rss_bytes();
+        let peak = peak_rss_bytes();
+        #[cfg(target_os = "linux")]
+        assert!(current > 0, "Expected nonzero current RSS on Linux");
+        // Peak is read second and never decreases, so it covers `current`
+        #[cfg(target_os = "linux")]
+        assert!(current <= peak, "current RSS {} above peak {}", current, peak);
+        let _ = (current, peak);
+    }
+
+    #[test]
+    fn test_peak_rss_tracks_allocation() {
+        let before = peak_rss_bytes();
+        let buf = vec![1u8; 64 * 1024 * 1024];
+        std::hint::black_box(&buf);
+        let after = peak_rss_bytes();
+        assert!(after >= before, "peak RSS must never decrease");
+        #[cfg(target_os = "linux")]
+        assert!(after >= 64 * 1024 * 1024, "peak RSS should cover a touched 64MB buffer");
+    }
+
+    #[test]
+    fn test_fmt_mb() {
+        assert_eq!(fmt_mb(0), "0.0 MB");
+        assert_eq!(fmt_mb(3 * 1024 * 1024 / 2), "1.5 MB");
+    }
 }
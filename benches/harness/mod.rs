@@ -3,26 +3,86 @@
 //! Provides database factory, data generators, latency percentile reporting,
 //! and configuration types used across all primitive benchmark files.
 
+pub mod baseline;
+pub mod budget;
+pub mod cold;
 pub mod metrics;
 pub mod scaling;
+#[cfg(feature = "redis-live")]
+pub mod resp;
+#[cfg(feature = "sqlite-compare")]
+pub mod store;
 
 use std::collections::HashMap;
 use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
-use stratadb::{Strata, Value, WalCounters};
+use stratadb::{Command, Output, Strata, Value, WalCounters};
 use tempfile::TempDir;
 
 // =============================================================================
 // Constants
 // =============================================================================
 
-/// Number of entries to pre-populate for read benchmarks.
+/// Default number of entries to pre-populate for read benchmarks. Override
+/// with `STRATA_BENCH_WARMUP`; benches should call `warmup_count()`.
 pub const WARMUP_COUNT: u64 = 10_000;
 
-/// Number of samples for percentile measurement.
+/// Default number of samples for percentile measurement. Override with
+/// `STRATA_BENCH_SAMPLES`; benches should call `percentile_samples()`.
 pub const PERCENTILE_SAMPLES: usize = 1_000;
 
+/// Default coefficient of variation above which `report_percentiles` flags a
+/// measurement as `[NOISY]`. Override with `STRATA_BENCH_CV_THRESHOLD`.
+pub const DEFAULT_CV_THRESHOLD: f64 = 0.5;
+
+/// Below 10,000 samples p99.99 is just the max, so `report_percentiles` prints `-`.
+pub const P9999_MIN_SAMPLES: usize = 10_000;
+
+/// Samples for percentile measurement: `STRATA_BENCH_SAMPLES` if it is a
+/// positive integer, else `PERCENTILE_SAMPLES`. Read once per process.
+pub fn percentile_samples() -> usize {
+    static SAMPLES: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+    *SAMPLES.get_or_init(|| parse_samples(std::env::var("STRATA_BENCH_SAMPLES").ok().as_deref()))
+}
+
+/// Parse a `STRATA_BENCH_SAMPLES` value, falling back (with a warning) on
+/// zero or anything that is not an integer.
+fn parse_samples(value: Option<&str>) -> usize {
+    parse_positive("STRATA_BENCH_SAMPLES", value, PERCENTILE_SAMPLES as u64) as usize
+}
+
+/// Entries to pre-populate for read benchmarks: `STRATA_BENCH_WARMUP` if it
+/// is a positive integer, else `WARMUP_COUNT`. Read once per process. Size it
+/// above or below the CPU caches to probe cache effects.
+pub fn warmup_count() -> u64 {
+    static WARMUP: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+    *WARMUP.get_or_init(|| parse_warmup(std::env::var("STRATA_BENCH_WARMUP").ok().as_deref()))
+}
+
+/// Parse a `STRATA_BENCH_WARMUP` value, with the same fallback as
+/// `parse_samples`.
+fn parse_warmup(value: Option<&str>) -> u64 {
+    parse_positive("STRATA_BENCH_WARMUP", value, WARMUP_COUNT)
+}
+
+/// Parse a positive integer from environment variable `var`, warning and
+/// returning `default` on zero or anything that is not an integer.
+fn parse_positive(var: &str, value: Option<&str>, default: u64) -> u64 {
+    let Some(raw) = value else {
+        return default;
+    };
+    match raw.trim().parse::<u64>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            eprintln!("warning: ignoring {}={:?}; using {}", var, raw, default);
+            default
+        }
+    }
+}
+
 // =============================================================================
 // Hardware Info
 // =============================================================================
@@ -113,6 +173,90 @@ fn read_total_ram_gb() -> u64 {
     0
 }
 
+/// One-line hardware description, used to tag stored results.
+pub fn hardware_summary() -> String {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(0);
+    format!(
+        "{}, {} cores, {} GB, {} ({})",
+        read_cpu_model(),
+        cores,
+        read_total_ram_gb(),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// Print the hardware info as a Markdown preamble on stdout, for `--markdown`
+/// output that is meant to be pasted into a PR or issue.
+pub fn print_hardware_info_markdown() {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(0);
+    println!("**Hardware**");
+    println!();
+    println!("- CPU: {}", read_cpu_model());
+    println!("- Cores: {}", cores);
+    println!("- RAM: {} GB", read_total_ram_gb());
+    println!("- OS: {} ({})", std::env::consts::OS, std::env::consts::ARCH);
+    println!();
+}
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Arguments cargo passes to every bench binary, which the custom-harness
+/// benches accept and ignore.
+const CARGO_BENCH_ARGS: &[&str] = &["--bench"];
+
+/// Warn about arguments a bench's parser did not recognise. With `strict`,
+/// exit with status 2 instead of running with silently defaulted settings.
+pub fn check_unknown_args(unknown: &[String], strict: bool) {
+    let unknown: Vec<&String> = unknown
+        .iter()
+        .filter(|a| !CARGO_BENCH_ARGS.contains(&a.as_str()))
+        .collect();
+    if unknown.is_empty() {
+        return;
+    }
+    let list: Vec<&str> = unknown.iter().map(|a| a.as_str()).collect();
+    if strict {
+        eprintln!("error: unknown arguments: {} (see --help)", list.join(" "));
+        std::process::exit(2);
+    }
+    eprintln!("warning: ignoring unknown arguments: {} (see --help)", list.join(" "));
+}
+
+// =============================================================================
+// Markdown Output
+// =============================================================================
+
+/// Render a GitHub-flavored Markdown table. The first column is left-aligned
+/// (it holds the operation name) and the rest are right-aligned numbers.
+pub fn markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    fn line<S: AsRef<str>>(cells: &[S]) -> String {
+        let cells: Vec<String> = cells.iter().map(|c| c.as_ref().replace('|', "\\|")).collect();
+        format!("| {} |\n", cells.join(" | "))
+    }
+    let align: Vec<&str> = (0..headers.len())
+        .map(|i| if i == 0 { ":---" } else { "---:" })
+        .collect();
+
+    let mut out = line(headers);
+    out.push_str(&format!("| {} |\n", align.join(" | ")));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+/// Print a Markdown table on stdout, followed by a blank line.
+pub fn print_markdown_table(headers: &[&str], rows: &[Vec<String>]) {
+    println!("{}", markdown_table(headers, rows));
+}
+
 // =============================================================================
 // Latency Percentiles
 // =============================================================================
@@ -122,11 +266,49 @@ pub struct Percentiles {
     pub p50: Duration,
     pub p95: Duration,
     pub p99: Duration,
+    /// p99.9; equal to `max` below 1,000 samples.
+    pub p999: Duration,
+    /// p99.99; only meaningful at n >= `P9999_MIN_SAMPLES`.
+    pub p9999: Duration,
     pub min: Duration,
     pub max: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
     pub samples: usize,
 }
 
+impl Percentiles {
+    /// Tail amplification: p99 as a multiple of p50.
+    ///
+    /// Near 1.0 means consistent latency; large values flag jitter.
+    pub fn tail_ratio(&self) -> f64 {
+        let p50 = self.p50.as_nanos();
+        if p50 == 0 {
+            return 0.0;
+        }
+        self.p99.as_nanos() as f64 / p50 as f64
+    }
+
+    /// Coefficient of variation: stddev / mean. Zero if the mean is zero.
+    pub fn cv(&self) -> f64 {
+        let mean = self.mean.as_nanos();
+        if mean == 0 {
+            return 0.0;
+        }
+        self.stddev.as_nanos() as f64 / mean as f64
+    }
+}
+
+/// CV threshold for `[NOISY]`: `STRATA_BENCH_CV_THRESHOLD` if set and valid,
+/// else `DEFAULT_CV_THRESHOLD`.
+pub fn cv_threshold() -> f64 {
+    std::env::var("STRATA_BENCH_CV_THRESHOLD")
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|t| t.is_finite() && *t > 0.0)
+        .unwrap_or(DEFAULT_CV_THRESHOLD)
+}
+
 /// Run `f` for `n` iterations, time each call individually, return percentiles.
 pub fn measure_percentiles<F: FnMut()>(n: usize, mut f: F) -> Percentiles {
     let mut timings = Vec::with_capacity(n);
@@ -135,14 +317,30 @@ pub fn measure_percentiles<F: FnMut()>(n: usize, mut f: F) -> Percentiles {
         f();
         timings.push(start.elapsed());
     }
+    percentiles_from_timings(timings)
+}
+
+/// Percentiles of timings collected by hand, for passes that time only part
+/// of each iteration. `timings` must not be empty.
+pub fn percentiles_from_timings(mut timings: Vec<Duration>) -> Percentiles {
     timings.sort();
     let len = timings.len();
+    let mean_ns = timings.iter().map(|t| t.as_nanos() as f64).sum::<f64>() / len as f64;
+    let variance = timings
+        .iter()
+        .map(|t| (t.as_nanos() as f64 - mean_ns).powi(2))
+        .sum::<f64>()
+        / len as f64;
     Percentiles {
         p50: timings[len * 50 / 100],
         p95: timings[len * 95 / 100],
         p99: timings[len * 99 / 100],
+        p999: timings[(len * 999 / 1000).min(len - 1)],
+        p9999: timings[(len * 9999 / 10000).min(len - 1)],
         min: timings[0],
         max: timings[len - 1],
+        mean: Duration::from_nanos(mean_ns as u64),
+        stddev: Duration::from_nanos(variance.sqrt() as u64),
         samples: len,
     }
 }
@@ -163,16 +361,165 @@ fn fmt_duration(d: Duration) -> String {
 
 /// Print percentiles to stderr in a compact table.
 pub fn report_percentiles(label: &str, p: &Percentiles) {
+    let p9999 = if p.samples >= P9999_MIN_SAMPLES {
+        fmt_duration(p.p9999)
+    } else {
+        "-".to_string()
+    };
+    let threshold = cv_threshold();
+    let noisy = p.cv() > threshold;
     eprintln!(
-        "  {:<45} p50={:<12} p95={:<12} p99={:<12} (n={})",
+        "  {:<45} p50={:<12} p95={:<12} p99={:<12} p99.9={:<12} p99.99={:<12} p99/p50={:<8} (n={}){}",
         label,
         fmt_duration(p.p50),
         fmt_duration(p.p95),
         fmt_duration(p.p99),
+        fmt_duration(p.p999),
+        p9999,
+        format!("{:.2}x", p.tail_ratio()),
         p.samples,
+        if noisy { " [NOISY]" } else { "" },
+    );
+    if noisy {
+        eprintln!(
+            "warning: {} is noisy (CV {:.2} > {:.2}); results may not be reliable",
+            label,
+            p.cv(),
+            threshold
+        );
+    }
+    report_percentiles_json(label, p);
+    baseline::report_baseline_delta(label, p);
+}
+
+/// One NDJSON line written by `report_percentiles_json`. Durations are
+/// integer nanoseconds.
+#[derive(serde::Serialize)]
+struct PercentilesRecord {
+    label: String,
+    samples: usize,
+    min_ns: u64,
+    p50_ns: u64,
+    p95_ns: u64,
+    p99_ns: u64,
+    p999_ns: u64,
+    p9999_ns: u64,
+    max_ns: u64,
+    mean_ns: u64,
+    stddev_ns: u64,
+}
+
+/// Whether `STRATA_BENCH_JSON=1` asks for NDJSON percentiles on stdout.
+fn json_output_enabled() -> bool {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("STRATA_BENCH_JSON").is_ok_and(|v| v.trim() == "1"))
+}
+
+/// With `STRATA_BENCH_JSON=1`, write the percentiles as one JSON object per
+/// line on stdout, for dashboards. A no-op otherwise. `report_percentiles`
+/// calls this, so every bench gets it without changes.
+pub fn report_percentiles_json(label: &str, p: &Percentiles) {
+    if !json_output_enabled() {
+        return;
+    }
+    let ns = |d: Duration| d.as_nanos() as u64;
+    let record = PercentilesRecord {
+        label: label.to_string(),
+        samples: p.samples,
+        min_ns: ns(p.min),
+        p50_ns: ns(p.p50),
+        p95_ns: ns(p.p95),
+        p99_ns: ns(p.p99),
+        p999_ns: ns(p.p999),
+        p9999_ns: ns(p.p9999),
+        max_ns: ns(p.max),
+        mean_ns: ns(p.mean),
+        stddev_ns: ns(p.stddev),
+    };
+    match serde_json::to_string(&record) {
+        Ok(line) => println!("{}", line),
+        Err(e) => eprintln!("warning: cannot serialize percentiles for {}: {}", label, e),
+    }
+}
+
+/// Print scan bandwidth for a list operation: the bytes one call returns
+/// divided by the p50 latency, in MB/s. List calls return keys only, so
+/// `bytes` is the total length of the returned keys, not of their values.
+pub fn report_scan_throughput(label: &str, p: &Percentiles, entries: u64, bytes: u64) {
+    let secs = p.p50.as_secs_f64();
+    let mb_per_sec = if secs > 0.0 {
+        bytes as f64 / secs / 1_000_000.0
+    } else {
+        0.0
+    };
+    eprintln!(
+        "  {:<45} returned={} keys, {} B  {:.1} MB/s of keys (at p50)",
+        label, entries, bytes, mb_per_sec,
     );
 }
 
+// =============================================================================
+// Cache vs Standard Comparison
+// =============================================================================
+
+/// Collects per-mode p50s so read benchmarks can show the cost of persistence
+/// as a single ratio instead of across separate per-mode rows.
+#[derive(Default)]
+pub struct ModeComparison {
+    rows: Vec<(String, Option<Duration>, Option<Duration>)>,
+}
+
+impl ModeComparison {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the p50 for `op` under `mode`. Always-mode results are ignored.
+    pub fn record(&mut self, op: &str, mode: DurabilityConfig, p: &Percentiles) {
+        let idx = match self.rows.iter().position(|(name, _, _)| name == op) {
+            Some(idx) => idx,
+            None => {
+                self.rows.push((op.to_string(), None, None));
+                self.rows.len() - 1
+            }
+        };
+        match mode {
+            DurabilityConfig::Cache => self.rows[idx].1 = Some(p.p50),
+            DurabilityConfig::Standard => self.rows[idx].2 = Some(p.p50),
+            DurabilityConfig::Always => {}
+        }
+    }
+
+    /// Print cache p50, standard p50, and the standard/cache slowdown factor.
+    pub fn report(&self, title: &str) {
+        eprintln!("\n--- Cache vs Standard p50: {} ---", title);
+        eprintln!(
+            "  {:<45} {:<12} {:<12} {:>8}",
+            "operation", "cache", "standard", "slowdown"
+        );
+        for (op, cache, standard) in &self.rows {
+            let (Some(cache), Some(standard)) = (cache, standard) else {
+                continue;
+            };
+            let slowdown = if cache.as_nanos() > 0 {
+                format!(
+                    "{:.2}x",
+                    standard.as_nanos() as f64 / cache.as_nanos() as f64
+                )
+            } else {
+                "-".to_string()
+            };
+            eprintln!(
+                "  {:<45} {:<12} {:<12} {:>8}",
+                op,
+                fmt_duration(*cache),
+                fmt_duration(*standard),
+                slowdown,
+            );
+        }
+    }
+}
+
 // =============================================================================
 // Durability Configuration
 // =============================================================================
@@ -251,6 +598,84 @@ pub fn kv_value_sized(size: ValueSize) -> Value {
     Value::Bytes(vec![0x42; size.byte_count()])
 }
 
+/// How compressible a generated value is. `kv_value_sized` is a single
+/// repeated byte, which hides compression cost on the WAL path.
+#[derive(Debug, Clone, Copy)]
+pub enum ValueEntropy {
+    /// One repeated byte — maximally compressible
+    Constant,
+    /// Half random bytes, half zero runs
+    Mixed,
+    /// PRNG output throughout — incompressible
+    Random,
+}
+
+impl ValueEntropy {
+    pub const ALL: [Self; 3] = [Self::Constant, Self::Mixed, Self::Random];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Constant => "const",
+            Self::Mixed => "mixed",
+            Self::Random => "random",
+        }
+    }
+
+    /// Generate a value of `size` with this entropy.
+    pub fn value(&self, size: ValueSize) -> Value {
+        match self {
+            Self::Constant => kv_value_sized(size),
+            Self::Mixed => kv_value_mixed(size, 0.5),
+            Self::Random => kv_value_random(size),
+        }
+    }
+}
+
+impl fmt::Display for ValueEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Bytes per run in `kv_value_mixed`: each run starts with random bytes and
+/// is zero-padded to this length.
+const MIXED_RUN: usize = 64;
+
+/// Seed for the value PRNG, advanced per value so no two values repeat.
+static VALUE_SEED: AtomicU64 = AtomicU64::new(0x5EED);
+
+/// Fill `buf` with LCG output (same generator as the scaling harness).
+fn fill_random(buf: &mut [u8]) {
+    let mut state = VALUE_SEED.fetch_add(1, Ordering::Relaxed);
+    for chunk in buf.chunks_mut(8) {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        // Fold the high bits down: an LCG's low bits have short periods
+        let bytes = (state ^ (state >> 33)).to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// Generate an incompressible byte value of the given size.
+pub fn kv_value_random(size: ValueSize) -> Value {
+    let mut buf = vec![0u8; size.byte_count()];
+    fill_random(&mut buf);
+    Value::Bytes(buf)
+}
+
+/// Generate a byte value where `entropy_fraction` (0.0..=1.0) of every
+/// 64-byte run is random and the rest is zeros.
+pub fn kv_value_mixed(size: ValueSize, entropy_fraction: f64) -> Value {
+    let random_len = (MIXED_RUN as f64 * entropy_fraction.clamp(0.0, 1.0)).round() as usize;
+    let mut buf = vec![0u8; size.byte_count()];
+    for run in buf.chunks_mut(MIXED_RUN) {
+        let n = random_len.min(run.len());
+        fill_random(&mut run[..n]);
+    }
+    Value::Bytes(buf)
+}
+
 // =============================================================================
 // BenchDb
 // =============================================================================
@@ -258,7 +683,30 @@ pub fn kv_value_sized(size: ValueSize) -> Value {
 /// Database wrapper that keeps temp directories alive for disk-backed modes.
 pub struct BenchDb {
     pub db: Strata,
-    _temp_dir: Option<TempDir>,
+    temp_dir: Option<TempDir>,
+}
+
+impl BenchDb {
+    /// Data directory of a disk-backed database; `None` in cache mode.
+    pub fn path(&self) -> Option<&Path> {
+        self.temp_dir.as_ref().map(TempDir::path)
+    }
+}
+
+/// Total size of the files under `dir`, recursively. Entries that vanish or
+/// can't be read mid-walk count as zero rather than failing the benchmark.
+pub fn dir_size_bytes(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(m) if m.is_dir() => dir_size_bytes(&entry.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
 }
 
 /// Create a database configured for the given durability mode.
@@ -270,7 +718,7 @@ pub fn create_db(config: DurabilityConfig) -> BenchDb {
             let strata = Strata::cache().expect("failed to create cache database");
             BenchDb {
                 db: strata,
-                _temp_dir: None,
+                temp_dir: None,
             }
         }
         DurabilityConfig::Standard => {
@@ -279,7 +727,7 @@ pub fn create_db(config: DurabilityConfig) -> BenchDb {
                 .expect("failed to open standard database");
             BenchDb {
                 db: strata,
-                _temp_dir: Some(temp_dir),
+                temp_dir: Some(temp_dir),
             }
         }
         DurabilityConfig::Always => {
@@ -293,7 +741,7 @@ pub fn create_db(config: DurabilityConfig) -> BenchDb {
                 .expect("failed to open always database");
             BenchDb {
                 db: strata,
-                _temp_dir: Some(temp_dir),
+                temp_dir: Some(temp_dir),
             }
         }
     }
@@ -318,6 +766,71 @@ pub fn kv_key_with_prefix(prefix: &str, i: u64) -> String {
     }
 }
 
+/// Zipf-distributed key indices over `[0, n)`: index 0 is the most popular,
+/// and popularity falls off as `1 / (rank + 1)^theta`.
+///
+/// Uses the closed-form method from Gray et al., "Quickly Generating
+/// Billion-Record Synthetic Databases" (SIGMOD 1994), as in YCSB. Setup is
+/// O(n) to compute the zeta normaliser; each draw is O(1).
+pub struct ZipfKeyGen {
+    pub n: u64,
+    pub theta: f64,
+    state: u64,
+    zetan: f64,
+    alpha: f64,
+    eta: f64,
+}
+
+impl ZipfKeyGen {
+    /// YCSB's default skew: roughly 80% of requests hit 20% of keys or fewer.
+    pub const DEFAULT_THETA: f64 = 0.99;
+
+    /// Create a generator over `n` keys with skew `theta` in (0, 1).
+    pub fn new(n: u64, theta: f64, seed: u64) -> Self {
+        assert!(n >= 2, "ZipfKeyGen needs at least 2 keys");
+        assert!(theta > 0.0 && theta < 1.0, "ZipfKeyGen theta must be in (0, 1)");
+        let zeta = |count: u64| (1..=count).map(|i| 1.0 / (i as f64).powf(theta)).sum::<f64>();
+        let zetan = zeta(n);
+        let zeta2 = zeta(2);
+        Self {
+            n,
+            theta,
+            state: seed,
+            zetan,
+            alpha: 1.0 / (1.0 - theta),
+            eta: (1.0 - (2.0 / n as f64).powf(1.0 - theta)) / (1.0 - zeta2 / zetan),
+        }
+    }
+
+    /// Draw the next key index in `[0, n)`.
+    pub fn next(&mut self) -> u64 {
+        let u = self.next_uniform();
+        let uz = u * self.zetan;
+        if uz < 1.0 {
+            return 0;
+        }
+        if uz < 1.0 + 0.5f64.powf(self.theta) {
+            return 1;
+        }
+        let rank = self.n as f64 * (self.eta * u - self.eta + 1.0).powf(self.alpha);
+        (rank as u64).min(self.n - 1)
+    }
+
+    /// Uniform f64 in [0, 1) from a 64-bit LCG (top 53 bits).
+    fn next_uniform(&mut self) -> f64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Next 100-byte KV key from a Zipf distribution (same format as `kv_key`).
+pub fn kv_key_zipf(gen: &mut ZipfKeyGen) -> String {
+    kv_key(gen.next())
+}
+
 /// Generate a 1KB byte value for KV benchmarks (default size).
 pub fn kv_value() -> Value {
     Value::Bytes(vec![0x42; 1024])
@@ -380,6 +893,25 @@ pub fn json_document(i: u64) -> Value {
     Value::Object(map)
 }
 
+/// Approximate encoded size of a value in bytes, for bandwidth reporting.
+///
+/// Counts payload bytes (string/bytes contents, 8 per number, object keys)
+/// and ignores container framing.
+pub fn approx_value_bytes(v: &Value) -> usize {
+    match v {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Int(_) | Value::Float(_) => 8,
+        Value::String(s) => s.len(),
+        Value::Bytes(b) => b.len(),
+        Value::Array(items) => items.iter().map(approx_value_bytes).sum(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| k.len() + approx_value_bytes(v))
+            .sum(),
+    }
+}
+
 /// Generate a deterministic 128-dimensional vector from an index.
 pub fn vector_128d(i: u64) -> Vec<f32> {
     let seed = i as f32;
@@ -388,6 +920,155 @@ pub fn vector_128d(i: u64) -> Vec<f32> {
         .collect()
 }
 
+// =============================================================================
+// Vector Recall
+// =============================================================================
+
+/// Cosine similarity of `a` and `b`; 0.0 if either is the zero vector.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Exact top-`k` keys of `corpus` by cosine similarity to `query`, best first,
+/// by brute force. The ground truth for recall measurements.
+pub fn exact_top_k(corpus: &[(String, Vec<f32>)], query: &[f32], k: usize) -> Vec<String> {
+    let mut scored: Vec<(f32, &str)> = corpus
+        .iter()
+        .map(|(key, vector)| (cosine_similarity(query, vector), key.as_str()))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(k).map(|(_, key)| key.to_string()).collect()
+}
+
+/// Fraction of `exact` keys present in `got` (recall@k with k = `exact.len()`).
+pub fn recall_at_k(exact: &[String], got: &[String]) -> f64 {
+    if exact.is_empty() {
+        return 1.0;
+    }
+    let hits = exact.iter().filter(|key| got.contains(key)).count();
+    hits as f64 / exact.len() as f64
+}
+
+// =============================================================================
+// Version History Setup
+// =============================================================================
+
+/// Write exactly `depth` versions of `key`, with `value_fn(v)` as the value of
+/// version `v` (0-based, oldest first).
+///
+/// Panics unless `key` is fresh and `kv_getv(key)` reports exactly `depth`
+/// versions afterwards, so a history benchmark can never silently measure a
+/// different depth than it asked for.
+pub fn prepopulate_versions(
+    db: &Strata,
+    key: &str,
+    depth: usize,
+    value_fn: impl Fn(usize) -> Value,
+) {
+    assert!(depth > 0, "prepopulate_versions: depth must be at least 1");
+    assert!(
+        db.kv_getv(key).expect("kv_getv failed").is_none(),
+        "prepopulate_versions: key {:?} already has history",
+        key
+    );
+    let actual = grow_versions(db, key, depth, value_fn);
+    assert_eq!(
+        actual, depth,
+        "prepopulate_versions: {:?} has {} versions after writing {}",
+        key, actual, depth
+    );
+}
+
+/// Overwrite `key` until it holds at least `target` versions, continuing the
+/// numbering of `value_fn` from the versions already there. Returns the
+/// version count `kv_getv` reports afterwards.
+pub fn grow_versions(
+    db: &Strata,
+    key: &str,
+    target: usize,
+    value_fn: impl Fn(usize) -> Value,
+) -> usize {
+    let version_count = || {
+        db.kv_getv(key)
+            .expect("kv_getv failed")
+            .map_or(0, |h| h.len())
+    };
+    for v in version_count()..target {
+        db.kv_put(key, value_fn(v))
+            .expect("grow_versions: kv_put failed");
+    }
+    version_count()
+}
+
+// =============================================================================
+// Batched Reads and Writes
+// =============================================================================
+
+/// Read many keys at once, returning values in request order with `None`
+/// for missing keys.
+///
+/// This is the signature a native engine multi-get should have. Until the
+/// engine exposes one, it issues the gets inside a single session
+/// transaction, so results come from one snapshot.
+pub fn kv_get_many(db: &Strata, keys: &[String]) -> Vec<Option<Value>> {
+    let mut session = db.session();
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .expect("multi-get txn begin failed");
+    let values = keys
+        .iter()
+        .map(|key| {
+            match session
+                .execute(Command::KvGet {
+                    branch: None,
+                    key: key.clone(),
+                })
+                .expect("multi-get kv_get failed")
+            {
+                Output::Maybe(v) => v,
+                _ => panic!("unexpected KvGet output in multi-get"),
+            }
+        })
+        .collect();
+    session.execute(Command::TxnCommit).expect("multi-get txn commit failed");
+    values
+}
+
+/// Write many keys at once, atomically.
+///
+/// Same stand-in as `kv_get_many`: until the engine exposes a native batch
+/// put, it issues one `KvPut` per entry inside a single session transaction,
+/// so the batch shares one commit (and, in `Always` mode, one WAL sync).
+pub fn batch_put(db: &Strata, entries: &[(String, Value)]) {
+    let mut session = db.session();
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .expect("batch put txn begin failed");
+    for (key, value) in entries {
+        session
+            .execute(Command::KvPut {
+                branch: None,
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .expect("batch put kv_put failed");
+    }
+    session.execute(Command::TxnCommit).expect("batch put txn commit failed");
+}
+
 // =============================================================================
 // WAL Counter Helpers
 // =============================================================================
@@ -429,6 +1110,92 @@ pub fn report_counters(label: &str, delta: &WalCounters, iterations: u64) {
     );
 }
 
+/// WAL bytes written per logical payload byte. 1.0 means the WAL wrote
+/// exactly the payload; above that is record framing, keys and metadata
+/// (below it, compression won). Zero when there is no payload to compare to.
+pub fn write_amplification(bytes_written: u64, iterations: u64, payload_bytes: usize) -> f64 {
+    let logical = iterations as f64 * payload_bytes as f64;
+    if logical == 0.0 {
+        return 0.0;
+    }
+    bytes_written as f64 / logical
+}
+
+/// Print a WAL counter delta with bytes/op split into payload and overhead,
+/// plus write amplification, given each operation writes `payload_bytes` of
+/// value data. Overhead is negative when the WAL compresses the payload.
+pub fn report_counters_detailed(
+    label: &str,
+    delta: &WalCounters,
+    iterations: u64,
+    payload_bytes: usize,
+) {
+    if delta.wal_appends == 0 && delta.sync_calls == 0 {
+        return; // Skip for ephemeral mode
+    }
+    report_counters(label, delta, iterations);
+    let bytes_per_op = delta.bytes_written as f64 / iterations as f64;
+    let overhead_per_op = bytes_per_op - payload_bytes as f64;
+    eprintln!(
+        "  {:<45} payload/op={}  overhead/op={:+.0}  write_amp={:.2}x",
+        "",
+        payload_bytes,
+        overhead_per_op,
+        write_amplification(delta.bytes_written, iterations, payload_bytes),
+    );
+}
+
+/// WAL counters for one (operation, mode) pair, for `--wal-focus` reports.
+pub struct WalRow {
+    pub op: String,
+    pub mode: DurabilityConfig,
+    pub iterations: u64,
+    pub delta: WalCounters,
+}
+
+impl WalRow {
+    fn per_op(&self, v: u64) -> f64 {
+        v as f64 / self.iterations.max(1) as f64
+    }
+
+    fn avg_sync_us(&self) -> f64 {
+        if self.delta.sync_calls > 0 {
+            (self.delta.sync_nanos as f64 / self.delta.sync_calls as f64) / 1_000.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Print a storage-efficiency table keyed by (operation, mode), sorted by
+/// bytes/op descending so the most write-amplifying operations come first.
+pub fn print_wal_focus_table(rows: &mut [WalRow]) {
+    rows.sort_by(|a, b| {
+        b.per_op(b.delta.bytes_written)
+            .total_cmp(&a.per_op(a.delta.bytes_written))
+            .then_with(|| a.op.cmp(&b.op))
+    });
+
+    eprintln!();
+    eprintln!("--- WAL Counters (sorted by bytes/op) ---");
+    eprintln!(
+        "  {:<40} {:<9} {:>11} {:>10} {:>11} {:>13}",
+        "operation", "mode", "appends/op", "syncs/op", "bytes/op", "avg_sync_us"
+    );
+    for r in rows.iter() {
+        eprintln!(
+            "  {:<40} {:<9} {:>11.2} {:>10.2} {:>11.0} {:>13.1}",
+            r.op,
+            r.mode.label(),
+            r.per_op(r.delta.wal_appends),
+            r.per_op(r.delta.sync_calls),
+            r.per_op(r.delta.bytes_written),
+            r.avg_sync_us(),
+        );
+    }
+    eprintln!();
+}
+
 /// Run `f` for `n` iterations with WAL counter tracking.
 pub fn measure_with_counters<F: FnMut()>(
     bench_db: &BenchDb,
@@ -441,3 +1208,288 @@ pub fn measure_with_counters<F: FnMut()>(
     let delta = counter_delta(&before, &after);
     (percentiles, delta)
 }
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::{
+        approx_value_bytes, cosine_similarity, dir_size_bytes, event_payload, exact_top_k,
+        json_document, kv_key, kv_key_zipf, kv_value, kv_value_mixed, kv_value_random,
+        kv_value_sized, markdown_table, measure_percentiles, parse_samples, parse_warmup,
+        recall_at_k, state_value, write_amplification, Percentiles, ValueEntropy, ValueSize,
+        ZipfKeyGen, DEFAULT_CV_THRESHOLD, PERCENTILE_SAMPLES, WARMUP_COUNT,
+    };
+    use std::time::Duration;
+    use stratadb::Value;
+
+    fn bytes_len(v: &Value) -> usize {
+        match v {
+            Value::Bytes(b) => b.len(),
+            other => panic!("expected Value::Bytes, got {:?}", other),
+        }
+    }
+
+    /// Nesting depth of objects: a flat object is 1.
+    fn object_depth(v: &Value) -> usize {
+        match v {
+            Value::Object(map) => 1 + map.values().map(object_depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn test_value_size_byte_counts() {
+        assert_eq!(ValueSize::Small.byte_count(), 128);
+        assert_eq!(ValueSize::Medium.byte_count(), 1024);
+        assert_eq!(ValueSize::Large.byte_count(), 8192);
+    }
+
+    #[test]
+    fn test_kv_value_sized_matches_byte_count() {
+        for size in ValueSize::ALL {
+            assert_eq!(bytes_len(&kv_value_sized(size)), size.byte_count(), "{}", size);
+        }
+    }
+
+    fn distinct_bytes(v: &Value) -> usize {
+        let Value::Bytes(b) = v else {
+            panic!("expected Bytes");
+        };
+        let mut seen = [false; 256];
+        b.iter().for_each(|&x| seen[x as usize] = true);
+        seen.iter().filter(|&&s| s).count()
+    }
+
+    #[test]
+    fn test_value_entropy_sizes_and_spread() {
+        for size in ValueSize::ALL {
+            for entropy in ValueEntropy::ALL {
+                let v = entropy.value(size);
+                assert_eq!(bytes_len(&v), size.byte_count(), "{}/{}", size, entropy);
+            }
+        }
+        assert_eq!(distinct_bytes(&ValueEntropy::Constant.value(ValueSize::Large)), 1);
+        // 8KB of uniform bytes covers nearly every byte value
+        assert!(distinct_bytes(&kv_value_random(ValueSize::Large)) > 250);
+    }
+
+    #[test]
+    fn test_random_values_differ() {
+        assert_ne!(kv_value_random(ValueSize::Small), kv_value_random(ValueSize::Small));
+    }
+
+    #[test]
+    fn test_kv_value_mixed_zero_runs() {
+        let Value::Bytes(b) = kv_value_mixed(ValueSize::Medium, 0.25) else {
+            panic!("expected Bytes");
+        };
+        // Each 64-byte run: 16 random bytes, then 48 zeros
+        for run in b.chunks(64) {
+            assert!(run[16..].iter().all(|&x| x == 0));
+            assert!(run[..16].iter().any(|&x| x != 0));
+        }
+        let Value::Bytes(none) = kv_value_mixed(ValueSize::Small, 0.0) else {
+            panic!("expected Bytes");
+        };
+        assert!(none.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn test_kv_value_is_1kb() {
+        assert_eq!(bytes_len(&kv_value()), 1024);
+    }
+
+    #[test]
+    fn test_state_value_is_64_bytes() {
+        assert_eq!(bytes_len(&state_value()), 64);
+    }
+
+    #[test]
+    fn test_event_payload_is_about_512_bytes() {
+        let payload = event_payload();
+        assert!(matches!(payload, Value::Object(_)));
+        let bytes = approx_value_bytes(&payload);
+        assert!((448..=576).contains(&bytes), "event payload is {} bytes, expected ~512", bytes);
+    }
+
+    #[test]
+    fn test_json_document_shape() {
+        let doc = json_document(7);
+        match &doc {
+            Value::Object(map) => assert_eq!(map.len(), 10),
+            other => panic!("expected Value::Object, got {:?}", other),
+        }
+        assert_eq!(object_depth(&doc), 3);
+    }
+
+    #[test]
+    fn test_json_document_is_deterministic() {
+        assert_eq!(json_document(42), json_document(42));
+        assert_ne!(json_document(1), json_document(2));
+    }
+
+    #[test]
+    fn test_markdown_table_layout() {
+        let rows = vec![vec!["GET".to_string(), "1.5".to_string()]];
+        let table = markdown_table(&["test", "p50_ms"], &rows);
+        assert_eq!(table, "| test | p50_ms |\n| :--- | ---: |\n| GET | 1.5 |\n");
+    }
+
+    #[test]
+    fn test_markdown_table_escapes_pipes() {
+        let rows = vec![vec!["a|b".to_string()]];
+        assert!(markdown_table(&["name"], &rows).contains("| a\\|b |"));
+    }
+
+    #[test]
+    fn test_tail_percentiles_ordered_and_clamped() {
+        let mut i = 0u64;
+        let p = measure_percentiles(10_000, || {
+            i += 1;
+            std::hint::black_box(i);
+        });
+        assert!(p.p99 <= p.p999 && p.p999 <= p.p9999 && p.p9999 <= p.max);
+
+        // A single sample clamps every percentile to it
+        let p = measure_percentiles(1, || {});
+        assert_eq!(p.p999, p.max);
+        assert_eq!(p.p9999, p.max);
+    }
+
+    #[test]
+    fn test_cv_of_constant_timings_is_zero() {
+        let p = Percentiles {
+            p50: Duration::from_micros(10),
+            p95: Duration::from_micros(10),
+            p99: Duration::from_micros(10),
+            p999: Duration::from_micros(10),
+            p9999: Duration::from_micros(10),
+            min: Duration::from_micros(10),
+            max: Duration::from_micros(10),
+            mean: Duration::from_micros(10),
+            stddev: Duration::ZERO,
+            samples: 100,
+        };
+        assert_eq!(p.cv(), 0.0);
+
+        let noisy = Percentiles {
+            stddev: Duration::from_micros(20),
+            ..p
+        };
+        assert!((noisy.cv() - 2.0).abs() < 1e-9);
+        assert!(noisy.cv() > DEFAULT_CV_THRESHOLD);
+    }
+
+    #[test]
+    fn test_parse_samples_falls_back_on_bad_values() {
+        assert_eq!(parse_samples(None), PERCENTILE_SAMPLES);
+        assert_eq!(parse_samples(Some("100")), 100);
+        assert_eq!(parse_samples(Some(" 1000000 ")), 1_000_000);
+        assert_eq!(parse_samples(Some("0")), PERCENTILE_SAMPLES);
+        assert_eq!(parse_samples(Some("-5")), PERCENTILE_SAMPLES);
+        assert_eq!(parse_samples(Some("lots")), PERCENTILE_SAMPLES);
+        assert_eq!(parse_samples(Some("")), PERCENTILE_SAMPLES);
+    }
+
+    #[test]
+    fn test_parse_warmup_falls_back_on_bad_values() {
+        assert_eq!(parse_warmup(None), WARMUP_COUNT);
+        assert_eq!(parse_warmup(Some("1000")), 1_000);
+        assert_eq!(parse_warmup(Some(" 2000000 ")), 2_000_000);
+        assert_eq!(parse_warmup(Some("0")), WARMUP_COUNT);
+        assert_eq!(parse_warmup(Some("10k")), WARMUP_COUNT);
+    }
+
+    #[test]
+    fn test_zipf_stays_in_range() {
+        let mut gen = ZipfKeyGen::new(1_000, ZipfKeyGen::DEFAULT_THETA, 42);
+        for _ in 0..100_000 {
+            assert!(gen.next() < 1_000);
+        }
+    }
+
+    #[test]
+    fn test_zipf_is_skewed() {
+        let n = 10_000;
+        let mut gen = ZipfKeyGen::new(n, ZipfKeyGen::DEFAULT_THETA, 7);
+        let draws = 200_000;
+        let mut counts = vec![0u64; n as usize];
+        for _ in 0..draws {
+            counts[gen.next() as usize] += 1;
+        }
+        // Rank 0 is the most popular and far above the uniform share
+        assert!(counts[0] > counts[1]);
+        assert!(counts[1] > counts[100]);
+        assert!(counts[0] as f64 > draws as f64 / n as f64 * 100.0);
+        // The hottest 1% of keys take roughly half the traffic
+        let top: u64 = counts[..(n / 100) as usize].iter().sum();
+        assert!(top as f64 / draws as f64 > 0.4, "top 1% share {}", top);
+    }
+
+    #[test]
+    fn test_zipf_deterministic_per_seed() {
+        let mut a = ZipfKeyGen::new(500, 0.8, 3);
+        let mut b = ZipfKeyGen::new(500, 0.8, 3);
+        for _ in 0..1_000 {
+            assert_eq!(a.next(), b.next());
+        }
+    }
+
+    #[test]
+    fn test_kv_key_zipf_matches_kv_key_format() {
+        let mut gen = ZipfKeyGen::new(100, 0.5, 1);
+        let key = kv_key_zipf(&mut gen);
+        assert_eq!(key.len(), 100);
+        let i: u64 = key.trim_start_matches('0').parse().unwrap_or(0);
+        assert_eq!(key, kv_key(i));
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]) + 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_exact_top_k_orders_by_similarity() {
+        let corpus = vec![
+            ("far".to_string(), vec![-1.0, 0.0]),
+            ("near".to_string(), vec![1.0, 0.1]),
+            ("exact".to_string(), vec![2.0, 0.0]),
+            ("side".to_string(), vec![0.0, 1.0]),
+        ];
+        assert_eq!(exact_top_k(&corpus, &[1.0, 0.0], 2), vec!["exact", "near"]);
+        assert_eq!(exact_top_k(&corpus, &[1.0, 0.0], 10).len(), 4);
+    }
+
+    #[test]
+    fn test_recall_at_k() {
+        let exact: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let got: Vec<String> = ["d", "x", "a"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(recall_at_k(&exact, &got), 0.5);
+        assert_eq!(recall_at_k(&exact, &exact), 1.0);
+        assert_eq!(recall_at_k(&[], &got), 1.0);
+    }
+
+    #[test]
+    fn test_dir_size_bytes_recurses() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 100]).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("b"), [0u8; 23]).unwrap();
+        assert_eq!(dir_size_bytes(dir.path()), 123);
+        assert_eq!(dir_size_bytes(&dir.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn test_write_amplification() {
+        // 10 puts of 1KB that wrote 12,800 WAL bytes: 1.25x
+        assert_eq!(write_amplification(12_800, 10, 1024), 1.25);
+        // Compressed below the payload size
+        assert_eq!(write_amplification(5_120, 10, 1024), 0.5);
+        assert_eq!(write_amplification(100, 0, 1024), 0.0);
+        assert_eq!(write_amplification(100, 10, 0), 0.0);
+    }
+}
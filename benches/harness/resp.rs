@@ -0,0 +1,149 @@
+//! Minimal RESP2 client for the live-Redis comparison (`--redis-url`, feature
+//! `redis-live`).
+//!
+//! Only what `redis_compare` needs: encode a command as an array of bulk
+//! strings, send it over one blocking TCP connection and read back a single
+//! reply. No pipelining, pub/sub, RESP3 or reconnects.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+/// A decoded RESP2 reply.
+#[derive(Debug, PartialEq)]
+pub enum RespValue {
+    Simple(String),
+    Error(String),
+    Int(i64),
+    /// `None` is the null bulk string (`$-1`), e.g. GET on a missing key.
+    Bulk(Option<Vec<u8>>),
+    Array(Option<Vec<RespValue>>),
+}
+
+/// Encode `args` as a RESP array of bulk strings.
+pub fn encode_command(args: &[&[u8]]) -> Vec<u8> {
+    let mut buf = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        buf.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        buf.extend_from_slice(arg);
+        buf.extend_from_slice(b"\r\n");
+    }
+    buf
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Read one line, without its trailing CRLF.
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"));
+    }
+    match line.strip_suffix("\r\n") {
+        Some(stripped) => Ok(stripped.to_string()),
+        None => Err(invalid(format!("reply line not CRLF-terminated: {:?}", line))),
+    }
+}
+
+fn parse_len(s: &str) -> io::Result<i64> {
+    s.parse()
+        .map_err(|_| invalid(format!("bad RESP length: {:?}", s)))
+}
+
+/// Decode one reply from `reader`.
+pub fn read_value(reader: &mut impl BufRead) -> io::Result<RespValue> {
+    let line = read_line(reader)?;
+    let (kind, rest) = line.split_at(line.len().min(1));
+    match kind {
+        "+" => Ok(RespValue::Simple(rest.to_string())),
+        "-" => Ok(RespValue::Error(rest.to_string())),
+        ":" => Ok(RespValue::Int(parse_len(rest)?)),
+        "$" => {
+            let len = parse_len(rest)?;
+            if len < 0 {
+                return Ok(RespValue::Bulk(None));
+            }
+            let mut data = vec![0u8; len as usize + 2];
+            reader.read_exact(&mut data)?;
+            if !data.ends_with(b"\r\n") {
+                return Err(invalid("bulk string not CRLF-terminated".to_string()));
+            }
+            data.truncate(len as usize);
+            Ok(RespValue::Bulk(Some(data)))
+        }
+        "*" => {
+            let len = parse_len(rest)?;
+            if len < 0 {
+                return Ok(RespValue::Array(None));
+            }
+            let items = (0..len)
+                .map(|_| read_value(reader))
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(RespValue::Array(Some(items)))
+        }
+        _ => Err(invalid(format!("unknown RESP reply: {:?}", line))),
+    }
+}
+
+/// One blocking connection to a Redis server.
+pub struct RespClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl RespClient {
+    /// Connect to `addr` (`host:port`) with Nagle disabled, as redis-benchmark does.
+    pub fn connect(addr: &str) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    /// Send one command and wait for its reply. A Redis error reply is
+    /// returned as an `io::Error` so callers can `?` through it.
+    pub fn command(&mut self, args: &[&[u8]]) -> io::Result<RespValue> {
+        self.writer.write_all(&encode_command(args))?;
+        match read_value(&mut self.reader)? {
+            RespValue::Error(msg) => Err(io::Error::other(format!("redis: {}", msg))),
+            reply => Ok(reply),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_command() {
+        assert_eq!(
+            encode_command(&[b"SET", b"key:000000000000", b"xxx"]),
+            b"*3\r\n$3\r\nSET\r\n$16\r\nkey:000000000000\r\n$3\r\nxxx\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_read_value_scalars() {
+        let mut input: &[u8] = b"+OK\r\n-ERR bad\r\n:42\r\n$3\r\nab\n\r\n$-1\r\n";
+        assert_eq!(read_value(&mut input).unwrap(), RespValue::Simple("OK".into()));
+        assert_eq!(read_value(&mut input).unwrap(), RespValue::Error("ERR bad".into()));
+        assert_eq!(read_value(&mut input).unwrap(), RespValue::Int(42));
+        // Bulk strings are length-prefixed, so embedded newlines are data
+        assert_eq!(read_value(&mut input).unwrap(), RespValue::Bulk(Some(b"ab\n".to_vec())));
+        assert_eq!(read_value(&mut input).unwrap(), RespValue::Bulk(None));
+        assert!(read_value(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_read_value_array() {
+        let mut input: &[u8] = b"*2\r\n:1\r\n$1\r\na\r\n";
+        assert_eq!(
+            read_value(&mut input).unwrap(),
+            RespValue::Array(Some(vec![RespValue::Int(1), RespValue::Bulk(Some(b"a".to_vec()))]))
+        );
+    }
+}
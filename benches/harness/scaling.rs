@@ -4,8 +4,9 @@
 //! coordinates warmup and measurement phases via barriers, and aggregates
 //! per-thread results into a single `ScalingResult`.
 
-use super::metrics::{delta_process_metrics, snapshot_process_metrics, ProcessMetrics};
-use std::sync::atomic::{AtomicBool, Ordering};
+use super::metrics::{delta_process_metrics, fmt_mb, snapshot_process_metrics, ProcessMetrics};
+use super::DurabilityConfig;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, Barrier};
 use std::time::{Duration, Instant};
 use stratadb::Strata;
@@ -20,8 +21,8 @@ pub struct ThreadResult {
     pub ops: u64,
     /// Number of aborted/conflicted attempts.
     pub aborts: u64,
-    /// Sampled operation latencies (reservoir sampling, max `RESERVOIR_SIZE`).
-    pub latencies: Vec<Duration>,
+    /// Operation latencies from the thread's `Collector`.
+    pub latencies: Latencies,
 }
 
 /// Maximum latency samples kept per thread (reservoir sampling).
@@ -38,9 +39,16 @@ pub struct ScalingResult {
     pub ops_per_sec_per_core: f64,
     pub abort_rate_pct: f64,
     pub retries_per_commit: f64,
+    /// Jain's fairness index over per-thread op counts: 1.0 when every thread
+    /// did equal work, down to 1/n when one thread did all of it.
+    pub fairness: f64,
+    /// Successful ops completed by each thread, in thread-id order.
+    pub per_thread_ops: Vec<u64>,
     pub p50: Duration,
     pub p95: Duration,
     pub p99: Duration,
+    /// CPU, context-switch and peak-RSS figures for the run. `peak_rss_kb` is
+    /// the process high-water mark, so it is monotonic across runs.
     pub cpu: ProcessMetrics,
     pub wal: WalDelta,
 }
@@ -93,6 +101,27 @@ pub fn physical_cores() -> usize {
         .unwrap_or(4)
 }
 
+// ---------------------------------------------------------------------------
+// Per-thread seeds
+// ---------------------------------------------------------------------------
+
+/// SplitMix64 finalizer: spreads every input bit across the output.
+pub fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// RNG seed for thread `tid` of a workload salted with `salt`. The seed is
+/// mixed before the thread id is folded in: a plain `seed ^ tid` only
+/// permutes the per-thread streams when two seeds differ in their low bits,
+/// so a different `--seed` would replay the same streams. (Mixing both and
+/// XORing has the same flaw, since it is symmetric in seed and tid.)
+pub fn thread_seed(seed: u64, tid: usize, salt: u64) -> u64 {
+    splitmix64(splitmix64(seed ^ salt) ^ tid as u64)
+}
+
 // ---------------------------------------------------------------------------
 // Reservoir sampler
 // ---------------------------------------------------------------------------
@@ -157,6 +186,214 @@ impl ReservoirSampler {
     }
 }
 
+// ---------------------------------------------------------------------------
+// HdrHistogram collector (feature `hdr`)
+// ---------------------------------------------------------------------------
+
+/// Full-fidelity latency histogram: every operation is counted, so tail
+/// percentiles stay exact under millions of ops where the reservoir only
+/// keeps `RESERVOIR_SIZE` samples.
+///
+/// Records at microsecond resolution with 3 significant figures; operations
+/// faster than 1 µs are counted as 0 µs.
+#[cfg(feature = "hdr")]
+pub struct HdrCollector {
+    hist: hdrhistogram::Histogram<u64>,
+}
+
+#[cfg(feature = "hdr")]
+impl HdrCollector {
+    pub fn new() -> Self {
+        Self {
+            hist: hdrhistogram::Histogram::new(3).expect("3 significant figures is valid"),
+        }
+    }
+
+    /// Record a latency sample.
+    #[inline]
+    pub fn record(&mut self, d: Duration) {
+        self.hist.saturating_record(d.as_micros() as u64);
+    }
+
+    /// Latency at percentile `p` (0.0..=100.0).
+    pub fn percentile(&self, p: f64) -> Duration {
+        Duration::from_micros(self.hist.value_at_percentile(p))
+    }
+
+    /// Fold another thread's histogram into this one.
+    pub fn merge(&mut self, other: &HdrCollector) {
+        self.hist
+            .add(&other.hist)
+            .expect("auto-resizing histograms always merge");
+    }
+
+    /// Number of recorded samples.
+    pub fn len(&self) -> u64 {
+        self.hist.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hist.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Intended-rate pacing (coordinated-omission correction)
+// ---------------------------------------------------------------------------
+
+/// Per-thread target rate in ops/sec set by `set_target_rate`; 0 = closed loop.
+static TARGET_RATE: AtomicU64 = AtomicU64::new(0);
+
+/// Waits shorter than this are spun rather than slept, since `sleep` can
+/// overshoot by tens of microseconds.
+const PACER_SPIN_THRESHOLD: Duration = Duration::from_micros(200);
+
+/// Pace every worker thread at `ops_per_sec` (per thread) for subsequent
+/// experiments. 0 restores closed-loop behavior.
+pub fn set_target_rate(ops_per_sec: u64) {
+    TARGET_RATE.store(ops_per_sec, Ordering::SeqCst);
+}
+
+/// Supplies each operation's start time.
+///
+/// Closed loop (target rate 0): the start is simply `Instant::now()`, so when
+/// the database stalls no new op starts and the stall is counted once — the
+/// coordinated-omission undercount. With a target rate, op k is *intended* to
+/// start at `t0 + k * interval`; `start()` waits for that instant if ahead of
+/// schedule and returns it, so latency measured from it includes time spent
+/// queued behind a stall.
+pub struct Pacer {
+    interval: Option<Duration>,
+    next: Instant,
+}
+
+impl Pacer {
+    /// Pacer for one worker thread, using the current `set_target_rate`.
+    pub fn new() -> Self {
+        let rate = TARGET_RATE.load(Ordering::Relaxed);
+        Self {
+            interval: (rate > 0).then(|| Duration::from_secs_f64(1.0 / rate as f64)),
+            next: Instant::now(),
+        }
+    }
+
+    /// Start time to measure the next op's latency from.
+    #[inline]
+    pub fn start(&mut self) -> Instant {
+        let Some(interval) = self.interval else {
+            return Instant::now();
+        };
+        let intended = self.next;
+        self.next += interval;
+        loop {
+            let now = Instant::now();
+            if now >= intended {
+                return intended;
+            }
+            let wait = intended - now;
+            if wait > PACER_SPIN_THRESHOLD {
+                std::thread::sleep(wait - PACER_SPIN_THRESHOLD);
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Collector selection
+// ---------------------------------------------------------------------------
+
+/// Set by `use_hdr`: new `Collector`s are HdrHistogram-backed.
+static USE_HDR: AtomicBool = AtomicBool::new(false);
+
+/// Select HdrHistogram collection for subsequent experiments. Returns false
+/// (and leaves reservoir sampling in place) when built without `hdr`.
+pub fn use_hdr(enabled: bool) -> bool {
+    if cfg!(feature = "hdr") {
+        USE_HDR.store(enabled, Ordering::SeqCst);
+        true
+    } else {
+        !enabled
+    }
+}
+
+/// Per-thread latency collector: a `ReservoirSampler` by default, or an
+/// `HdrCollector` after `use_hdr(true)`.
+pub enum Collector {
+    Reservoir(ReservoirSampler),
+    #[cfg(feature = "hdr")]
+    Hdr(HdrCollector),
+}
+
+impl Collector {
+    /// Collector for one worker thread; `seed` keeps reservoirs independent.
+    pub fn for_thread(seed: u64) -> Self {
+        #[cfg(feature = "hdr")]
+        if USE_HDR.load(Ordering::Relaxed) {
+            return Collector::Hdr(HdrCollector::new());
+        }
+        Collector::Reservoir(ReservoirSampler::with_seed(seed))
+    }
+
+    /// Record a latency sample.
+    #[inline]
+    pub fn record(&mut self, d: Duration) {
+        match self {
+            Collector::Reservoir(s) => s.record(d),
+            #[cfg(feature = "hdr")]
+            Collector::Hdr(h) => h.record(d),
+        }
+    }
+
+    pub fn into_latencies(self) -> Latencies {
+        match self {
+            Collector::Reservoir(s) => Latencies::Samples(s.into_samples()),
+            #[cfg(feature = "hdr")]
+            Collector::Hdr(h) => Latencies::Hdr(h),
+        }
+    }
+}
+
+/// A thread's collected latencies, as returned in `ThreadResult`.
+pub enum Latencies {
+    /// Individual samples (reservoir-sampled).
+    Samples(Vec<Duration>),
+    #[cfg(feature = "hdr")]
+    Hdr(HdrCollector),
+}
+
+impl Latencies {
+    /// No latencies, for threads whose operations aren't being timed.
+    pub fn none() -> Self {
+        Latencies::Samples(Vec::new())
+    }
+}
+
+/// Merge every thread's latencies and return (p50, p95, p99).
+fn merged_percentiles(all: impl IntoIterator<Item = Latencies>) -> (Duration, Duration, Duration) {
+    let mut samples = Vec::new();
+    #[cfg(feature = "hdr")]
+    let mut hist: Option<HdrCollector> = None;
+
+    for latencies in all {
+        match latencies {
+            Latencies::Samples(s) => samples.extend(s),
+            #[cfg(feature = "hdr")]
+            Latencies::Hdr(h) => match hist.as_mut() {
+                Some(merged) => merged.merge(&h),
+                None => hist = Some(h),
+            },
+        }
+    }
+
+    #[cfg(feature = "hdr")]
+    if let Some(h) = hist.filter(|h| !h.is_empty()) {
+        return (h.percentile(50.0), h.percentile(95.0), h.percentile(99.0));
+    }
+    compute_percentiles(&mut samples)
+}
+
 // ---------------------------------------------------------------------------
 // Core experiment runner
 // ---------------------------------------------------------------------------
@@ -171,7 +408,8 @@ impl ReservoirSampler {
 /// * `measure_secs` - Measurement duration.
 /// * `work_fn` - Closure called by each thread. Receives `(thread_id, Strata, stop_flag)`.
 ///   Each thread gets its own `Strata` instance created via `strata.new_handle()`.
-///   The closure must respect the `stop` flag.
+///   The closure must respect the `stop` flag. Closures that take op start
+///   times from a `Pacer` run open-loop at the `set_target_rate` rate.
 pub fn run_scaling_experiment<F>(
     strata: &Strata,
     num_threads: usize,
@@ -269,12 +507,11 @@ where
         0.0
     };
 
-    // Merge latency reservoirs and compute percentiles
-    let mut all_latencies: Vec<Duration> = thread_results
-        .into_iter()
-        .flat_map(|r| r.latencies)
-        .collect();
-    let (p50, p95, p99) = compute_percentiles(&mut all_latencies);
+    let per_thread_ops: Vec<u64> = thread_results.iter().map(|r| r.ops).collect();
+    let fairness = jain_fairness(&per_thread_ops);
+
+    // Merge per-thread latencies and compute percentiles
+    let (p50, p95, p99) = merged_percentiles(thread_results.into_iter().map(|r| r.latencies));
 
     let cpu = delta_process_metrics(&cpu_before, &cpu_after);
     let wal = WalDelta {
@@ -295,6 +532,8 @@ where
         ops_per_sec_per_core,
         abort_rate_pct,
         retries_per_commit,
+        fairness,
+        per_thread_ops,
         p50,
         p95,
         p99,
@@ -303,6 +542,139 @@ where
     }
 }
 
+// ---------------------------------------------------------------------------
+// Ramp experiment (step increases in concurrency)
+// ---------------------------------------------------------------------------
+
+/// One 1-second bucket of a ramp experiment.
+#[derive(Debug, Clone)]
+pub struct RampBucket {
+    /// Seconds since the first stage was released, at the end of the bucket.
+    pub elapsed_sec: u64,
+    /// Threads running during this bucket.
+    pub active_threads: usize,
+    /// Successful ops completed in this bucket.
+    pub ops: u64,
+    /// Failed (aborted/conflicted) ops in this bucket.
+    pub aborts: u64,
+}
+
+/// Per-thread counters on their own cache line so the sampler can read them
+/// mid-run without threads contending on a shared atomic.
+#[repr(align(64))]
+#[derive(Default)]
+struct RampCounters {
+    ops: AtomicU64,
+    aborts: AtomicU64,
+}
+
+/// Run `op_fn` on a growing number of threads and report throughput per second.
+///
+/// `stages` is the active thread count for each successive stage (e.g. the
+/// thread sweep `[1, 2, 4, 8]`); each stage lasts `stage_secs`. All threads are
+/// spawned up front and held at their stage's barrier, so a stage starts
+/// without thread-creation cost in the measurement.
+///
+/// `op_fn(thread_id, strata, seq)` performs one operation and returns whether
+/// it succeeded; `seq` counts that thread's calls from 0.
+pub fn run_ramp_experiment<F>(
+    strata: &Strata,
+    stages: &[usize],
+    stage_secs: u64,
+    op_fn: F,
+) -> Vec<RampBucket>
+where
+    F: Fn(usize, &Strata, u64) -> bool + Send + Sync + 'static,
+{
+    let max_threads = stages.iter().copied().max().unwrap_or(0);
+    let op_fn = Arc::new(op_fn);
+    let stop = Arc::new(AtomicBool::new(false));
+    let counters: Arc<Vec<RampCounters>> =
+        Arc::new((0..max_threads).map(|_| RampCounters::default()).collect());
+
+    // Stage i releases the threads that take the count from stages[i-1] to stages[i]
+    let mut barriers = Vec::with_capacity(stages.len());
+    let mut handles = Vec::with_capacity(max_threads);
+    let mut started = 0;
+    for &target in stages {
+        let new_threads = target.saturating_sub(started);
+        let barrier = Arc::new(Barrier::new(new_threads + 1));
+        for tid in started..started + new_threads {
+            let thread_strata = strata.new_handle().expect("failed to create Strata for thread");
+            let barrier = Arc::clone(&barrier);
+            let stop = Arc::clone(&stop);
+            let counters = Arc::clone(&counters);
+            let op_fn = Arc::clone(&op_fn);
+            handles.push(std::thread::spawn(move || {
+                barrier.wait();
+                let mine = &counters[tid];
+                let mut seq = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    if op_fn(tid, &thread_strata, seq) {
+                        mine.ops.fetch_add(1, Ordering::Relaxed);
+                    } else {
+                        mine.aborts.fetch_add(1, Ordering::Relaxed);
+                    }
+                    seq += 1;
+                }
+            }));
+        }
+        started = started.max(target);
+        barriers.push(barrier);
+    }
+
+    let totals = || {
+        counters.iter().fold((0u64, 0u64), |(ops, aborts), c| {
+            (ops + c.ops.load(Ordering::Relaxed), aborts + c.aborts.load(Ordering::Relaxed))
+        })
+    };
+
+    let mut buckets = Vec::with_capacity(stages.len() * stage_secs as usize);
+    let start = Instant::now();
+    let mut active = 0;
+    let mut prev = (0u64, 0u64);
+    for (stage, barrier) in barriers.iter().enumerate() {
+        barrier.wait();
+        active = active.max(stages[stage]);
+        for _ in 0..stage_secs {
+            let elapsed_sec = buckets.len() as u64 + 1;
+            let deadline = start + Duration::from_secs(elapsed_sec);
+            std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            let now = totals();
+            buckets.push(RampBucket {
+                elapsed_sec,
+                active_threads: active,
+                ops: now.0 - prev.0,
+                aborts: now.1 - prev.1,
+            });
+            prev = now;
+        }
+    }
+
+    stop.store(true, Ordering::SeqCst);
+    for h in handles {
+        h.join().expect("worker thread panicked");
+    }
+    buckets
+}
+
+// ---------------------------------------------------------------------------
+// Fairness
+// ---------------------------------------------------------------------------
+
+/// Jain's fairness index `(Σx)² / (n·Σx²)` over per-thread throughput.
+///
+/// 1.0 means perfectly even work; 1/n means a single thread did everything.
+/// Returns 1.0 when no thread completed any ops (nothing to be unfair about).
+pub fn jain_fairness(ops: &[u64]) -> f64 {
+    let sum: f64 = ops.iter().map(|&x| x as f64).sum();
+    let sum_sq: f64 = ops.iter().map(|&x| (x as f64) * (x as f64)).sum();
+    if ops.is_empty() || sum_sq == 0.0 {
+        return 1.0;
+    }
+    sum * sum / (ops.len() as f64 * sum_sq)
+}
+
 // ---------------------------------------------------------------------------
 // Percentile computation
 // ---------------------------------------------------------------------------
@@ -365,19 +737,19 @@ pub fn fmt_ops(ops: f64) -> String {
 /// Print the header row for a scaling result table.
 pub fn print_table_header() {
     eprintln!(
-        "{:<8}| {:<12}| {:<11}| {:<9}| {:<9}| {:<9}| {:<7}| {:<7}| {:<11}| {:<8}| {:<8}| {:<7}| {:<9}| {:<8}| {:<8}",
+        "{:<8}| {:<12}| {:<11}| {:<9}| {:<9}| {:<9}| {:<7}| {:<7}| {:<11}| {:<8}| {:<8}| {:<8}| {:<7}| {:<9}| {:<8}| {:<8}| {:<10}| {:<10}| {:<10}| {:<9}| {:<8}",
         "threads", "ops/sec", "ops/s/core", "p50", "p95", "p99",
-        "aborts", "abort%", "retries/op",
+        "aborts", "abort%", "retries/op", "fairness",
         "cpu_usr", "cpu_sys", "vol_cs", "invol_cs",
-        "wal_app", "wal_sync"
+        "wal_app", "wal_sync", "peak_rss", "disk_rd", "disk_wr", "min_flt", "maj_flt"
     );
-    eprintln!("{}", "-".repeat(150));
+    eprintln!("{}", "-".repeat(217));
 }
 
 /// Print one row of a scaling result table.
 pub fn print_table_row(r: &ScalingResult) {
     eprintln!(
-        "{:<8}| {:<12}| {:<11}| {:<9}| {:<9}| {:<9}| {:<7}| {:<7.2}| {:<11.2}| {:<8}| {:<8}| {:<7}| {:<9}| {:<8}| {:<8}",
+        "{:<8}| {:<12}| {:<11}| {:<9}| {:<9}| {:<9}| {:<7}| {:<7.2}| {:<11.2}| {:<8.3}| {:<8}| {:<8}| {:<7}| {:<9}| {:<8}| {:<8}| {:<10}| {:<10}| {:<10}| {:<9}| {:<8}",
         r.threads,
         fmt_ops(r.ops_per_sec),
         fmt_ops(r.ops_per_sec_per_core),
@@ -387,22 +759,307 @@ pub fn print_table_row(r: &ScalingResult) {
         fmt_num(r.total_aborts),
         r.abort_rate_pct,
         r.retries_per_commit,
+        r.fairness,
         format!("{} ms", r.cpu.user_time_ms),
         format!("{} ms", r.cpu.system_time_ms),
         r.cpu.voluntary_ctx,
         r.cpu.involuntary_ctx,
         r.wal.wal_appends,
         r.wal.sync_calls,
+        fmt_mb(r.cpu.peak_rss_kb * 1024),
+        fmt_mb(r.cpu.read_bytes),
+        fmt_mb(r.cpu.write_bytes),
+        fmt_num(r.cpu.minor_faults),
+        fmt_num(r.cpu.major_faults),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// ASCII chart (--chart)
+// ---------------------------------------------------------------------------
+
+/// Set by `set_chart`; read by `chart_enabled`.
+static SHOW_CHART: AtomicBool = AtomicBool::new(false);
+
+/// Rows in the chart body; each row is split into eighths by block glyphs.
+const CHART_HEIGHT: usize = 12;
+
+/// Width of one bar; each column is a space plus the bar.
+const CHART_BAR_WIDTH: usize = 4;
+
+/// Width of the Y-axis label gutter.
+const CHART_LABEL_WIDTH: usize = 12;
+
+/// Partial-cell glyphs indexed by eighths filled (0 = empty, 8 = full).
+const CHART_BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Glyph for the physical-core guide line.
+const CHART_GUIDE: char = '┆';
+
+/// Enable `--chart`: workloads call `print_ascii_chart` after their table.
+pub fn set_chart(enabled: bool) {
+    SHOW_CHART.store(enabled, Ordering::SeqCst);
+}
+
+pub fn chart_enabled() -> bool {
+    SHOW_CHART.load(Ordering::Relaxed)
+}
+
+/// Round `v` up to 1, 2 or 5 times a power of ten, so the Y axis tops out
+/// at a readable number just above the highest bar.
+fn nice_ceiling(v: f64) -> f64 {
+    if v.is_nan() || v <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(v.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&top| top >= v)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Render ops/sec vs thread count as a bar chart, one bar per result in
+/// ascending thread order. A `┆` guide separates thread counts up to `cores`
+/// from oversubscribed ones; it is omitted when no result exceeds `cores`.
+fn render_ascii_chart(results: &[ScalingResult], cores: usize) -> Vec<String> {
+    if results.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted: Vec<&ScalingResult> = results.iter().collect();
+    sorted.sort_by_key(|r| r.threads);
+
+    let top = nice_ceiling(sorted.iter().map(|r| r.ops_per_sec).fold(0.0, f64::max));
+    let eighths: Vec<usize> = sorted
+        .iter()
+        .map(|r| (r.ops_per_sec.max(0.0) / top * (CHART_HEIGHT * 8) as f64).round() as usize)
+        .collect();
+    let guide_at = sorted.iter().filter(|r| r.threads <= cores).count();
+    let has_guide = guide_at < sorted.len();
+
+    // Build a row from one cell per column, inserting the guide cell where
+    // the thread count first exceeds `cores`
+    let row = |cell: &dyn Fn(usize) -> String, guide: &str| {
+        let mut line = String::new();
+        for i in 0..sorted.len() {
+            if has_guide && i == guide_at {
+                line.push_str(guide);
+            }
+            line.push_str(&cell(i));
+        }
+        line
+    };
+    let guide = format!(" {}", CHART_GUIDE);
+
+    let mut lines = Vec::with_capacity(CHART_HEIGHT + 3);
+    for level in (0..CHART_HEIGHT).rev() {
+        let label = if level == CHART_HEIGHT - 1 {
+            format!("{} ┤", fmt_ops(top))
+        } else if level == CHART_HEIGHT / 2 - 1 {
+            format!("{} ┤", fmt_ops(top / 2.0))
+        } else {
+            "│".to_string()
+        };
+        let bars = row(
+            &|i| {
+                let fill = eighths[i].saturating_sub(level * 8).min(8);
+                format!(" {}", CHART_BLOCKS[fill].to_string().repeat(CHART_BAR_WIDTH))
+            },
+            &guide,
+        );
+        lines.push(format!("{:>width$}{}", label, bars, width = CHART_LABEL_WIDTH + 2));
+    }
+
+    let axis = row(&|_| "─".repeat(CHART_BAR_WIDTH + 1), "─┴");
+    lines.push(format!("{:>width$} └{}", 0, axis, width = CHART_LABEL_WIDTH));
+    let labels = row(
+        &|i| format!("{:>width$}", sorted[i].threads, width = CHART_BAR_WIDTH + 1),
+        "  ",
     );
+    lines.push(format!("{:width$}{}", "", labels, width = CHART_LABEL_WIDTH + 2));
+
+    let mut caption = format!("{:width$}  ops/sec by thread count", "", width = CHART_LABEL_WIDTH);
+    if has_guide {
+        caption.push_str(&format!(" ({} = {} physical cores)", CHART_GUIDE, cores));
+    }
+    lines.push(caption);
+    lines
+}
+
+/// Print ops/sec vs thread count as a terminal bar chart on stderr, with a
+/// guide at `physical_cores()`. Meant to follow the table for one workload.
+pub fn print_ascii_chart(results: &[ScalingResult]) {
+    eprintln!();
+    for line in render_ascii_chart(results, physical_cores()) {
+        eprintln!("{}", line);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Machine-readable output (--csv / --json)
+// ---------------------------------------------------------------------------
+
+/// Machine-readable copy of each result on stdout, alongside the stderr table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+/// Set by `set_output_format`; read by `emit_result`.
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+/// Select stdout output for `emit_result`. The last call wins, so nothing is
+/// printed until `print_output_header` runs after argument parsing.
+pub fn set_output_format(format: OutputFormat) {
+    let code = match format {
+        OutputFormat::Table => 0,
+        OutputFormat::Csv => 1,
+        OutputFormat::Json => 2,
+    };
+    OUTPUT_FORMAT.store(code, Ordering::SeqCst);
+}
+
+/// Print the CSV header row if the final output format is `Csv`.
+pub fn print_output_header() {
+    if output_format() == OutputFormat::Csv {
+        println!("{}", CSV_HEADER);
+    }
+}
+
+fn output_format() -> OutputFormat {
+    match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+        1 => OutputFormat::Csv,
+        2 => OutputFormat::Json,
+        _ => OutputFormat::Table,
+    }
+}
+
+const CSV_HEADER: &str = "workload,durability,threads,duration_ms,total_ops,total_aborts,\
+ops_per_sec,ops_per_sec_per_core,abort_rate_pct,retries_per_commit,fairness,p50_ns,p95_ns,\
+p99_ns,cpu_user_ms,cpu_sys_ms,minor_faults,major_faults,voluntary_ctx,involuntary_ctx,\
+peak_rss_kb,read_bytes,write_bytes,wal_appends,sync_calls";
+
+/// One `--json` line: every `ScalingResult` field, flattened. Durations are
+/// integer nanoseconds (milliseconds for the run duration).
+#[derive(serde::Serialize)]
+struct ScalingRecord {
+    workload: String,
+    durability: String,
+    threads: usize,
+    duration_ms: u64,
+    total_ops: u64,
+    total_aborts: u64,
+    ops_per_sec: f64,
+    ops_per_sec_per_core: f64,
+    abort_rate_pct: f64,
+    retries_per_commit: f64,
+    fairness: f64,
+    p50_ns: u64,
+    p95_ns: u64,
+    p99_ns: u64,
+    cpu_user_ms: u64,
+    cpu_sys_ms: u64,
+    minor_faults: u64,
+    major_faults: u64,
+    voluntary_ctx: u64,
+    involuntary_ctx: u64,
+    peak_rss_kb: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+    wal_appends: u64,
+    sync_calls: u64,
+}
+
+fn scaling_record(workload: &str, mode: DurabilityConfig, r: &ScalingResult) -> ScalingRecord {
+    ScalingRecord {
+        workload: workload.to_string(),
+        durability: mode.label().to_string(),
+        threads: r.threads,
+        duration_ms: r.duration.as_millis() as u64,
+        total_ops: r.total_ops,
+        total_aborts: r.total_aborts,
+        ops_per_sec: r.ops_per_sec,
+        ops_per_sec_per_core: r.ops_per_sec_per_core,
+        abort_rate_pct: r.abort_rate_pct,
+        retries_per_commit: r.retries_per_commit,
+        fairness: r.fairness,
+        p50_ns: r.p50.as_nanos() as u64,
+        p95_ns: r.p95.as_nanos() as u64,
+        p99_ns: r.p99.as_nanos() as u64,
+        cpu_user_ms: r.cpu.user_time_ms,
+        cpu_sys_ms: r.cpu.system_time_ms,
+        minor_faults: r.cpu.minor_faults,
+        major_faults: r.cpu.major_faults,
+        voluntary_ctx: r.cpu.voluntary_ctx,
+        involuntary_ctx: r.cpu.involuntary_ctx,
+        peak_rss_kb: r.cpu.peak_rss_kb,
+        read_bytes: r.cpu.read_bytes,
+        write_bytes: r.cpu.write_bytes,
+        wal_appends: r.wal.wal_appends,
+        sync_calls: r.wal.sync_calls,
+    }
+}
+
+fn csv_row(rec: &ScalingRecord) -> String {
+    format!(
+        "{},{},{},{},{},{},{:.2},{:.2},{:.4},{:.4},{:.4},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        rec.workload,
+        rec.durability,
+        rec.threads,
+        rec.duration_ms,
+        rec.total_ops,
+        rec.total_aborts,
+        rec.ops_per_sec,
+        rec.ops_per_sec_per_core,
+        rec.abort_rate_pct,
+        rec.retries_per_commit,
+        rec.fairness,
+        rec.p50_ns,
+        rec.p95_ns,
+        rec.p99_ns,
+        rec.cpu_user_ms,
+        rec.cpu_sys_ms,
+        rec.minor_faults,
+        rec.major_faults,
+        rec.voluntary_ctx,
+        rec.involuntary_ctx,
+        rec.peak_rss_kb,
+        rec.read_bytes,
+        rec.write_bytes,
+        rec.wal_appends,
+        rec.sync_calls,
+    )
+}
+
+/// Write `r` to stdout as a CSV row or NDJSON line, per `set_output_format`.
+/// A no-op for `OutputFormat::Table`.
+pub fn emit_result(workload: &str, mode: DurabilityConfig, r: &ScalingResult) {
+    match output_format() {
+        OutputFormat::Table => {}
+        OutputFormat::Csv => println!("{}", csv_row(&scaling_record(workload, mode, r))),
+        OutputFormat::Json => match serde_json::to_string(&scaling_record(workload, mode, r)) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("warning: cannot serialize {} result: {}", workload, e),
+        },
+    }
 }
 
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
     use super::{
-        compute_percentiles, fmt_duration, fmt_num, parse_thread_counts, thread_counts,
-        ReservoirSampler, RESERVOIR_SIZE,
+        compute_percentiles, fmt_duration, fmt_num, jain_fairness, merged_percentiles,
+        parse_thread_counts, thread_counts, Latencies, ReservoirSampler, RESERVOIR_SIZE,
     };
+    use super::{csv_row, scaling_record, ScalingResult, WalDelta, CSV_HEADER};
+    use super::{set_target_rate, Pacer};
+    use super::{nice_ceiling, render_ascii_chart, CHART_GUIDE, CHART_HEIGHT};
+    use super::{DurabilityConfig, ProcessMetrics};
+    use super::thread_seed;
+    #[cfg(feature = "hdr")]
+    use super::HdrCollector;
     use std::time::Duration;
 
     #[test]
@@ -477,4 +1134,162 @@ mod tests {
         assert!(fmt_duration(Duration::from_millis(50)).contains("ms"));
         assert!(fmt_duration(Duration::from_secs(2)).contains("s"));
     }
+
+    #[test]
+    fn test_merged_percentiles_from_samples() {
+        let threads = (1..=4u64).map(|t| {
+            Latencies::Samples((0..25).map(|i| Duration::from_micros(t * 25 + i)).collect())
+        });
+        let (p50, _, p99) = merged_percentiles(threads);
+        assert!(p50 >= Duration::from_micros(70) && p50 <= Duration::from_micros(80));
+        assert!(p99 >= Duration::from_micros(120));
+    }
+
+    #[cfg(feature = "hdr")]
+    #[test]
+    fn test_hdr_collector_merges_across_threads() {
+        let mut a = HdrCollector::new();
+        let mut b = HdrCollector::new();
+        for us in 1..=500 {
+            a.record(Duration::from_micros(us));
+            b.record(Duration::from_micros(500 + us));
+        }
+        a.merge(&b);
+        assert_eq!(a.len(), 1_000);
+
+        // 3 significant figures: within 0.1% of the exact value
+        let p50 = a.percentile(50.0).as_micros() as f64;
+        assert!((p50 - 500.0).abs() <= 1.0, "p50 = {}", p50);
+        let p99 = a.percentile(99.0).as_micros() as f64;
+        assert!((p99 - 990.0).abs() <= 1.0, "p99 = {}", p99);
+    }
+
+    #[test]
+    fn test_jain_fairness() {
+        assert!((jain_fairness(&[100, 100, 100, 100]) - 1.0).abs() < 1e-9);
+        // One thread doing everything: 1/n
+        assert!((jain_fairness(&[400, 0, 0, 0]) - 0.25).abs() < 1e-9);
+        // (1+3)^2 / (2 * (1+9)) = 0.8
+        assert!((jain_fairness(&[1, 3]) - 0.8).abs() < 1e-9);
+        assert_eq!(jain_fairness(&[]), 1.0);
+        assert_eq!(jain_fairness(&[0, 0]), 1.0);
+    }
+
+    #[test]
+    fn test_csv_row_matches_header() {
+        let r = ScalingResult {
+            threads: 4,
+            duration: Duration::from_secs(5),
+            total_ops: 1_000,
+            total_aborts: 10,
+            ops_per_sec: 200.0,
+            ops_per_sec_per_core: 50.0,
+            abort_rate_pct: 0.99,
+            retries_per_commit: 1.01,
+            fairness: 0.98,
+            per_thread_ops: vec![250; 4],
+            p50: Duration::from_micros(1),
+            p95: Duration::from_micros(2),
+            p99: Duration::from_micros(3),
+            cpu: ProcessMetrics::default(),
+            wal: WalDelta::default(),
+        };
+        let row = csv_row(&scaling_record("kv_get", DurabilityConfig::Cache, &r));
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+        assert!(row.starts_with("kv_get,cache,4,5000,1000,10,"));
+    }
+
+    fn chart_result(threads: usize, ops_per_sec: f64) -> ScalingResult {
+        ScalingResult {
+            threads,
+            duration: Duration::from_secs(1),
+            total_ops: ops_per_sec as u64,
+            total_aborts: 0,
+            ops_per_sec,
+            ops_per_sec_per_core: ops_per_sec / threads as f64,
+            abort_rate_pct: 0.0,
+            retries_per_commit: 1.0,
+            fairness: 1.0,
+            per_thread_ops: vec![ops_per_sec as u64 / threads as u64; threads],
+            p50: Duration::ZERO,
+            p95: Duration::ZERO,
+            p99: Duration::ZERO,
+            cpu: ProcessMetrics::default(),
+            wal: WalDelta::default(),
+        }
+    }
+
+    #[test]
+    fn test_nice_ceiling() {
+        assert_eq!(nice_ceiling(0.0), 1.0);
+        assert_eq!(nice_ceiling(1.0), 1.0);
+        assert_eq!(nice_ceiling(1.5), 2.0);
+        assert_eq!(nice_ceiling(3_400.0), 5_000.0);
+        assert_eq!(nice_ceiling(51_000.0), 100_000.0);
+    }
+
+    #[test]
+    fn test_ascii_chart_scales_bars_and_marks_cores() {
+        let results: Vec<_> = [(1, 10_000.0), (2, 20_000.0), (4, 40_000.0), (8, 30_000.0)]
+            .iter()
+            .map(|&(t, ops)| chart_result(t, ops))
+            .collect();
+        let lines = render_ascii_chart(&results, 2);
+        // Body rows, X axis, thread labels, caption
+        assert_eq!(lines.len(), CHART_HEIGHT + 3);
+        assert!(lines[0].contains("50,000"), "top label: {}", lines[0]);
+
+        // Y axis tops out at 50k, so the 40k bar fills 80% of the body:
+        // its column is full on the bottom rows and empty on the top one
+        let bottom = &lines[CHART_HEIGHT - 1];
+        assert_eq!(bottom.matches('█').count(), 16, "bottom row: {}", bottom);
+        assert!(!lines[0].contains('█'));
+
+        // Guide sits between the 2- and 4-thread columns on every body row
+        for line in &lines[..CHART_HEIGHT] {
+            assert_eq!(line.matches(CHART_GUIDE).count(), 1, "row: {}", line);
+        }
+        assert!(lines[CHART_HEIGHT + 2].contains("2 physical cores"));
+    }
+
+    #[test]
+    fn test_ascii_chart_sorts_threads_and_omits_guide_within_cores() {
+        let results = vec![chart_result(4, 4.0), chart_result(1, 1.0)];
+        let lines = render_ascii_chart(&results, 4);
+        assert!(lines.iter().all(|l| !l.contains(CHART_GUIDE)));
+        let labels: Vec<&str> = lines[CHART_HEIGHT + 1].split_whitespace().collect();
+        assert_eq!(labels, ["1", "4"]);
+        assert!(render_ascii_chart(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_pacer_schedules_intended_starts() {
+        set_target_rate(1_000);
+        let mut pacer = Pacer::new();
+        set_target_rate(0);
+        let first = pacer.start();
+        let second = pacer.start();
+        assert_eq!(second - first, Duration::from_millis(1));
+
+        // Behind schedule: intended starts keep advancing by the interval
+        std::thread::sleep(Duration::from_millis(5));
+        let late = pacer.start();
+        assert_eq!(late - second, Duration::from_millis(1));
+        assert!(late.elapsed() >= Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_thread_seed_streams_differ_across_low_bit_seeds() {
+        // With seed ^ tid, seeds 0 and 1 gave threads {0, 1} the same two
+        // streams, swapped
+        let streams = |seed| -> Vec<u64> { (0..8).map(|tid| thread_seed(seed, tid, 0)).collect() };
+        let (a, b) = (streams(0), streams(1));
+        assert!(a.iter().all(|s| !b.contains(s)));
+        // Still distinct per thread, and per salt
+        let mut unique = a.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), a.len());
+        assert_ne!(thread_seed(7, 3, 0), thread_seed(7, 3, 0xc0ffee));
+    }
 }
@@ -0,0 +1,171 @@
+//! SQLite-backed history of benchmark results (`--store`, feature `sqlite-compare`).
+//!
+//! Every result is stored as one row per metric in a single long-format table,
+//! tagged with the commit, run timestamp and hardware. A metric's trend across
+//! runs is then a single query:
+//!
+//! ```sql
+//! SELECT commit_sha, value FROM results
+//! WHERE operation = 'SET' AND mode = 'always' AND metric = 'p99_ms'
+//! ORDER BY timestamp;
+//! ```
+//!
+//! This file does not use the rest of the harness so `examples/query_trends.rs`
+//! can include it directly.
+
+use rusqlite::{params, Connection};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS results (
+    id          INTEGER PRIMARY KEY,
+    commit_sha  TEXT    NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    hardware    TEXT    NOT NULL,
+    bench       TEXT    NOT NULL,
+    operation   TEXT    NOT NULL,
+    mode        TEXT    NOT NULL,
+    metric      TEXT    NOT NULL,
+    value       REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS results_series
+    ON results (operation, mode, metric, timestamp);
+";
+
+/// An open results database plus the tags applied to every row of this run.
+pub struct ResultStore {
+    conn: Connection,
+    commit: String,
+    timestamp: i64,
+    hardware: String,
+    bench: String,
+}
+
+impl ResultStore {
+    /// Open (creating if needed) the store at `path` for a run of `bench`.
+    pub fn open(path: &str, bench: &str, hardware: &str) -> Result<Self, String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("cannot open results store {}: {}", path, e))?;
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| format!("cannot initialise results store {}: {}", path, e))?;
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        Ok(Self {
+            conn,
+            commit: current_commit(),
+            timestamp,
+            hardware: hardware.to_string(),
+            bench: bench.to_string(),
+        })
+    }
+
+    /// Insert `(metric, value)` pairs for one operation in one mode.
+    pub fn record(
+        &self,
+        operation: &str,
+        mode: &str,
+        metrics: &[(&str, f64)],
+    ) -> Result<(), String> {
+        for (metric, value) in metrics {
+            self.conn
+                .execute(
+                    "INSERT INTO results
+                     (commit_sha, timestamp, hardware, bench, operation, mode, metric, value)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        self.commit,
+                        self.timestamp,
+                        self.hardware,
+                        self.bench,
+                        operation,
+                        mode,
+                        metric,
+                        value
+                    ],
+                )
+                .map_err(|e| format!("cannot store {}/{}/{}: {}", operation, mode, metric, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// The commit under test: `STRATA_BENCH_COMMIT` if set (for CI checkouts
+/// without git metadata), else `git rev-parse --short HEAD`, else "unknown".
+pub fn current_commit() -> String {
+    if let Ok(commit) = std::env::var("STRATA_BENCH_COMMIT") {
+        return commit;
+    }
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Queries
+// ---------------------------------------------------------------------------
+
+/// One stored value of a metric.
+pub struct TrendPoint {
+    pub commit: String,
+    /// UTC run time, `YYYY-MM-DD HH:MM:SS`.
+    pub time: String,
+    pub hardware: String,
+    pub value: f64,
+}
+
+/// The most recent `limit` values of (operation, mode, metric), oldest first.
+pub fn history(
+    path: &str,
+    operation: &str,
+    mode: &str,
+    metric: &str,
+    limit: usize,
+) -> Result<Vec<TrendPoint>, String> {
+    let conn = Connection::open(path)
+        .map_err(|e| format!("cannot open results store {}: {}", path, e))?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT commit_sha, datetime(timestamp, 'unixepoch'), hardware, value
+             FROM results
+             WHERE operation = ?1 AND mode = ?2 AND metric = ?3
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?4",
+        )
+        .map_err(|e| format!("query failed: {}", e))?;
+    let rows = stmt
+        .query_map(params![operation, mode, metric, limit as i64], |row| {
+            Ok(TrendPoint {
+                commit: row.get(0)?,
+                time: row.get(1)?,
+                hardware: row.get(2)?,
+                value: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("query failed: {}", e))?;
+
+    let mut points = rows
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("query failed: {}", e))?;
+    points.reverse();
+    Ok(points)
+}
+
+/// Every distinct (operation, mode, metric) series in the store.
+pub fn series(path: &str) -> Result<Vec<(String, String, String)>, String> {
+    let conn = Connection::open(path)
+        .map_err(|e| format!("cannot open results store {}: {}", path, e))?;
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT operation, mode, metric FROM results ORDER BY 1, 2, 3")
+        .map_err(|e| format!("query failed: {}", e))?;
+    let rows = stmt
+        .query_map(params![], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("query failed: {}", e))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("query failed: {}", e))
+}
@@ -10,8 +10,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use harness::{
-    create_db, json_document, measure_with_counters, report_counters, report_percentiles,
-    DurabilityConfig, PERCENTILE_SAMPLES, WARMUP_COUNT,
+    create_db, json_document, measure_with_counters, percentile_samples, report_counters,
+    report_percentiles, report_scan_throughput, warmup_count, DurabilityConfig, ModeComparison,
 };
 use stratadb::Value;
 
@@ -35,7 +35,7 @@ fn json_set_root(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(u64::MAX / 2);
         let label = format!("json/set_root/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed);
             bench_db
                 .db
@@ -43,7 +43,7 @@ fn json_set_root(c: &mut Criterion) {
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
@@ -78,7 +78,7 @@ fn json_set_path(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("json/set_path/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed) % 1000;
             bench_db
                 .db
@@ -90,19 +90,21 @@ fn json_set_path(c: &mut Criterion) {
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
 
 fn json_get(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("json/get");
     group.throughput(Throughput::Elements(1));
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: json/get ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
-        for i in 0..WARMUP_COUNT {
+        for i in 0..warmup {
             bench_db
                 .db
                 .json_set(&format!("doc:{}", i), "$", json_document(i))
@@ -111,23 +113,28 @@ fn json_get(c: &mut Criterion) {
         let counter = AtomicU64::new(0);
         group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
             b.iter(|| {
-                let i = counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+                let i = counter.fetch_add(1, Ordering::Relaxed) % warmup;
                 bench_db.db.json_get(&format!("doc:{}", i), "$").unwrap();
             });
         });
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("json/get/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % warmup;
             bench_db.db.json_get(&format!("doc:{}", i), "$").unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        comparison.record("json/get", mode, &p);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
+    comparison.report("json/get");
 }
 
+/// Page size requested from json_list.
+const LIST_PAGE_SIZE: u64 = 100;
+
 fn json_list(c: &mut Criterion) {
     let mut group = c.benchmark_group("json/list");
     group.throughput(Throughput::Elements(1));
@@ -145,23 +152,29 @@ fn json_list(c: &mut Criterion) {
             b.iter(|| {
                 bench_db
                     .db
-                    .json_list(Some("bench:".to_string()), None, 100)
+                    .json_list(Some("bench:".to_string()), None, LIST_PAGE_SIZE)
                     .unwrap();
             });
         });
 
         let label = format!("json/list/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             bench_db
                 .db
-                .json_list(Some("bench:".to_string()), None, 100)
+                .json_list(Some("bench:".to_string()), None, LIST_PAGE_SIZE)
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        let (keys, _) = bench_db
+            .db
+            .json_list(Some("bench:".to_string()), None, LIST_PAGE_SIZE)
+            .unwrap();
+        let key_bytes = keys.iter().map(|k| k.len() as u64).sum();
+        report_scan_throughput(&label, &p, keys.len() as u64, key_bytes);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
 
 criterion_group!(benches, json_set_root, json_set_path, json_get, json_list);
-criterion_main!(benches);
+criterion_main!(benches, harness::baseline::check_baseline);
@@ -1,23 +1,50 @@
-//! KV primitive benchmarks: put, get, delete, list_prefix
+//! KV primitive benchmarks: put, get, delete, list_prefix, overwrite_vs_reinsert,
+//! multi_get, batch_put, history_depth, version_growth
 //!
 //! put and get include a value-size sweep (128B, 1KB, 8KB) to expose
-//! cache-hierarchy effects. All benchmarks report latency percentiles.
+//! cache-hierarchy effects, crossed with a value-entropy sweep (constant,
+//! mixed, random bytes) to expose compression cost on incompressible
+//! payloads. version_growth overwrites one hot key tens of thousands of times
+//! to show what unbounded version history costs in put latency and RSS, and
+//! whether `compact()` trims it. All benchmarks report latency percentiles.
+//! put also breaks WAL bytes/op into payload and overhead and reports write
+//! amplification (WAL bytes / value bytes).
+//!
+//! With `STRATA_BENCH_COLD=1`, get also reopens each disk database, evicts
+//! its files from the page cache and reports a separate `kv/get/cold/...`
+//! pass where every key is read once. The pass also reports how many bytes
+//! it read from disk, and says so when it read none (the engine served it
+//! from memory it loaded during recovery, so it is not a cold read).
 
 #[allow(unused)]
 #[path = "harness/mod.rs"]
 mod harness;
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use harness::cold::{cold_enabled, disk_read_bytes, reopen_cold};
+use harness::metrics::{current_rss_bytes, fmt_mb};
+use harness::scaling::fmt_duration;
 use harness::{
-    create_db, kv_key, kv_key_with_prefix, kv_value, kv_value_sized, measure_with_counters,
-    report_counters, report_percentiles, DurabilityConfig, ValueSize, PERCENTILE_SAMPLES,
-    WARMUP_COUNT,
+    batch_put, create_db, grow_versions, kv_get_many, kv_key, kv_key_with_prefix, kv_value,
+    measure_percentiles, measure_with_counters, percentile_samples, prepopulate_versions,
+    report_counters, report_counters_detailed, report_percentiles, report_scan_throughput,
+    warmup_count, BenchDb, DurabilityConfig, ModeComparison, ValueEntropy, ValueSize,
 };
+use stratadb::Value;
+
+/// Distinct pre-generated values per (size, entropy) pass, so random-value
+/// generation stays out of the timed loop.
+const VALUE_POOL: usize = 16;
+
+fn value_pool(size: ValueSize, entropy: ValueEntropy) -> Vec<Value> {
+    (0..VALUE_POOL).map(|_| entropy.value(size)).collect()
+}
 
 // =============================================================================
-// PUT — value-size sweep × durability
+// PUT — value-size sweep × value entropy × durability
 // =============================================================================
 
 fn kv_put(c: &mut Criterion) {
@@ -26,79 +53,127 @@ fn kv_put(c: &mut Criterion) {
 
     eprintln!("\n--- Latency Percentiles: kv/put ---");
     for size in ValueSize::ALL {
-        for mode in DurabilityConfig::ALL {
-            let bench_db = create_db(mode);
-            let counter = AtomicU64::new(0);
-            let id = format!("{}/{}", size.label(), mode.label());
+        for entropy in ValueEntropy::ALL {
+            let values = value_pool(size, entropy);
+            for mode in DurabilityConfig::ALL {
+                let bench_db = create_db(mode);
+                let counter = AtomicU64::new(0);
+                let id = format!("{}/{}/{}", size.label(), entropy.label(), mode.label());
 
-            group.bench_function(BenchmarkId::new("durability", &id), |b| {
-                b.iter(|| {
-                    let i = counter.fetch_add(1, Ordering::Relaxed);
-                    bench_db
-                        .db
-                        .kv_put(&kv_key(i), kv_value_sized(size))
-                        .unwrap();
+                group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                    b.iter(|| {
+                        let i = counter.fetch_add(1, Ordering::Relaxed);
+                        let value = values[i as usize % VALUE_POOL].clone();
+                        bench_db.db.kv_put(&kv_key(i), value).unwrap();
+                    });
                 });
-            });
 
-            // Percentile pass
-            let pct_counter = AtomicU64::new(u64::MAX / 2); // offset to avoid key collisions
-            let label = format!("kv/put/{}/{}", size.label(), mode.label());
-            let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-                let i = pct_counter.fetch_add(1, Ordering::Relaxed);
-                bench_db
-                    .db
-                    .kv_put(&kv_key(i), kv_value_sized(size))
-                    .unwrap();
-            });
-            report_percentiles(&label, &p);
-            report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+                // Percentile pass
+                let pct_counter = AtomicU64::new(u64::MAX / 2); // offset to avoid key collisions
+                let label = format!("kv/put/{}", id);
+                let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                    let i = pct_counter.fetch_add(1, Ordering::Relaxed);
+                    let value = values[i as usize % VALUE_POOL].clone();
+                    bench_db.db.kv_put(&kv_key(i), value).unwrap();
+                });
+                report_percentiles(&label, &p);
+                report_counters_detailed(
+                    &label,
+                    &counters,
+                    percentile_samples() as u64,
+                    size.byte_count(),
+                );
+            }
         }
     }
     group.finish();
 }
 
 // =============================================================================
-// GET — value-size sweep × durability
+// GET — value-size sweep × value entropy × durability
 // =============================================================================
 
 fn kv_get(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("kv/get");
     group.throughput(Throughput::Elements(1));
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: kv/get ---");
     for size in ValueSize::ALL {
-        for mode in DurabilityConfig::ALL {
-            let bench_db = create_db(mode);
-            // Pre-populate with this value size
-            for i in 0..WARMUP_COUNT {
-                bench_db
-                    .db
-                    .kv_put(&kv_key(i), kv_value_sized(size))
-                    .unwrap();
-            }
-            let counter = AtomicU64::new(0);
-            let id = format!("{}/{}", size.label(), mode.label());
+        for entropy in ValueEntropy::ALL {
+            for mode in DurabilityConfig::ALL {
+                let bench_db = create_db(mode);
+                // Pre-populate with this value size and entropy
+                for i in 0..warmup {
+                    bench_db.db.kv_put(&kv_key(i), entropy.value(size)).unwrap();
+                }
+                let counter = AtomicU64::new(0);
+                let id = format!("{}/{}/{}", size.label(), entropy.label(), mode.label());
 
-            group.bench_function(BenchmarkId::new("durability", &id), |b| {
-                b.iter(|| {
-                    let i = counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+                group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                    b.iter(|| {
+                        let i = counter.fetch_add(1, Ordering::Relaxed) % warmup;
+                        bench_db.db.kv_get(&kv_key(i)).unwrap();
+                    });
+                });
+
+                // Percentile pass
+                let pct_counter = AtomicU64::new(0);
+                let label = format!("kv/get/{}", id);
+                let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                    let i = pct_counter.fetch_add(1, Ordering::Relaxed) % warmup;
                     bench_db.db.kv_get(&kv_key(i)).unwrap();
                 });
-            });
+                report_percentiles(&label, &p);
+                let row = format!("kv/get/{}/{}", size.label(), entropy.label());
+                comparison.record(&row, mode, &p);
+                report_counters(&label, &counters, percentile_samples() as u64);
 
-            // Percentile pass
-            let pct_counter = AtomicU64::new(0);
-            let label = format!("kv/get/{}/{}", size.label(), mode.label());
-            let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-                let i = pct_counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
-                bench_db.db.kv_get(&kv_key(i)).unwrap();
-            });
-            report_percentiles(&label, &p);
-            report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+                if cold_enabled() {
+                    kv_get_cold(bench_db, warmup, &id);
+                }
+            }
         }
     }
     group.finish();
+    comparison.report("kv/get");
+}
+
+/// Cold pass: reopen with the page cache evicted and read each key at most
+/// once, so no sample is served from a page an earlier sample pulled in by
+/// key. No-op in cache mode.
+fn kv_get_cold(bench_db: BenchDb, warmup: u64, id: &str) {
+    let Ok((cold_db, _)) = reopen_cold(bench_db) else {
+        return;
+    };
+    let samples = percentile_samples().min(warmup as usize);
+    // Stride across the keyspace rather than reading neighbours, which may
+    // share a page or readahead window
+    let stride = (warmup / samples as u64).max(1);
+    let mut i = 0u64;
+    let read_before = disk_read_bytes();
+    let p = measure_percentiles(samples, || {
+        cold_db.db.kv_get(&kv_key(i * stride)).unwrap();
+        i += 1;
+    });
+    let read = read_before.zip(disk_read_bytes()).map(|(b, a)| a.saturating_sub(b));
+
+    let label = format!("kv/get/cold/{}", id);
+    report_percentiles(&label, &p);
+    match read {
+        Some(0) => eprintln!(
+            "  {:<45} no disk reads: served from engine memory, not a cold read",
+            label
+        ),
+        Some(bytes) => eprintln!(
+            "  {:<45} disk reads: {} ({:.0} B/get)",
+            label,
+            fmt_mb(bytes),
+            bytes as f64 / samples as f64
+        ),
+        None => eprintln!("  {:<45} disk reads: unknown (no /proc/self/io)", label),
+    }
 }
 
 // =============================================================================
@@ -106,19 +181,20 @@ fn kv_get(c: &mut Criterion) {
 // =============================================================================
 
 fn kv_delete(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("kv/delete");
     group.throughput(Throughput::Elements(1));
 
     eprintln!("\n--- Latency Percentiles: kv/delete ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
-        for i in 0..WARMUP_COUNT {
+        for i in 0..warmup {
             bench_db.db.kv_put(&kv_key(i), kv_value()).unwrap();
         }
         let counter = AtomicU64::new(0);
         group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
             b.iter(|| {
-                let i = counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+                let i = counter.fetch_add(1, Ordering::Relaxed) % warmup;
                 let key = kv_key(i);
                 bench_db.db.kv_delete(&key).unwrap();
                 bench_db.db.kv_put(&key, kv_value()).unwrap();
@@ -127,14 +203,14 @@ fn kv_delete(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("kv/delete/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % warmup;
             let key = kv_key(i);
             bench_db.db.kv_delete(&key).unwrap();
             bench_db.db.kv_put(&key, kv_value()).unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
@@ -143,6 +219,9 @@ fn kv_delete(c: &mut Criterion) {
 // LIST PREFIX — 1KB default, all durability modes
 // =============================================================================
 
+/// Keys per prefix populated for the list benchmark.
+const LIST_KEYS_PER_PREFIX: u64 = 1000;
+
 fn kv_list_prefix(c: &mut Criterion) {
     let mut group = c.benchmark_group("kv/list_prefix");
     group.throughput(Throughput::Elements(1));
@@ -150,7 +229,7 @@ fn kv_list_prefix(c: &mut Criterion) {
     eprintln!("\n--- Latency Percentiles: kv/list_prefix ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
-        for i in 0..1000u64 {
+        for i in 0..LIST_KEYS_PER_PREFIX {
             bench_db
                 .db
                 .kv_put(&kv_key_with_prefix("alpha:", i), kv_value())
@@ -167,14 +246,368 @@ fn kv_list_prefix(c: &mut Criterion) {
         });
 
         let label = format!("kv/list_prefix/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             bench_db.db.kv_list(Some("alpha:")).unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        let keys = bench_db.db.kv_list(Some("alpha:")).unwrap();
+        let key_bytes = keys.iter().map(|k| k.len() as u64).sum();
+        report_scan_throughput(&label, &p, keys.len() as u64, key_bytes);
+        report_counters(&label, &counters, percentile_samples() as u64);
+    }
+    group.finish();
+    // kv_list returns an eager Vec, so first-result latency is the full latency
+    eprintln!(
+        "  note: kv_list is eager (no limit or iterator); time-to-first-result = full scan latency"
+    );
+}
+
+// =============================================================================
+// OVERWRITE vs DELETE+REINSERT — 1KB default, all durability modes
+// =============================================================================
+
+/// Overwrite pattern: a single `kv_put` on an existing key.
+const PATTERN_OVERWRITE: &str = "overwrite";
+/// Reinsert pattern: `kv_delete` followed by `kv_put` on the same key.
+const PATTERN_REINSERT: &str = "delete_reinsert";
+
+fn kv_overwrite_vs_reinsert(c: &mut Criterion) {
+    let warmup = warmup_count();
+    let mut group = c.benchmark_group("kv/overwrite_vs_reinsert");
+    group.throughput(Throughput::Elements(1));
+
+    eprintln!("\n--- Latency Percentiles: kv/overwrite_vs_reinsert ---");
+    for pattern in [PATTERN_OVERWRITE, PATTERN_REINSERT] {
+        for mode in DurabilityConfig::ALL {
+            let bench_db = create_db(mode);
+            for i in 0..warmup {
+                bench_db.db.kv_put(&kv_key(i), kv_value()).unwrap();
+            }
+            let write = |i: u64| {
+                let key = kv_key(i % warmup);
+                if pattern == PATTERN_REINSERT {
+                    bench_db.db.kv_delete(&key).unwrap();
+                }
+                bench_db.db.kv_put(&key, kv_value()).unwrap();
+            };
+
+            let counter = AtomicU64::new(0);
+            let id = format!("{}/{}", pattern, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| write(counter.fetch_add(1, Ordering::Relaxed)));
+            });
+
+            let pct_counter = AtomicU64::new(0);
+            let label = format!("kv/{}/{}", pattern, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                write(pct_counter.fetch_add(1, Ordering::Relaxed));
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+
+            // Version history retained for a key rewritten by both passes
+            let versions = bench_db
+                .db
+                .kv_getv(&kv_key(0))
+                .unwrap()
+                .map(|v| v.len())
+                .unwrap_or(0);
+            eprintln!("  {:<45} versions retained for key 0: {}", label, versions);
+        }
+    }
+    group.finish();
+}
+
+// =============================================================================
+// MULTI-GET — batched vs individual reads, batch-size sweep
+// =============================================================================
+
+/// Keys per read batch.
+const MULTI_GET_BATCH_SIZES: [u64; 4] = [1, 10, 100, 1000];
+/// Strategy: a loop of individual `kv_get` calls.
+const STRATEGY_INDIVIDUAL: &str = "individual";
+/// Strategy: one `kv_get_many` call for the whole batch.
+const STRATEGY_BATCHED: &str = "batched";
+
+fn kv_multi_get(c: &mut Criterion) {
+    let warmup = warmup_count();
+    let mut group = c.benchmark_group("kv/multi_get");
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: kv/multi_get ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        for i in 0..warmup {
+            bench_db.db.kv_put(&kv_key(i), kv_value()).unwrap();
+        }
+
+        for batch in MULTI_GET_BATCH_SIZES {
+            group.throughput(Throughput::Elements(batch));
+            let make_keys = |start: u64| -> Vec<String> {
+                (0..batch).map(|j| kv_key((start + j) % warmup)).collect()
+            };
+
+            let mut per_key_p50 = [Duration::ZERO; 2];
+            for (s, strategy) in [STRATEGY_INDIVIDUAL, STRATEGY_BATCHED].into_iter().enumerate() {
+                let read = |keys: &[String]| {
+                    if strategy == STRATEGY_BATCHED {
+                        kv_get_many(&bench_db.db, keys);
+                    } else {
+                        for key in keys {
+                            bench_db.db.kv_get(key).unwrap();
+                        }
+                    }
+                };
+
+                let counter = AtomicU64::new(0);
+                let id = format!("{}/{}/{}", strategy, batch, mode.label());
+                group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                    b.iter(|| read(&make_keys(counter.fetch_add(batch, Ordering::Relaxed))));
+                });
+
+                let pct_counter = AtomicU64::new(0);
+                let label = format!("kv/multi_get/{}/{}/{}", strategy, batch, mode.label());
+                let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                    read(&make_keys(pct_counter.fetch_add(batch, Ordering::Relaxed)));
+                });
+                report_percentiles(&label, &p);
+                report_counters(&label, &counters, percentile_samples() as u64);
+                per_key_p50[s] = p.p50 / batch as u32;
+            }
+            rows.push((mode, batch, per_key_p50[0], per_key_p50[1]));
+        }
+    }
+    group.finish();
+
+    eprintln!("\n--- kv/multi_get per-key p50 ---");
+    eprintln!(
+        "  {:<10} {:>6} {:>14} {:>14} {:>10}",
+        "mode", "batch", STRATEGY_INDIVIDUAL, STRATEGY_BATCHED, "speedup"
+    );
+    for (mode, batch, individual, batched) in rows {
+        let speedup = if batched.is_zero() {
+            0.0
+        } else {
+            individual.as_secs_f64() / batched.as_secs_f64()
+        };
+        eprintln!(
+            "  {:<10} {:>6} {:>14} {:>14} {:>10}",
+            mode.label(),
+            batch,
+            fmt_duration(individual),
+            fmt_duration(batched),
+            format!("{:.2}x", speedup),
+        );
+    }
+}
+
+// =============================================================================
+// BATCH PUT — one transaction per batch, batch-size sweep
+// =============================================================================
+
+/// Keys written per `batch_put` call.
+const BATCH_PUT_SIZES: [u64; 4] = [1, 10, 100, 1000];
+
+/// Percentile samples for a batch size: fewer samples for larger batches,
+/// so every size writes about the same number of keys.
+fn batch_put_samples(batch: u64) -> usize {
+    let samples = percentile_samples();
+    (samples * 10 / batch as usize).clamp(10, samples)
+}
+
+fn kv_batch_put(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kv/batch_put");
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: kv/batch_put ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let value = kv_value();
+        // Shared across batch sizes so every batch writes fresh keys
+        let counter = AtomicU64::new(0);
+        let make_batch = |start: u64, batch: u64| -> Vec<(String, Value)> {
+            (start..start + batch).map(|i| (kv_key(i), value.clone())).collect()
+        };
+
+        for batch in BATCH_PUT_SIZES {
+            group.throughput(Throughput::Elements(batch));
+            let id = format!("{}/{}", batch, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| {
+                    let start = counter.fetch_add(batch, Ordering::Relaxed);
+                    batch_put(&bench_db.db, &make_batch(start, batch));
+                });
+            });
+
+            let samples = batch_put_samples(batch);
+            let label = format!("kv/batch_put/{}", id);
+            let (p, counters) = measure_with_counters(&bench_db, samples, || {
+                let start = counter.fetch_add(batch, Ordering::Relaxed);
+                batch_put(&bench_db.db, &make_batch(start, batch));
+            });
+            report_percentiles(&label, &p);
+            // Per key, so the amortization across the batch shows directly
+            let keys = samples as u64 * batch;
+            report_counters(&label, &counters, keys);
+            let per_key_p50 = p.p50 / batch as u32;
+            let syncs_per_key = counters.sync_calls as f64 / keys as f64;
+            rows.push((mode, batch, per_key_p50, syncs_per_key));
+        }
+    }
+    group.finish();
+
+    eprintln!("\n--- kv/batch_put per-key cost ---");
+    eprintln!("  {:<10} {:>6} {:>14} {:>12}", "mode", "batch", "p50/key", "syncs/key");
+    for (mode, batch, per_key_p50, syncs_per_key) in rows {
+        eprintln!(
+            "  {:<10} {:>6} {:>14} {:>12.4}",
+            mode.label(),
+            batch,
+            fmt_duration(per_key_p50),
+            syncs_per_key,
+        );
+    }
+}
+
+// =============================================================================
+// HISTORY DEPTH — point read and full history vs version count
+// =============================================================================
+
+/// Versions written to the benchmarked key before measuring.
+const HISTORY_DEPTHS: [usize; 4] = [1, 10, 100, 1000];
+
+fn kv_history_depth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kv/history_depth");
+    group.throughput(Throughput::Elements(1));
+
+    eprintln!("\n--- Latency Percentiles: kv/history_depth ---");
+    for depth in HISTORY_DEPTHS {
+        for mode in DurabilityConfig::ALL {
+            let bench_db = create_db(mode);
+            let key = kv_key(depth as u64);
+            prepopulate_versions(&bench_db.db, &key, depth, |v| Value::Int(v as i64));
+
+            let id = format!("get/{}/{}", depth, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| bench_db.db.kv_get(&key).unwrap());
+            });
+            let id = format!("getv/{}/{}", depth, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| bench_db.db.kv_getv(&key).unwrap());
+            });
+
+            let label = format!("kv/history_depth/get/{}/{}", depth, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                bench_db.db.kv_get(&key).unwrap();
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+
+            let label = format!("kv/history_depth/getv/{}/{}", depth, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                bench_db.db.kv_getv(&key).unwrap();
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+        }
+    }
+    group.finish();
+}
+
+// =============================================================================
+// VERSION GROWTH — put latency and memory as one key's history grows
+// =============================================================================
+
+/// Version counts at which the hot key's put latency and RSS are sampled.
+const VERSION_GROWTH_CHECKPOINTS: [usize; 4] = [100, 1_000, 10_000, 50_000];
+
+/// Always mode fsyncs every put, so it stops growing history at this many
+/// versions and samples at most 200 puts per checkpoint, as `vector_upsert`
+/// does.
+const VERSION_GROWTH_ALWAYS_MAX: usize = 1_000;
+
+fn kv_version_growth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kv/version_growth");
+    group.throughput(Throughput::Elements(1));
+
+    eprintln!("\n--- Latency Percentiles: kv/version_growth ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let key = "hot-counter";
+        let rss_start = current_rss_bytes();
+        let counter = AtomicU64::new(0);
+        let (max_versions, samples) = match mode {
+            DurabilityConfig::Always => (VERSION_GROWTH_ALWAYS_MAX, 200.min(percentile_samples())),
+            _ => (usize::MAX, percentile_samples()),
+        };
+
+        for target in VERSION_GROWTH_CHECKPOINTS
+            .into_iter()
+            .filter(|&t| t <= max_versions)
+        {
+            // Earlier passes already added versions, so count what is there
+            let versions = grow_versions(&bench_db.db, key, target, |v| Value::Int(v as i64));
+
+            let id = format!("put/{}/{}", target, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| {
+                    let i = counter.fetch_add(1, Ordering::Relaxed);
+                    bench_db.db.kv_put(key, Value::Int(i as i64)).unwrap();
+                });
+            });
+
+            let label = format!("kv/version_growth/{}", id);
+            let (p, counters) = measure_with_counters(&bench_db, samples, || {
+                let i = counter.fetch_add(1, Ordering::Relaxed);
+                bench_db.db.kv_put(key, Value::Int(i as i64)).unwrap();
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, samples as u64);
+            eprintln!(
+                "  {:<50} versions={:<8} rss={} (+{} since first put)",
+                label,
+                versions,
+                fmt_mb(current_rss_bytes()),
+                fmt_mb(current_rss_bytes().saturating_sub(rss_start))
+            );
+        }
+
+        // Does compaction trim the retained history?
+        let before = bench_db.db.kv_getv(key).unwrap().map_or(0, |v| v.len());
+        let rss_before = current_rss_bytes();
+        bench_db.db.compact().unwrap();
+        let after = bench_db.db.kv_getv(key).unwrap().map_or(0, |v| v.len());
+        let label = format!("kv/version_growth/compact/{}", mode.label());
+        eprintln!(
+            "  {:<50} versions {} -> {}, rss {} -> {}",
+            label,
+            before,
+            after,
+            fmt_mb(rss_before),
+            fmt_mb(current_rss_bytes())
+        );
+
+        let label = format!("kv/version_growth/put/after_compact/{}", mode.label());
+        let (p, counters) = measure_with_counters(&bench_db, samples, || {
+            let i = counter.fetch_add(1, Ordering::Relaxed);
+            bench_db.db.kv_put(key, Value::Int(i as i64)).unwrap();
+        });
+        report_percentiles(&label, &p);
+        report_counters(&label, &counters, samples as u64);
     }
     group.finish();
 }
 
-criterion_group!(benches, kv_put, kv_get, kv_delete, kv_list_prefix);
-criterion_main!(benches);
+criterion_group!(
+    benches,
+    kv_put,
+    kv_get,
+    kv_delete,
+    kv_list_prefix,
+    kv_overwrite_vs_reinsert,
+    kv_multi_get,
+    kv_batch_put,
+    kv_history_depth,
+    kv_version_growth
+);
+criterion_main!(benches, harness::baseline::check_baseline);
@@ -0,0 +1,303 @@
+//! Crash-Recovery Benchmark for StrataDB
+//!
+//! For each operation count, writes 1KB KV entries to a disk database without
+//! flushing, drops it (a crash as far as the WAL is concerned), then times
+//! `Database::open` + `Strata::from_database` on the same directory — i.e.
+//! how long WAL replay takes. Reported per durability mode, alongside the
+//! on-disk size being replayed and the replay rate.
+//!
+//! Cache mode has no WAL to replay and is not run. Every run starts from a
+//! fresh directory, since recovery may checkpoint the WAL it replayed.
+//!
+//! Uses a custom harness (like fill_level.rs): each sample needs seconds of
+//! untimed setup, and the axis of interest is operation count.
+//!
+//! Run:    `cargo bench --bench recovery`
+//! Custom: `cargo bench --bench recovery -- --ops 1000,50000 --runs 5`
+//! Single: `cargo bench --bench recovery -- --durability standard`
+//! CSV:    `cargo bench --bench recovery -- --csv`
+//! Help:   `cargo bench --bench recovery -- --help`
+
+#[allow(unused)]
+#[path = "harness/mod.rs"]
+mod harness;
+
+use harness::metrics::fmt_mb;
+use harness::scaling::{fmt_duration, fmt_num};
+use harness::{check_unknown_args, dir_size_bytes, kv_value, print_hardware_info, DurabilityConfig};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use stratadb::{Database, Strata, Value};
+
+// ---------------------------------------------------------------------------
+// Parameters
+// ---------------------------------------------------------------------------
+
+const DEFAULT_OPS: &[usize] = &[1_000, 10_000, 100_000];
+const DEFAULT_RUNS: usize = 3;
+/// Modes with a WAL; cache mode has nothing to recover.
+const RECOVERABLE_MODES: [DurabilityConfig; 2] =
+    [DurabilityConfig::Standard, DurabilityConfig::Always];
+
+fn key(i: usize) -> String {
+    format!("recover:{:012}", i)
+}
+
+// ---------------------------------------------------------------------------
+// Benchmark
+// ---------------------------------------------------------------------------
+
+struct RecoveryResult {
+    mode: DurabilityConfig,
+    ops: usize,
+    /// Data directory size at the moment of the crash (last run).
+    wal_bytes: u64,
+    /// Reopen time per run, sorted ascending.
+    times: Vec<Duration>,
+    /// Whether the last write was readable after the last reopen. Standard
+    /// mode may legitimately lose an unsynced tail.
+    last_write_recovered: bool,
+}
+
+impl RecoveryResult {
+    fn median(&self) -> Duration {
+        self.times[self.times.len() / 2]
+    }
+
+    fn ops_per_sec(&self) -> f64 {
+        self.ops as f64 / self.median().as_secs_f64()
+    }
+}
+
+fn open(dir: &Path) -> Strata {
+    Strata::from_database(Database::open(dir).expect("failed to open database"))
+        .expect("failed to wrap database")
+}
+
+/// Write `ops` entries, drop without flushing, and time the reopen.
+fn crash_and_recover(mode: DurabilityConfig, ops: usize) -> (Duration, u64, bool) {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    if let DurabilityConfig::Always = mode {
+        std::fs::write(dir.path().join("strata.toml"), "durability = \"always\"\n")
+            .expect("failed to write always config");
+    }
+
+    {
+        let db = open(dir.path());
+        let value = kv_value();
+        for i in 0..ops {
+            db.kv_put(&key(i), value.clone()).unwrap();
+        }
+        // NO flush — dropped as if the process crashed
+    }
+    let wal_bytes = dir_size_bytes(dir.path());
+
+    let start = Instant::now();
+    let db = open(dir.path());
+    let elapsed = start.elapsed();
+
+    let recovered = matches!(db.kv_get(&key(ops - 1)).unwrap(), Some(Value::Bytes(_)));
+    (elapsed, wal_bytes, recovered)
+}
+
+fn run(mode: DurabilityConfig, ops: usize, runs: usize) -> RecoveryResult {
+    let mut times = Vec::with_capacity(runs);
+    let mut wal_bytes = 0;
+    let mut last_write_recovered = false;
+    for _ in 0..runs {
+        let (elapsed, bytes, recovered) = crash_and_recover(mode, ops);
+        times.push(elapsed);
+        wal_bytes = bytes;
+        last_write_recovered = recovered;
+    }
+    times.sort_unstable();
+    RecoveryResult { mode, ops, wal_bytes, times, last_write_recovered }
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+fn print_table_header() {
+    eprintln!(
+        "{:<10} {:>10} {:>11} {:>11} {:>11} {:>11} {:>14} {:>9}",
+        "mode", "ops", "on_disk", "min", "median", "max", "replay ops/s", "last_op"
+    );
+    eprintln!("{}", "-".repeat(94));
+}
+
+fn print_table_row(r: &RecoveryResult) {
+    eprintln!(
+        "{:<10} {:>10} {:>11} {:>11} {:>11} {:>11} {:>14} {:>9}",
+        r.mode.label(),
+        fmt_num(r.ops as u64),
+        fmt_mb(r.wal_bytes),
+        fmt_duration(r.times[0]),
+        fmt_duration(r.median()),
+        fmt_duration(r.times[r.times.len() - 1]),
+        fmt_num(r.ops_per_sec() as u64),
+        if r.last_write_recovered { "yes" } else { "lost" }
+    );
+}
+
+fn print_csv_header() {
+    println!("mode,ops,on_disk_bytes,runs,min_ns,median_ns,max_ns,last_write_recovered");
+}
+
+fn print_csv_row(r: &RecoveryResult) {
+    println!(
+        "{},{},{},{},{},{},{},{}",
+        r.mode.label(),
+        r.ops,
+        r.wal_bytes,
+        r.times.len(),
+        r.times[0].as_nanos(),
+        r.median().as_nanos(),
+        r.times[r.times.len() - 1].as_nanos(),
+        r.last_write_recovered
+    );
+}
+
+// ---------------------------------------------------------------------------
+// CLI
+// ---------------------------------------------------------------------------
+
+struct Config {
+    ops: Vec<usize>,
+    runs: usize,
+    modes: Vec<DurabilityConfig>,
+    csv: bool,
+}
+
+fn print_usage() {
+    let ops: Vec<String> = DEFAULT_OPS.iter().map(|n| n.to_string()).collect();
+    println!(
+        "\
+Recovery benchmark: WAL replay time after a crash, by operation count.
+
+Usage: cargo bench --bench recovery -- [OPTIONS]
+
+Options:
+  --ops <list>          Comma-separated operation counts to write before the crash
+                        [default: {ops}]
+  --runs <n>            Crash/reopen cycles per point; min/median/max reported
+                        [default: {runs}]
+  --durability <list>   Comma-separated: standard, always [default: both]
+  --csv                 Print results as CSV on stdout
+  --strict              Exit with an error on unknown arguments instead of warning
+  -h, --help            Print this help and exit
+
+Examples:
+  cargo bench --bench recovery -- --ops 1000,50000 --runs 5
+  cargo bench --bench recovery -- --durability always --csv",
+        ops = ops.join(","),
+        runs = DEFAULT_RUNS,
+    );
+}
+
+fn parse_mode(s: &str) -> DurabilityConfig {
+    match s.trim() {
+        "standard" => DurabilityConfig::Standard,
+        "always" => DurabilityConfig::Always,
+        "cache" => {
+            eprintln!("error: cache mode has no WAL to recover (use standard, always)");
+            std::process::exit(2);
+        }
+        other => {
+            eprintln!("error: unknown durability mode {:?} (standard, always)", other);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn parse_args() -> Config {
+    let args: Vec<String> = std::env::args().collect();
+    let mut config = Config {
+        ops: DEFAULT_OPS.to_vec(),
+        runs: DEFAULT_RUNS,
+        modes: RECOVERABLE_MODES.to_vec(),
+        csv: false,
+    };
+
+    let mut unknown = Vec::new();
+    let mut strict = false;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--ops" => {
+                i += 1;
+                config.ops = args[i]
+                    .split(',')
+                    .filter_map(|s| s.trim().parse().ok())
+                    .filter(|&n| n > 0)
+                    .collect();
+            }
+            "--runs" => {
+                i += 1;
+                config.runs = args[i].parse().unwrap_or(DEFAULT_RUNS).max(1);
+            }
+            "--durability" => {
+                i += 1;
+                config.modes = args[i].split(',').map(parse_mode).collect();
+            }
+            "--csv" => config.csv = true,
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
+        }
+        i += 1;
+    }
+    check_unknown_args(&unknown, strict);
+
+    config
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() {
+    let config = parse_args();
+    print_hardware_info();
+
+    if config.csv {
+        print_csv_header();
+    } else {
+        eprintln!("=== StrataDB Recovery Benchmark ===");
+        eprintln!(
+            "Parameters: 1KB values, ops {:?}, {} run(s) per point",
+            config.ops, config.runs
+        );
+        eprintln!("Time is Database::open + Strata::from_database after an unflushed drop.");
+        eprintln!();
+    }
+
+    let mut results = Vec::new();
+    for &mode in &config.modes {
+        for &ops in &config.ops {
+            if !config.csv {
+                eprint!("  {} @ {} ops...", mode.label(), fmt_num(ops as u64));
+            }
+            let result = run(mode, ops, config.runs);
+            if config.csv {
+                print_csv_row(&result);
+            } else {
+                eprintln!(" {}", fmt_duration(result.median()));
+            }
+            results.push(result);
+        }
+    }
+
+    if !config.csv {
+        eprintln!();
+        print_table_header();
+        for r in &results {
+            print_table_row(r);
+        }
+        eprintln!();
+        eprintln!("=== Benchmark complete ===");
+    }
+}
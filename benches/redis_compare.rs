@@ -9,17 +9,39 @@
 //!
 //! Run: `cargo bench --bench redis_compare`
 //! Random keys: `cargo bench --bench redis_compare -- -r 100000`
+//! Replay:  `cargo bench --bench redis_compare -- -r 100000 --seed 42`
+//! Clients: `cargo bench --bench redis_compare -- -c 50 -r 100000`
+//! Pipeline: `cargo bench --bench redis_compare -- -P 16 -t set,get`
 //! Quick: `cargo bench --bench redis_compare -- --durability cache -q`
 //! CSV:  `cargo bench --bench redis_compare -- --csv`
+//! JSON: `cargo bench --bench redis_compare -- --json > results.ndjson`
+//! Markdown: `cargo bench --bench redis_compare -- --markdown > results.md`
+//! WAL:  `cargo bench --bench redis_compare -- --wal-focus`
+//! SLA:  `cargo bench --bench redis_compare -- --budget budgets.toml`
+//! Live:  `cargo bench --bench redis_compare --features redis-live -- --redis-url 127.0.0.1:6379`
+//! History: `cargo bench --bench redis_compare --features sqlite-compare -- --store results.db`
+//! Help: `cargo bench --bench redis_compare -- --help`
 
 #[allow(unused)]
 #[path = "harness/mod.rs"]
 mod harness;
 
-use harness::{create_db, print_hardware_info, BenchDb, DurabilityConfig};
+use harness::budget::{BudgetChecker, LatencyBudgets};
+#[cfg(feature = "redis-live")]
+use harness::resp::RespClient;
+use harness::scaling::thread_seed;
+#[cfg(feature = "sqlite-compare")]
+use harness::{hardware_summary, store::ResultStore};
+use harness::{
+    batch_put, check_unknown_args, counter_delta, create_db, print_hardware_info,
+    print_hardware_info_markdown, print_markdown_table, print_wal_focus_table, snapshot_counters,
+    BenchDb, DurabilityConfig, WalRow,
+};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Barrier;
 use std::time::{Duration, Instant};
-use stratadb::{Command, Value};
+use stratadb::{Command, Strata, Value, WalCounters};
 
 // ---------------------------------------------------------------------------
 // Parameters (matching redis-benchmark defaults)
@@ -60,11 +82,14 @@ struct KeyGen {
     rng_state: u64,
 }
 
+/// Default `--seed`: the LCG state every `KeyGen` starts from.
+const DEFAULT_SEED: u64 = 0xdeadbeef;
+
 impl KeyGen {
-    fn new(keyspace: u64) -> Self {
+    fn new(keyspace: u64, seed: u64) -> Self {
         Self {
             keyspace,
-            rng_state: 0xdeadbeef,
+            rng_state: seed,
         }
     }
 
@@ -107,34 +132,97 @@ struct BenchResult {
     p99: Duration,
     min: Duration,
     max: Duration,
+    /// Operations per pipelined transaction (`-P`); 1 when not pipelined.
+    /// Latencies are per pipeline, `total_ops` and `ops_per_sec` per op.
+    pipeline: usize,
+    /// WAL counter delta over the timed loop (zero in cache mode).
+    wal: WalCounters,
 }
 
 // ---------------------------------------------------------------------------
 // Core measurement function
 // ---------------------------------------------------------------------------
 
+/// Number of parallel clients set by `-c` (redis-benchmark's `-c`).
+static CLIENTS: AtomicUsize = AtomicUsize::new(1);
+
 /// Run a benchmark. No warmup phase — matches redis-benchmark which starts
 /// timing immediately (see benchmark() at line 946).
+///
+/// With `-c 1` (the default) every operation runs on `db` in this thread.
+/// With more clients, `total_ops` is split across that many threads, each on
+/// its own `new_handle()` and its own `KeyGen` stream, released together by a
+/// barrier as in `run_scaling_experiment`; latencies are merged and the RPS
+/// is combined over the wall time of the slowest client.
 fn run_bench(
+    db: &BenchDb,
     name: &str,
     redis_equiv: &str,
     total_ops: usize,
-    mut bench_fn: impl FnMut(&mut KeyGen),
+    bench_fn: impl Fn(&Strata, &mut KeyGen) + Sync,
     keygen: &mut KeyGen,
 ) -> BenchResult {
+    let clients = CLIENTS.load(Ordering::Relaxed).max(1);
+
     // Measure every operation
     let mut latencies = Vec::with_capacity(total_ops);
-    let wall_start = Instant::now();
+    let wal_before = snapshot_counters(db);
+    let wall_start;
+
+    if clients == 1 {
+        wall_start = Instant::now();
+        for _ in 0..total_ops {
+            let op_start = Instant::now();
+            bench_fn(&db.db, keygen);
+            latencies.push(op_start.elapsed());
+        }
+    } else {
+        let barrier = Barrier::new(clients + 1);
+        let bench_fn = &bench_fn;
+        let barrier = &barrier;
+        wall_start = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..clients)
+                .map(|client| {
+                    // Spread the remainder so the clients sum to total_ops
+                    let ops = total_ops / clients + usize::from(client < total_ops % clients);
+                    let strata = db.db.new_handle().expect("failed to create Strata for client");
+                    let seed = thread_seed(keygen.rng_state, client, 0);
+                    let mut kg = KeyGen::new(keygen.keyspace, seed);
+                    scope.spawn(move || {
+                        let mut latencies = Vec::with_capacity(ops);
+                        barrier.wait();
+                        for _ in 0..ops {
+                            let op_start = Instant::now();
+                            bench_fn(&strata, &mut kg);
+                            latencies.push(op_start.elapsed());
+                        }
+                        latencies
+                    })
+                })
+                .collect();
 
-    for _ in 0..total_ops {
-        let op_start = Instant::now();
-        bench_fn(keygen);
-        latencies.push(op_start.elapsed());
+            barrier.wait(); // release all clients
+            let wall_start = Instant::now();
+            for h in handles {
+                latencies.extend(h.join().expect("client thread panicked"));
+            }
+            wall_start
+        });
     }
 
     let elapsed = wall_start.elapsed();
+    let wal = counter_delta(&wal_before, &snapshot_counters(db));
+    summarize(name, redis_equiv, latencies, elapsed, wal)
+}
 
-    // Compute statistics
+/// Compute a `BenchResult` from per-operation latencies and the wall time.
+fn summarize(
+    name: &str,
+    redis_equiv: &str,
+    mut latencies: Vec<Duration>,
+    elapsed: Duration,
+    wal: WalCounters,
+) -> BenchResult {
     latencies.sort_unstable();
     let len = latencies.len();
     let sum: Duration = latencies.iter().sum();
@@ -151,9 +239,131 @@ fn run_bench(
         p99: latencies[(len * 99 / 100).min(len - 1)],
         min: latencies[0],
         max: latencies[len - 1],
+        pipeline: 1,
+        wal,
     }
 }
 
+/// Pipeline depth set by `-P` (redis-benchmark's `-P`).
+static PIPELINE: AtomicUsize = AtomicUsize::new(1);
+
+fn pipeline_depth() -> usize {
+    PIPELINE.load(Ordering::Relaxed).max(1)
+}
+
+/// Run a benchmark with `-P` pipelining. Strata is embedded, so there are no
+/// round-trips to amortize; the closest equivalent is batching each group of
+/// `pipeline_depth()` commands into one Session transaction, as
+/// `bench_mset_10` does. Latency is measured per pipeline (like
+/// redis-benchmark with -P); throughput is reported per logical op.
+fn run_pipelined(
+    db: &BenchDb,
+    name: &str,
+    redis_equiv: &str,
+    total_ops: usize,
+    make_cmd: impl Fn(&mut KeyGen) -> Command + Sync,
+    keygen: &mut KeyGen,
+) -> BenchResult {
+    let depth = pipeline_depth();
+    let pipelines = total_ops.div_ceil(depth);
+    let redis_equiv = format!("{} (-P {}: txn per pipeline)", redis_equiv, depth);
+    let mut result = run_bench(db, name, &redis_equiv, pipelines, |strata, kg| {
+        let mut session = strata.session();
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        for _ in 0..depth {
+            session.execute(make_cmd(kg)).unwrap();
+        }
+        session.execute(Command::TxnCommit).unwrap();
+    }, keygen);
+    result.total_ops = pipelines * depth;
+    result.ops_per_sec = result.total_ops as f64 / result.elapsed.as_secs_f64();
+    result.pipeline = depth;
+    result
+}
+
+// ---------------------------------------------------------------------------
+// Live Redis comparison (--redis-url, feature `redis-live`)
+// ---------------------------------------------------------------------------
+
+/// Tests also run against the live Redis: `(name, prefix)` where `prefix` is
+/// the key prefix both sides use, so `-r` picks the same keys on each.
+#[cfg(feature = "redis-live")]
+const LIVE_REDIS_TESTS: &[(&str, &str)] = &[("SET", "key"), ("GET", "key"), ("INCR", "counter")];
+
+/// Run `LIVE_REDIS_TESTS` against the Redis at `url` over one connection,
+/// unpipelined, with the same request count, payload, keyspace and seed as
+/// the Strata side. Keys are overwritten but nothing is flushed, exactly as
+/// redis-benchmark does.
+#[cfg(feature = "redis-live")]
+fn run_live_redis(url: &str, config: &Config, data: &[u8]) -> Vec<BenchResult> {
+    let mut client = RespClient::connect(url).unwrap_or_else(|e| {
+        eprintln!("error: cannot connect to Redis at {}: {}", url, e);
+        std::process::exit(2);
+    });
+
+    let mut results = Vec::new();
+    for &(name, prefix) in LIVE_REDIS_TESTS {
+        if !test_is_selected(name, &config.tests) {
+            continue;
+        }
+        let mut kg = KeyGen::new(config.keyspace, config.seed);
+        let mut latencies = Vec::with_capacity(config.requests);
+        let wall_start = Instant::now();
+        for _ in 0..config.requests {
+            let key = kg.key(prefix);
+            let op_start = Instant::now();
+            let reply = match name {
+                "SET" => client.command(&[b"SET", key.as_bytes(), data]),
+                "GET" => client.command(&[b"GET", key.as_bytes()]),
+                _ => client.command(&[b"INCR", key.as_bytes()]),
+            };
+            latencies.push(op_start.elapsed());
+            if let Err(e) = reply {
+                eprintln!("error: Redis {} failed: {}", name, e);
+                std::process::exit(2);
+            }
+        }
+        let elapsed = wall_start.elapsed();
+        results.push(summarize(name, name, latencies, elapsed, WalCounters::default()));
+    }
+    results
+}
+
+/// Print Strata-vs-Redis columns for every test run on both sides. `strata`
+/// holds this durability mode's results as `(name, rps, p50, p99)`.
+#[cfg(feature = "redis-live")]
+fn print_live_comparison(strata: &[(String, f64, Duration, Duration)], redis: &[BenchResult]) {
+    eprintln!("--- Strata vs live Redis ---");
+    eprintln!(
+        "  {:<8} {:>14} {:>14} {:>8} {:>12} {:>12} {:>12} {:>12}",
+        "test", "strata_rps", "redis_rps", "ratio", "strata_p50", "redis_p50", "strata_p99",
+        "redis_p99"
+    );
+    for (name, rps, p50, p99) in strata {
+        let Some(r) = redis.iter().find(|r| &r.name == name) else {
+            continue;
+        };
+        eprintln!(
+            "  {:<8} {:>14.2} {:>14.2} {:>7.2}x {:>12.3} {:>12.3} {:>12.3} {:>12.3}",
+            name,
+            rps,
+            r.ops_per_sec,
+            rps / r.ops_per_sec,
+            duration_ms(*p50),
+            duration_ms(r.p50),
+            duration_ms(*p99),
+            duration_ms(r.p99),
+        );
+    }
+    eprintln!("  (latencies in msec; Redis side is one unpipelined TCP connection)");
+    eprintln!();
+}
+
 // ---------------------------------------------------------------------------
 // Output formatters
 // ---------------------------------------------------------------------------
@@ -172,8 +382,20 @@ fn print_verbose(r: &BenchResult, payload_size: usize) {
         r.total_ops,
         r.elapsed.as_secs_f64()
     );
-    eprintln!("  1 parallel client (embedded, no network)");
+    let clients = CLIENTS.load(Ordering::Relaxed);
+    if clients == 1 {
+        eprintln!("  1 parallel client (embedded, no network)");
+    } else {
+        eprintln!("  {} parallel clients (embedded, no network)", clients);
+    }
     eprintln!("  {} bytes payload", payload_size);
+    if r.pipeline > 1 {
+        eprintln!(
+            "  pipeline: {} ops per txn (latency per pipeline; p50 per op {:.3} msec)",
+            r.pipeline,
+            per_op_ms(r, r.p50)
+        );
+    }
     eprintln!();
     eprintln!(
         "  throughput summary: {:.2} requests per second",
@@ -195,7 +417,25 @@ fn print_verbose(r: &BenchResult, payload_size: usize) {
     eprintln!();
 }
 
+/// A pipeline latency divided over its ops. Equal to the plain latency for
+/// unpipelined rows, so machine-readable outputs can always carry it.
+fn per_op_ms(r: &BenchResult, latency: Duration) -> f64 {
+    duration_ms(latency) / r.pipeline.max(1) as f64
+}
+
 fn print_quiet(r: &BenchResult) {
+    if r.pipeline > 1 {
+        eprintln!(
+            "{}: {:.2} requests per second, p50={:.3} msec per pipeline of {} \
+             ({:.3} msec per op)",
+            r.name,
+            r.ops_per_sec,
+            duration_ms(r.p50),
+            r.pipeline,
+            per_op_ms(r, r.p50),
+        );
+        return;
+    }
     eprintln!(
         "{}: {:.2} requests per second, p50={:.3} msec",
         r.name,
@@ -206,13 +446,14 @@ fn print_quiet(r: &BenchResult) {
 
 fn print_csv_header() {
     println!(
-        "\"test\",\"rps\",\"avg_latency_ms\",\"min_latency_ms\",\"p50_latency_ms\",\"p95_latency_ms\",\"p99_latency_ms\",\"max_latency_ms\""
+        "\"test\",\"rps\",\"avg_latency_ms\",\"min_latency_ms\",\"p50_latency_ms\",\"p95_latency_ms\",\"p99_latency_ms\",\"max_latency_ms\",\
+         \"pipeline\",\"p50_per_op_ms\",\"p99_per_op_ms\""
     );
 }
 
 fn print_csv_row(r: &BenchResult) {
     println!(
-        "\"{}\",{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3}",
+        "\"{}\",{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{},{:.3},{:.3}",
         r.name,
         r.ops_per_sec,
         duration_ms(r.avg_latency),
@@ -221,9 +462,135 @@ fn print_csv_row(r: &BenchResult) {
         duration_ms(r.p95),
         duration_ms(r.p99),
         duration_ms(r.max),
+        r.pipeline,
+        per_op_ms(r, r.p50),
+        per_op_ms(r, r.p99),
     );
 }
 
+/// One `--json` line. Latencies are in milliseconds, like the CSV columns,
+/// and are per pipeline when `pipeline > 1`; the `_per_op_` fields divide
+/// them over the pipeline's ops.
+#[derive(serde::Serialize)]
+struct JsonRow {
+    name: String,
+    redis_equiv: String,
+    total_ops: usize,
+    elapsed_secs: f64,
+    ops_per_sec: f64,
+    avg_latency_ms: f64,
+    min_latency_ms: f64,
+    p50_latency_ms: f64,
+    p95_latency_ms: f64,
+    p99_latency_ms: f64,
+    max_latency_ms: f64,
+    pipeline: usize,
+    p50_per_op_ms: f64,
+    p99_per_op_ms: f64,
+}
+
+fn print_json_row(r: &BenchResult) {
+    let row = JsonRow {
+        name: r.name.clone(),
+        redis_equiv: r.redis_equiv.clone(),
+        total_ops: r.total_ops,
+        elapsed_secs: r.elapsed.as_secs_f64(),
+        ops_per_sec: r.ops_per_sec,
+        avg_latency_ms: duration_ms(r.avg_latency),
+        min_latency_ms: duration_ms(r.min),
+        p50_latency_ms: duration_ms(r.p50),
+        p95_latency_ms: duration_ms(r.p95),
+        p99_latency_ms: duration_ms(r.p99),
+        max_latency_ms: duration_ms(r.max),
+        pipeline: r.pipeline,
+        p50_per_op_ms: per_op_ms(r, r.p50),
+        p99_per_op_ms: per_op_ms(r, r.p99),
+    };
+    match serde_json::to_string(&row) {
+        Ok(line) => println!("{}", line),
+        Err(e) => eprintln!("warning: cannot serialize {} result: {}", r.name, e),
+    }
+}
+
+/// Same figures as the `print_verbose` summary, one table per durability mode.
+/// Latency columns are per pipeline; `*_per_op_ms` divide them by `pipeline`.
+const MARKDOWN_HEADERS: &[&str] = &[
+    "test", "rps", "avg_ms", "min_ms", "p50_ms", "p95_ms", "p99_ms", "max_ms", "pipeline",
+    "p50_per_op_ms", "p99_per_op_ms",
+];
+
+fn markdown_row(r: &BenchResult) -> Vec<String> {
+    vec![
+        r.name.clone(),
+        format!("{:.2}", r.ops_per_sec),
+        format!("{:.3}", duration_ms(r.avg_latency)),
+        format!("{:.3}", duration_ms(r.min)),
+        format!("{:.3}", duration_ms(r.p50)),
+        format!("{:.3}", duration_ms(r.p95)),
+        format!("{:.3}", duration_ms(r.p99)),
+        format!("{:.3}", duration_ms(r.max)),
+        r.pipeline.to_string(),
+        format!("{:.3}", per_op_ms(r, r.p50)),
+        format!("{:.3}", per_op_ms(r, r.p99)),
+    ]
+}
+
+/// `--store`: append one result's metrics to the SQLite history. A failed
+/// insert is reported but does not abort the run.
+#[cfg(feature = "sqlite-compare")]
+fn store_result(store: &ResultStore, r: &BenchResult, mode: DurabilityConfig) {
+    let metrics = [
+        ("rps", r.ops_per_sec),
+        ("avg_ms", duration_ms(r.avg_latency)),
+        ("min_ms", duration_ms(r.min)),
+        ("p50_ms", duration_ms(r.p50)),
+        ("p95_ms", duration_ms(r.p95)),
+        ("p99_ms", duration_ms(r.p99)),
+        ("max_ms", duration_ms(r.max)),
+        ("p50_per_op_ms", per_op_ms(r, r.p50)),
+        ("p99_per_op_ms", per_op_ms(r, r.p99)),
+    ];
+    if let Err(e) = store.record(&r.name, mode.label(), &metrics) {
+        eprintln!("warning: {}", e);
+    }
+}
+
+/// `--markdown`: hardware and parameters as a preamble, then one GFM table
+/// per durability mode, mirroring the human-readable sections.
+fn print_markdown(config: &Config, tables: &[(DurabilityConfig, Vec<Vec<String>>)]) {
+    println!("## StrataDB Redis-Comparison Benchmark");
+    println!();
+    print_hardware_info_markdown();
+    let keys = if config.keyspace == 0 {
+        "same key".to_string()
+    } else {
+        format!("keyspace {}", config.keyspace)
+    };
+    println!(
+        "{} requests, {} bytes payload, {}, {} embedded client(s) (no network)",
+        config.requests, config.payload_size, keys, config.clients
+    );
+    println!();
+    for (mode, rows) in tables {
+        println!(
+            "### durability: {} (comparable to: {})",
+            mode.label(),
+            redis_equivalent(*mode)
+        );
+        println!();
+        print_markdown_table(MARKDOWN_HEADERS, rows);
+    }
+}
+
+/// The Redis persistence setting each durability mode is comparable to.
+fn redis_equivalent(mode: DurabilityConfig) -> &'static str {
+    match mode {
+        DurabilityConfig::Cache => "Redis no persistence (save \"\", appendonly no)",
+        DurabilityConfig::Standard => "Redis appendfsync everysec (default)",
+        DurabilityConfig::Always => "Redis appendfsync always",
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Test definitions
 //
@@ -236,17 +603,24 @@ fn print_csv_row(r: &BenchResult) {
 
 /// PING_INLINE: "PING\r\n" (redis-benchmark.c line 1880)
 fn bench_ping(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("PING_INLINE", "PING_INLINE", n, |_kg| {
-        db.db.ping().unwrap();
+    run_bench(db, "PING_INLINE", "PING_INLINE", n, |strata, _kg| {
+        strata.ping().unwrap();
     }, keygen)
 }
 
 /// SET: "SET key:__rand_int__ <data>" (redis-benchmark.c line 1889)
 /// Without -r: all writes go to the same key (hot-key benchmark).
 fn bench_set(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("SET", "SET", n, |kg| {
+    if pipeline_depth() > 1 {
+        return run_pipelined(db, "SET", "SET", n, |kg| Command::KvPut {
+            branch: None,
+            key: kg.key("key"),
+            value: data.clone(),
+        }, keygen);
+    }
+    run_bench(db, "SET", "SET", n, |strata, kg| {
         let key = kg.key("key");
-        db.db.kv_put(&key, data.clone()).unwrap();
+        strata.kv_put(&key, data.clone()).unwrap();
     }, keygen)
 }
 
@@ -254,9 +628,15 @@ fn bench_set(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> Bench
 /// In redis-benchmark, GET runs after SET so the key already exists.
 /// Without -r: reads the same key SET wrote.
 fn bench_get(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("GET", "GET", n, |kg| {
+    if pipeline_depth() > 1 {
+        return run_pipelined(db, "GET", "GET", n, |kg| Command::KvGet {
+            branch: None,
+            key: kg.key("key"),
+        }, keygen);
+    }
+    run_bench(db, "GET", "GET", n, |strata, kg| {
         let key = kg.key("key");
-        let _ = db.db.kv_get(&key);
+        let _ = strata.kv_get(&key);
     }, keygen)
 }
 
@@ -264,14 +644,14 @@ fn bench_get(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult {
 /// Redis INCR is a single atomic O(1) command.
 /// Strata equivalent requires state_read + state_set (2 operations).
 fn bench_incr(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("INCR", "INCR (state_read+state_set)", n, |kg| {
+    run_bench(db, "INCR", "INCR (state_read+state_set)", n, |strata, kg| {
         let cell = kg.key("counter");
-        let current = db.db.state_read(&cell).unwrap();
+        let current = strata.state_read(&cell).unwrap();
         let val = match current {
             Some(Value::Int(v)) => v,
             _ => 0,
         };
-        db.db.state_set(&cell, Value::Int(val + 1)).unwrap();
+        strata.state_set(&cell, Value::Int(val + 1)).unwrap();
     }, keygen)
 }
 
@@ -280,9 +660,16 @@ fn bench_incr(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult {
 /// We use kv_put with composite key "myhash:element:X" which is the closest
 /// in terms of cost/complexity to Redis HSET.
 fn bench_hset(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("HSET", "HSET (kv_put composite key)", n, |kg| {
+    if pipeline_depth() > 1 {
+        return run_pipelined(db, "HSET", "HSET (kv_put composite key)", n, |kg| Command::KvPut {
+            branch: None,
+            key: kg.key("myhash:element"),
+            value: data.clone(),
+        }, keygen);
+    }
+    run_bench(db, "HSET", "HSET (kv_put composite key)", n, |strata, kg| {
         let key = kg.key("myhash:element");
-        db.db.kv_put(&key, data.clone()).unwrap();
+        strata.kv_put(&key, data.clone()).unwrap();
     }, keygen)
 }
 
@@ -290,25 +677,10 @@ fn bench_hset(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> Benc
 /// Redis MSET is a single atomic command. Without -r, all 10 keys are the same.
 /// Strata equivalent uses Session + TxnBegin + 10x KvPut + TxnCommit.
 fn bench_mset_10(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("MSET (10 keys)", "MSET (10 keys) via txn", n, |kg| {
-        let mut session = db.db.session();
-        session
-            .execute(Command::TxnBegin {
-                branch: None,
-                options: None,
-            })
-            .unwrap();
-        for _ in 0..10 {
-            let key = kg.key("key");
-            session
-                .execute(Command::KvPut {
-                    branch: None,
-                    key,
-                    value: data.clone(),
-                })
-                .unwrap();
-        }
-        session.execute(Command::TxnCommit).unwrap();
+    run_bench(db, "MSET (10 keys)", "MSET (10 keys) via txn", n, |strata, kg| {
+        let entries: Vec<(String, Value)> =
+            (0..10).map(|_| (kg.key("key"), data.clone())).collect();
+        batch_put(strata, &entries);
     }, keygen)
 }
 
@@ -319,8 +691,15 @@ fn bench_xadd(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> Benc
     payload_map.insert("myfield".to_string(), data.clone());
     let payload = Value::Object(payload_map);
 
-    run_bench("XADD", "XADD", n, |_kg| {
-        db.db.event_append("mystream", payload.clone()).unwrap();
+    if pipeline_depth() > 1 {
+        return run_pipelined(db, "XADD", "XADD", n, |_kg| Command::EventAppend {
+            branch: None,
+            event_type: "mystream".to_string(),
+            payload: payload.clone(),
+        }, keygen);
+    }
+    run_bench(db, "XADD", "XADD", n, |strata, _kg| {
+        strata.event_append("mystream", payload.clone()).unwrap();
     }, keygen)
 }
 
@@ -340,22 +719,82 @@ fn bench_lrange_100(mode: DurabilityConfig, n: usize, data: &Value, keygen: &mut
     }
 
     run_bench(
+        &bench_db,
         "LRANGE_100 (first 100 elements)",
         "LRANGE_100 (kv_list prefix scan — NOT equivalent)",
         n,
-        |_kg| {
-            let _ = bench_db.db.kv_list(Some("mylist:")).unwrap();
+        |strata, _kg| {
+            let _ = strata.kv_list(Some("mylist:")).unwrap();
         },
         keygen,
     )
 }
 
+// --- Additional string commands ---
+//
+// Not in redis-benchmark's default run; compare against e.g.
+// `redis-benchmark -r 100000 -n 100000 GETSET key:__rand_int__ xxx`.
+
+/// GETSET: "GETSET key:__rand_int__ <data>"
+/// Redis GETSET is a single atomic O(1) command.
+/// Strata equivalent requires kv_get + kv_put (2 operations, not atomic).
+fn bench_getset(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
+    run_bench(db, "GETSET", "GETSET (kv_get+kv_put)", n, |strata, kg| {
+        let key = kg.key("key");
+        let old = strata.kv_get(&key).unwrap();
+        strata.kv_put(&key, data.clone()).unwrap();
+        std::hint::black_box(old);
+    }, keygen)
+}
+
+/// Values are reset to the bare payload once an append would grow them past
+/// this size, so the same-key (no -r) case doesn't turn into a quadratic copy.
+const APPEND_MAX_BYTES: usize = 64 * 1024;
+
+/// APPEND: "APPEND key:__rand_int__ <data>"
+/// Redis APPEND is amortized O(1) in place. Strata has no append, so this is
+/// a read-modify-write: kv_get + concatenate + kv_put (2 operations, cost
+/// grows with the value, capped at APPEND_MAX_BYTES).
+fn bench_append(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
+    let suffix = match data {
+        Value::Bytes(b) => b.clone(),
+        _ => Vec::new(),
+    };
+    run_bench(db, "APPEND", "APPEND (kv_get+kv_put read-modify-write)", n, |strata, kg| {
+        let key = kg.key("append");
+        let mut value = match strata.kv_get(&key).unwrap() {
+            Some(Value::Bytes(b)) if b.len() + suffix.len() <= APPEND_MAX_BYTES => b,
+            _ => Vec::new(),
+        };
+        value.extend_from_slice(&suffix);
+        strata.kv_put(&key, Value::Bytes(value)).unwrap();
+    }, keygen)
+}
+
+/// SETNX: "SETNX key:__rand_int__ <data>"
+/// Redis SETNX sets only if the key is absent. Strata's closest single-op
+/// equivalent is state_init, which never overwrites an existing cell. Without
+/// -r every call after the first is a no-op on the existing cell, as in Redis.
+fn bench_setnx(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
+    if pipeline_depth() > 1 {
+        return run_pipelined(db, "SETNX", "SETNX (state_init)", n, |kg| Command::StateInit {
+            branch: None,
+            cell: kg.key("nxkey"),
+            value: data.clone(),
+        }, keygen);
+    }
+    run_bench(db, "SETNX", "SETNX (state_init)", n, |strata, kg| {
+        let cell = kg.key("nxkey");
+        strata.state_init(&cell, data.clone()).unwrap();
+    }, keygen)
+}
+
 // --- Strata-unique bonus tests ---
 
 fn bench_state_set(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) -> BenchResult {
-    run_bench("STATE_SET", "(Strata unique)", n, |kg| {
+    run_bench(db, "STATE_SET", "(Strata unique)", n, |strata, kg| {
         let cell = kg.key("cell");
-        db.db.state_set(&cell, data.clone()).unwrap();
+        strata.state_set(&cell, data.clone()).unwrap();
     }, keygen)
 }
 
@@ -365,9 +804,9 @@ fn bench_state_read(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult
         .state_set("rcell:000000000000", Value::Int(42))
         .unwrap();
 
-    run_bench("STATE_READ", "(Strata unique)", n, |kg| {
+    run_bench(db, "STATE_READ", "(Strata unique)", n, |strata, kg| {
         let cell = kg.key("rcell");
-        let _ = db.db.state_read(&cell).unwrap();
+        let _ = strata.state_read(&cell).unwrap();
     }, keygen)
 }
 
@@ -382,9 +821,9 @@ fn bench_event_read(db: &BenchDb, n: usize, keygen: &mut KeyGen) -> BenchResult
         db.db.event_append("readstream", payload.clone()).unwrap();
     }
 
-    run_bench("EVENT_READ", "(Strata unique)", n, |kg| {
+    run_bench(db, "EVENT_READ", "(Strata unique)", n, |strata, kg| {
         let seq = (kg.next_rand() % event_count) + 1;
-        let _ = db.db.event_read(seq).unwrap();
+        let _ = strata.event_read(seq).unwrap();
     }, keygen)
 }
 
@@ -397,13 +836,13 @@ fn bench_kv_delete(db: &BenchDb, n: usize, data: &Value, keygen: &mut KeyGen) ->
             .unwrap();
     }
 
-    run_bench("KV_DELETE", "DEL (bonus)", n, |kg| {
+    run_bench(db, "KV_DELETE", "DEL (bonus)", n, |strata, kg| {
         if kg.keyspace == 0 {
-            let _ = db.db.kv_delete("dkey:000000000000");
+            let _ = strata.kv_delete("dkey:000000000000");
         } else {
             let idx = kg.next_rand() % keyspace;
             let key = format!("dkey:{:012}", idx);
-            let _ = db.db.kv_delete(&key);
+            let _ = strata.kv_delete(&key);
         }
     }, keygen)
 }
@@ -420,26 +859,103 @@ const SKIPPED_REDIS_TESTS: &[&str] = &[
 #[derive(Clone)]
 struct Config {
     requests: usize,
+    /// Parallel clients (`-c`); `requests` is split across them.
+    clients: usize,
+    /// Ops per pipelined transaction (`-P`); 1 disables pipelining.
+    pipeline: usize,
     payload_size: usize,
     keyspace: u64,
+    /// Starting LCG state for every test's key generator.
+    seed: u64,
     durability: Vec<DurabilityConfig>,
     tests: Option<Vec<String>>,
     csv: bool,
+    /// One NDJSON object per test on stdout.
+    json: bool,
+    markdown: bool,
     quiet: bool,
+    wal_focus: bool,
+    /// Path to a TOML file of per-(mode, operation) p99 budgets.
+    budget: Option<String>,
+    /// Path to a SQLite results history (requires `sqlite-compare`).
+    store: Option<String>,
+    /// `host:port` of a live Redis to compare against (requires `redis-live`).
+    redis_url: Option<String>,
+}
+
+impl Config {
+    /// CSV and JSON results go to stdout, so the banner, per-mode headers and
+    /// skipped-test list are suppressed to keep stdout parseable.
+    fn machine_readable(&self) -> bool {
+        self.csv || self.json
+    }
+}
+
+fn print_usage() {
+    println!(
+        "\
+Redis-comparison benchmark: redis-benchmark's default suite against Strata's API.
+
+Usage: cargo bench --bench redis_compare -- [OPTIONS]
+
+Options:
+  -n <requests>         Requests per test [default: {requests}]
+  -c <clients>          Parallel clients, each on its own handle [default: 1]
+  -P <n>                Pipeline n ops per transaction (SET, GET, HSET, XADD, SETNX;
+                        other tests run unpipelined) [default: 1]
+  -d <bytes>            Payload size in bytes [default: {payload}]
+  -r <keyspace>         Randomize keys over [0, keyspace); 0 = same key every time [default: 0]
+  --seed <u64>          Starting RNG state for -r key selection [default: {seed}]
+  -t <tests>            Comma-separated test names, e.g. set,get; `state` selects
+                        STATE_SET and STATE_READ [default: all]
+  --durability <mode>   cache, standard, or always [default: all three]
+  --csv                 Print results as CSV on stdout
+  --json                Print results as one JSON object per line on stdout
+  --markdown            Print results as GitHub-flavored Markdown tables (one per
+                        durability mode) on stdout
+  -q                    One line per test
+  --wal-focus           Print a WAL appends/syncs per-op table instead of latencies
+  --budget <file>       Check p99 against a TOML budget file; exit 1 if any is exceeded
+  --store <file>        Append results to a SQLite history (needs --features sqlite-compare)
+  --redis-url <addr>    Also run SET/GET/INCR against the Redis at host:port and print
+                        Strata vs Redis per test (needs --features redis-live)
+  --strict              Exit with an error on unknown arguments instead of warning
+  -h, --help            Print this help and exit
+
+Examples:
+  cargo bench --bench redis_compare -- -r 100000
+  cargo bench --bench redis_compare -- --durability cache -q
+  cargo bench --bench redis_compare -- -t set,get -n 1000000 --csv
+  cargo bench --bench redis_compare -- --budget budgets.toml",
+        requests = DEFAULT_REQUESTS,
+        payload = DEFAULT_PAYLOAD_SIZE,
+        seed = DEFAULT_SEED,
+    );
 }
 
 fn parse_args() -> Config {
     let args: Vec<String> = std::env::args().collect();
     let mut config = Config {
         requests: DEFAULT_REQUESTS,
+        clients: 1,
+        pipeline: 1,
         payload_size: DEFAULT_PAYLOAD_SIZE,
         keyspace: 0, // default: no randomization, same key every time (matches redis-benchmark)
+        seed: DEFAULT_SEED,
         durability: DurabilityConfig::ALL.to_vec(),
         tests: None,
         csv: false,
+        json: false,
+        markdown: false,
         quiet: false,
+        wal_focus: false,
+        budget: None,
+        store: None,
+        redis_url: None,
     };
 
+    let mut unknown = Vec::new();
+    let mut strict = false;
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
@@ -447,6 +963,26 @@ fn parse_args() -> Config {
                 i += 1;
                 config.requests = args[i].parse().unwrap_or(DEFAULT_REQUESTS);
             }
+            "-c" => {
+                i += 1;
+                match args.get(i).map(|val| val.parse::<usize>()) {
+                    Some(Ok(clients)) if clients > 0 => config.clients = clients,
+                    _ => {
+                        eprintln!("error: -c expects a positive number of clients");
+                        std::process::exit(2);
+                    }
+                }
+            }
+            "-P" => {
+                i += 1;
+                match args.get(i).map(|val| val.parse::<usize>()) {
+                    Some(Ok(depth)) if depth > 0 => config.pipeline = depth,
+                    _ => {
+                        eprintln!("error: -P expects a positive pipeline depth");
+                        std::process::exit(2);
+                    }
+                }
+            }
             "-d" => {
                 i += 1;
                 config.payload_size = args[i].parse().unwrap_or(DEFAULT_PAYLOAD_SIZE);
@@ -455,6 +991,16 @@ fn parse_args() -> Config {
                 i += 1;
                 config.keyspace = args[i].parse().unwrap_or(0);
             }
+            "--seed" => {
+                i += 1;
+                config.seed = match args.get(i).map(|val| val.parse()) {
+                    Some(Ok(seed)) => seed,
+                    _ => {
+                        eprintln!("error: --seed expects an unsigned 64-bit integer");
+                        std::process::exit(2);
+                    }
+                };
+            }
             "--durability" => {
                 i += 1;
                 config.durability = match args[i].as_str() {
@@ -473,11 +1019,32 @@ fn parse_args() -> Config {
                 config.tests = Some(names);
             }
             "--csv" => config.csv = true,
+            "--json" => config.json = true,
+            "--markdown" => config.markdown = true,
             "-q" => config.quiet = true,
-            _ => {}
+            "--wal-focus" => config.wal_focus = true,
+            "--budget" => {
+                i += 1;
+                config.budget = args.get(i).cloned();
+            }
+            "--store" => {
+                i += 1;
+                config.store = args.get(i).cloned();
+            }
+            "--redis-url" => {
+                i += 1;
+                config.redis_url = args.get(i).cloned();
+            }
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
         }
         i += 1;
     }
+    check_unknown_args(&unknown, strict);
 
     config
 }
@@ -486,10 +1053,16 @@ fn parse_args() -> Config {
 // Test filter
 // ---------------------------------------------------------------------------
 
+/// `-t` matches whole test names, case-insensitively, so `get` does not also
+/// select GETSET. A name up to an underscore selects the group, e.g. `state`
+/// runs STATE_SET and STATE_READ.
 fn test_is_selected(name: &str, filter: &Option<Vec<String>>) -> bool {
     match filter {
         None => true,
-        Some(names) => names.iter().any(|f| name.to_uppercase().starts_with(&f.to_uppercase())),
+        Some(names) => names.iter().any(|f| {
+            let (name, f) = (name.to_uppercase(), f.trim().to_uppercase());
+            name == f || name.starts_with(&format!("{}_", f))
+        }),
     }
 }
 
@@ -499,13 +1072,42 @@ fn test_is_selected(name: &str, filter: &Option<Vec<String>>) -> bool {
 
 fn main() {
     let config = parse_args();
+    CLIENTS.store(config.clients, Ordering::Relaxed);
+    PIPELINE.store(config.pipeline, Ordering::Relaxed);
     print_hardware_info();
 
+    // Load budgets up front so a bad file fails before the run, not after it
+    let mut budgets = config.budget.as_ref().map(|path| match LatencyBudgets::load(path) {
+        Ok(b) => BudgetChecker::new(b),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    });
+
+    #[cfg(feature = "sqlite-compare")]
+    let store = config.store.as_ref().map(|path| {
+        ResultStore::open(path, "redis_compare", &hardware_summary()).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        })
+    });
+    #[cfg(not(feature = "sqlite-compare"))]
+    if config.store.is_some() {
+        eprintln!("error: --store requires building with --features sqlite-compare");
+        std::process::exit(2);
+    }
+    #[cfg(not(feature = "redis-live"))]
+    if config.redis_url.is_some() {
+        eprintln!("error: --redis-url requires building with --features redis-live");
+        std::process::exit(2);
+    }
+
     // Generate random payload data matching redis-benchmark's genBenchmarkRandomData
     let data_bytes = gen_benchmark_random_data(config.payload_size);
-    let data = Value::Bytes(data_bytes);
+    let data = Value::Bytes(data_bytes.clone());
 
-    if !config.csv {
+    if !config.machine_readable() {
         eprintln!("=== StrataDB Redis-Comparison Benchmark ===");
         eprintln!("NOTE: Not an apples-to-apples comparison.");
         eprintln!("- Strata is embedded (no network overhead, no serialization)");
@@ -520,28 +1122,48 @@ fn main() {
             eprintln!("  (use -r <keyspace> to enable random keys, e.g. -r 100000)");
         } else {
             eprintln!(
-                "Parameters: {} requests, {} bytes payload, keyspace {} (random keys)",
-                config.requests, config.payload_size, config.keyspace
+                "Parameters: {} requests, {} bytes payload, keyspace {} (random keys, seed {})",
+                config.requests, config.payload_size, config.keyspace, config.seed
+            );
+        }
+        if config.clients > 1 {
+            eprintln!(
+                "Clients: {} parallel (requests split across clients, each on its own handle)",
+                config.clients
+            );
+        }
+        if config.pipeline > 1 {
+            eprintln!(
+                "Pipeline: {} ops per transaction for SET, GET, HSET, XADD, SETNX \
+                 (latency per pipeline, rps per op)",
+                config.pipeline
             );
         }
         eprintln!();
     }
 
-    if config.csv {
+    #[cfg(feature = "redis-live")]
+    let live_redis = match &config.redis_url {
+        Some(url) => {
+            eprintln!("Running SET/GET/INCR against live Redis at {}...", url);
+            run_live_redis(url, &config, &data_bytes)
+        }
+        None => Vec::new(),
+    };
+
+    if config.csv && !config.wal_focus {
         print_csv_header();
     }
 
+    let mut wal_rows = Vec::new();
+    let mut markdown_tables = Vec::new();
+
     for mode in &config.durability {
-        if !config.csv {
-            let redis_equiv = match mode {
-                DurabilityConfig::Cache => "Redis no persistence (save \"\", appendonly no)",
-                DurabilityConfig::Standard => "Redis appendfsync everysec (default)",
-                DurabilityConfig::Always => "Redis appendfsync always",
-            };
+        if !config.machine_readable() {
             eprintln!(
                 "--- durability: {} (comparable to: {}) ---",
                 mode.label(),
-                redis_equiv
+                redis_equivalent(*mode)
             );
             eprintln!();
         }
@@ -549,101 +1171,175 @@ fn main() {
         // Shared database for all tests in this durability mode
         // (matches Redis where all tests share the same instance)
         let bench_db = create_db(*mode);
+        let mut markdown_rows = Vec::new();
+        #[cfg(feature = "redis-live")]
+        let mut live_rows = Vec::new();
+
+        // Report the result (or in --wal-focus mode record its WAL counter delta),
+        // and check it against its p99 budget if one is configured
+        let mut finish = |result: BenchResult| {
+            if let Some(checker) = budgets.as_mut() {
+                checker.record(&result.name, *mode, result.p99);
+            }
+            #[cfg(feature = "redis-live")]
+            if live_redis.iter().any(|r| r.name == result.name) {
+                live_rows.push((result.name.clone(), result.ops_per_sec, result.p50, result.p99));
+            }
+            #[cfg(feature = "sqlite-compare")]
+            if let Some(store) = store.as_ref() {
+                store_result(store, &result, *mode);
+            }
+            if config.wal_focus {
+                wal_rows.push(WalRow {
+                    op: result.name.clone(),
+                    mode: *mode,
+                    iterations: result.total_ops as u64,
+                    delta: result.wal,
+                });
+            } else if config.markdown {
+                markdown_rows.push(markdown_row(&result));
+            } else {
+                print_result(&result, &config);
+            }
+        };
 
         // --- Redis-equivalent tests (in redis-benchmark's exact order) ---
 
         if test_is_selected("PING", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_ping(&bench_db, config.requests, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("SET", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_set(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("GET", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_get(&bench_db, config.requests, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("INCR", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_incr(&bench_db, config.requests, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("HSET", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_hset(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("MSET", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_mset_10(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("XADD", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_xadd(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("LRANGE", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_lrange_100(*mode, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
+        }
+
+        // --- Additional string commands (not in redis-benchmark's default run) ---
+
+        if test_is_selected("GETSET", &config.tests) {
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
+            let result = bench_getset(&bench_db, config.requests, &data, &mut kg);
+            finish(result);
+        }
+
+        if test_is_selected("APPEND", &config.tests) {
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
+            let result = bench_append(&bench_db, config.requests, &data, &mut kg);
+            finish(result);
+        }
+
+        if test_is_selected("SETNX", &config.tests) {
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
+            let result = bench_setnx(&bench_db, config.requests, &data, &mut kg);
+            finish(result);
         }
 
         // --- Strata-unique bonus tests ---
 
         if test_is_selected("STATE_SET", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_state_set(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("STATE_READ", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_state_read(&bench_db, config.requests, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("EVENT_READ", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_event_read(&bench_db, config.requests, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         if test_is_selected("KV_DELETE", &config.tests) {
-            let mut kg = KeyGen::new(config.keyspace);
+            let mut kg = KeyGen::new(config.keyspace, config.seed);
             let result = bench_kv_delete(&bench_db, config.requests, &data, &mut kg);
-            print_result(&result, &config);
+            finish(result);
         }
 
         // List skipped Redis tests
-        if !config.csv && !config.quiet {
+        if !config.machine_readable() && !config.quiet && !config.wal_focus {
             eprintln!("--- Skipped (no Strata equivalent) ---");
             for name in SKIPPED_REDIS_TESTS {
                 eprintln!("  {}: N/A", name);
             }
             eprintln!();
         }
+
+        #[cfg(feature = "redis-live")]
+        if !live_rows.is_empty() {
+            print_live_comparison(&live_rows, &live_redis);
+        }
+
+        if config.markdown {
+            markdown_tables.push((*mode, markdown_rows));
+        }
     }
 
-    if !config.csv {
+    if config.wal_focus {
+        print_wal_focus_table(&mut wal_rows);
+    } else if config.markdown {
+        print_markdown(&config, &markdown_tables);
+    }
+
+    let within_budget = budgets.as_ref().is_none_or(|checker| checker.report());
+
+    if !config.machine_readable() {
         eprintln!("=== Benchmark complete ===");
     }
+
+    if !within_budget {
+        std::process::exit(1);
+    }
 }
 
 fn print_result(result: &BenchResult, config: &Config) {
     if config.csv {
         print_csv_row(result);
+    } else if config.json {
+        print_json_row(result);
     } else if config.quiet {
         print_quiet(result);
     } else {
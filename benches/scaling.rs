@@ -5,18 +5,40 @@
 //!
 //! Run: `cargo bench --bench scaling`
 //! Quick: `cargo bench --bench scaling -- --threads 1,2,4`
+//! HDR:   `cargo bench --bench scaling --features hdr -- --hdr`
+//! Seed:  `cargo bench --bench scaling -- --seed 42`
+//! Mix:   `cargo bench --bench scaling -- --mix 50,95,99`
+//! Hot:   `cargo bench --bench scaling -- --hot-keys 1,4,16,64`
+//! Rate:  `cargo bench --bench scaling -- --target-rate 10000`
+//! CSV:   `cargo bench --bench scaling -- --csv > scaling.csv`
+//! JSON:  `cargo bench --bench scaling -- --json > scaling.ndjson`
+//! Chart: `cargo bench --bench scaling -- --chart`
+//!
+//! `--csv` and `--json` write every result field to stdout (one row or NDJSON
+//! line per workload, durability mode and thread count); the tables stay on
+//! stderr.
+//!
+//! `--target-rate <ops_per_sec>` paces each worker thread at a fixed interval
+//! and measures latency from each op's intended start, so a stall also counts
+//! against the ops queued behind it (coordinated-omission correction). Rate 0,
+//! the default, keeps the closed-loop behavior: each op starts as soon as the
+//! previous one finishes.
+//! Help:  `cargo bench --bench scaling -- --help`
 
 #[allow(unused)]
 #[path = "harness/mod.rs"]
 mod harness;
 
 use harness::scaling::{
-    parse_thread_counts, physical_cores, print_table_header, print_table_row,
-    run_scaling_experiment, ReservoirSampler, ThreadResult,
+    chart_enabled, emit_result, fmt_duration, fmt_ops, parse_thread_counts, physical_cores,
+    print_ascii_chart, print_output_header, print_table_header, print_table_row,
+    run_ramp_experiment, run_scaling_experiment, set_chart, set_output_format, set_target_rate,
+    thread_seed, use_hdr, Collector, Latencies, OutputFormat, Pacer, ThreadResult,
 };
-use harness::{create_db, DurabilityConfig};
-use std::sync::atomic::Ordering;
-use std::time::Instant;
+use harness::{check_unknown_args, create_db, DurabilityConfig};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use stratadb::Value;
 
 // ---------------------------------------------------------------------------
@@ -26,6 +48,9 @@ use stratadb::Value;
 const WARMUP_SECS: u64 = 1;
 const MEASURE_SECS: u64 = 5;
 
+/// Seconds each ramp stage runs before the next batch of threads joins.
+const RAMP_STAGE_SECS: u64 = 2;
+
 /// Number of keys to pre-populate for read-heavy workloads.
 const PREPOPULATE_KEYS: usize = 100_000;
 
@@ -33,6 +58,10 @@ const PREPOPULATE_KEYS: usize = 100_000;
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Default `--seed`. Each thread's RNG is seeded with `thread_seed(seed,
+/// tid, salt)`, salted per workload, so every seed gives its own streams.
+const DEFAULT_SEED: u64 = 0;
+
 /// Simple LCG for key selection (fast, deterministic, no rand dependency).
 #[inline]
 fn fast_rand(state: &mut u64) -> u64 {
@@ -46,7 +75,7 @@ fn fast_rand(state: &mut u64) -> u64 {
 // Workload: KV GET (read-only, no contention)
 // ---------------------------------------------------------------------------
 
-fn run_kv_get_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
+fn run_kv_get_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
     eprintln!(
         "\n=== KV GET (read-only, no contention) | durability: {} ===",
         mode.label()
@@ -66,18 +95,20 @@ fn run_kv_get_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
 
     print_table_header();
 
+    let mut results = Vec::new();
     for &n in thread_sweep {
         let result =
             run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
-                let mut sampler = ReservoirSampler::with_seed(tid as u64);
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
                 let mut ops = 0u64;
-                let mut rng = tid as u64 ^ 0x12345678;
+                let mut rng = thread_seed(seed, tid, 0x12345678);
 
                 while !stop.load(Ordering::Relaxed) {
                     let idx = fast_rand(&mut rng) % PREPOPULATE_KEYS as u64;
                     let key = format!("key{:06}", idx);
 
-                    let start = Instant::now();
+                    let start = pacer.start();
                     let _ = strata.kv_get(&key);
                     sampler.record(start.elapsed());
                     ops += 1;
@@ -86,10 +117,15 @@ fn run_kv_get_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
                 ThreadResult {
                     ops,
                     aborts: 0,
-                    latencies: sampler.into_samples(),
+                    latencies: sampler.into_latencies(),
                 }
             });
         print_table_row(&result);
+        emit_result("kv_get", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
     }
 }
 
@@ -97,7 +133,7 @@ fn run_kv_get_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
 // Workload: KV PUT (independent keys, no contention)
 // ---------------------------------------------------------------------------
 
-fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
+fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
     eprintln!(
         "\n=== KV PUT (independent keys, no contention) | durability: {} ===",
         mode.label()
@@ -105,12 +141,14 @@ fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig
 
     print_table_header();
 
+    let mut results = Vec::new();
     for &n in thread_sweep {
         // Fresh database per thread count to avoid accumulation effects
         let bench_db = create_db(mode);
         let result =
             run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
-                let mut sampler = ReservoirSampler::with_seed(tid as u64);
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
                 let mut ops = 0u64;
                 let mut seq = 0u64;
 
@@ -118,7 +156,7 @@ fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig
                     let key = format!("t{}_{}", tid, seq);
                     seq += 1;
 
-                    let start = Instant::now();
+                    let start = pacer.start();
                     let _ = strata.kv_put(&key, Value::Int(seq as i64));
                     sampler.record(start.elapsed());
                     ops += 1;
@@ -127,10 +165,15 @@ fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig
                 ThreadResult {
                     ops,
                     aborts: 0,
-                    latencies: sampler.into_samples(),
+                    latencies: sampler.into_latencies(),
                 }
             });
         print_table_row(&result);
+        emit_result("kv_put_independent", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
     }
 }
 
@@ -138,7 +181,7 @@ fn run_kv_put_independent_scaling(thread_sweep: &[usize], mode: DurabilityConfig
 // Workload: KV PUT (hot key, maximum contention)
 // ---------------------------------------------------------------------------
 
-fn run_kv_put_hot_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
+fn run_kv_put_hot_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
     eprintln!(
         "\n=== KV PUT (hot key, maximum contention) | durability: {} ===",
         mode.label()
@@ -146,6 +189,7 @@ fn run_kv_put_hot_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
 
     print_table_header();
 
+    let mut results = Vec::new();
     for &n in thread_sweep {
         let bench_db = create_db(mode);
 
@@ -157,14 +201,15 @@ fn run_kv_put_hot_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
 
         let result =
             run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
-                let mut sampler = ReservoirSampler::with_seed(tid as u64);
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
                 let mut ops = 0u64;
                 let mut aborts = 0u64;
                 let mut seq = 0u64;
 
                 while !stop.load(Ordering::Relaxed) {
                     seq += 1;
-                    let start = Instant::now();
+                    let start = pacer.start();
                     match strata.kv_put("hot", Value::Int(seq as i64)) {
                         Ok(_) => {
                             sampler.record(start.elapsed());
@@ -179,20 +224,304 @@ fn run_kv_put_hot_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
                 ThreadResult {
                     ops,
                     aborts,
-                    latencies: sampler.into_samples(),
+                    latencies: sampler.into_latencies(),
                 }
             });
         print_table_row(&result);
+        emit_result("kv_put_hot", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
     }
 }
 
 // ---------------------------------------------------------------------------
-// Workload: Mixed 90/10 (90% get, 10% put, low contention)
+// Workload: KV PUT (N hot keys, tunable contention)
 // ---------------------------------------------------------------------------
 
-fn run_mixed_90_10_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
+/// Every thread writes to a random one of `num_keys` shared keys, covering the
+/// contention curve between independent keys and the single hot key.
+fn run_n_hot_keys_scaling(
+    num_keys: usize,
+    thread_sweep: &[usize],
+    mode: DurabilityConfig,
+    seed: u64,
+) {
+    let workload = format!("kv_put_hot_{}", num_keys);
     eprintln!(
-        "\n=== MIXED 90/10 (90% get, 10% put, low contention) | durability: {} ===",
+        "\n=== KV PUT ({} hot keys, shared) | durability: {} ===",
+        num_keys,
+        mode.label()
+    );
+
+    print_table_header();
+
+    let mut results = Vec::new();
+    for &n in thread_sweep {
+        let bench_db = create_db(mode);
+
+        // Pre-populate the hot keys
+        for i in 0..num_keys {
+            bench_db
+                .db
+                .kv_put(&format!("hot{}", i), Value::Int(0))
+                .expect("pre-populate hot keys failed");
+        }
+
+        let result =
+            run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
+                let mut ops = 0u64;
+                let mut aborts = 0u64;
+                let mut rng = thread_seed(seed, tid, 0xc0ffee);
+                let mut seq = 0u64;
+
+                while !stop.load(Ordering::Relaxed) {
+                    seq += 1;
+                    let key = format!("hot{}", fast_rand(&mut rng) % num_keys as u64);
+                    let start = pacer.start();
+                    match strata.kv_put(&key, Value::Int(seq as i64)) {
+                        Ok(_) => {
+                            sampler.record(start.elapsed());
+                            ops += 1;
+                        }
+                        Err(_) => {
+                            aborts += 1;
+                        }
+                    }
+                }
+
+                ThreadResult {
+                    ops,
+                    aborts,
+                    latencies: sampler.into_latencies(),
+                }
+            });
+        print_table_row(&result);
+        emit_result(&workload, mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workload: CAS counter (shared state cell, retry until committed)
+// ---------------------------------------------------------------------------
+
+/// Every thread increments one shared state cell with a read/CAS retry loop.
+///
+/// Each attempt reads the cell with `state_read` and CASes value + 1 against
+/// the last published counter. `state_read` returns no counter, so the winner
+/// of each CAS publishes the new one for the others, i.e. the counter a reader
+/// would observe alongside the value. A `None` (stale counter) is counted as
+/// an abort and retried, so `retries/op` is the true attempts per committed
+/// increment. Latency covers the whole increment, retries included.
+fn run_cas_counter_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    eprintln!(
+        "\n=== STATE CAS counter (shared cell, retry loop) | durability: {} ===",
+        mode.label()
+    );
+
+    print_table_header();
+
+    let mut results = Vec::new();
+    for &n in thread_sweep {
+        let bench_db = create_db(mode);
+        let initial = bench_db
+            .db
+            .state_set("counter", Value::Int(0))
+            .expect("pre-populate counter failed");
+        let published = Arc::new(AtomicU64::new(initial));
+
+        let result =
+            run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
+                let mut ops = 0u64;
+                let mut aborts = 0u64;
+
+                'increment: while !stop.load(Ordering::Relaxed) {
+                    let start = pacer.start();
+                    loop {
+                        let expected = published.load(Ordering::Acquire);
+                        let current = match strata.state_read("counter") {
+                            Ok(Some(Value::Int(v))) => v,
+                            _ => 0,
+                        };
+                        match strata.state_cas("counter", Some(expected), Value::Int(current + 1)) {
+                            Ok(Some(counter)) => {
+                                published.fetch_max(counter, Ordering::AcqRel);
+                                break;
+                            }
+                            // Stale counter or conflict: another thread won, retry
+                            Ok(None) | Err(_) => {
+                                aborts += 1;
+                                if stop.load(Ordering::Relaxed) {
+                                    break 'increment;
+                                }
+                            }
+                        }
+                    }
+                    sampler.record(start.elapsed());
+                    ops += 1;
+                }
+
+                ThreadResult {
+                    ops,
+                    aborts,
+                    latencies: sampler.into_latencies(),
+                }
+            });
+        print_table_row(&result);
+        emit_result("cas_counter", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workload: EVENT APPEND (one stream, global sequence)
+// ---------------------------------------------------------------------------
+
+/// One event payload, tagged with the writing thread and its local sequence.
+fn event_payload(tid: usize, seq: u64) -> Value {
+    Value::Object(HashMap::from([
+        ("tid".to_string(), Value::Int(tid as i64)),
+        ("seq".to_string(), Value::Int(seq as i64)),
+    ]))
+}
+
+/// Every thread appends to the same stream. Appends share one global
+/// sequence, so if assigning it serializes writers the abort rate and
+/// retries/op columns show it; a failed append is counted as an abort.
+fn run_event_append_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    eprintln!(
+        "\n=== EVENT APPEND (one stream, global sequence) | durability: {} ===",
+        mode.label()
+    );
+
+    print_table_header();
+
+    let mut results = Vec::new();
+    for &n in thread_sweep {
+        let bench_db = create_db(mode);
+
+        let result =
+            run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
+                let mut ops = 0u64;
+                let mut aborts = 0u64;
+                let mut seq = 0u64;
+
+                while !stop.load(Ordering::Relaxed) {
+                    seq += 1;
+                    let payload = event_payload(tid, seq);
+                    let start = pacer.start();
+                    match strata.event_append("stream", payload) {
+                        Ok(_) => {
+                            sampler.record(start.elapsed());
+                            ops += 1;
+                        }
+                        Err(_) => {
+                            aborts += 1;
+                        }
+                    }
+                }
+
+                ThreadResult {
+                    ops,
+                    aborts,
+                    latencies: sampler.into_latencies(),
+                }
+            });
+        print_table_row(&result);
+        emit_result("event_append", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workload: EVENT READ (read-only, pre-populated stream)
+// ---------------------------------------------------------------------------
+
+fn run_event_read_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    eprintln!(
+        "\n=== EVENT READ (read-only, no contention) | durability: {} ===",
+        mode.label()
+    );
+
+    let bench_db = create_db(mode);
+
+    // Pre-populate the stream; sequences are 1-based
+    eprint!("  Pre-populating {} events...", PREPOPULATE_KEYS);
+    for i in 0..PREPOPULATE_KEYS {
+        bench_db
+            .db
+            .event_append("stream", event_payload(0, i as u64))
+            .expect("pre-populate failed");
+    }
+    eprintln!(" done.");
+
+    print_table_header();
+
+    let mut results = Vec::new();
+    for &n in thread_sweep {
+        let result =
+            run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
+                let mut ops = 0u64;
+                let mut rng = thread_seed(seed, tid, 0xe7e27);
+
+                while !stop.load(Ordering::Relaxed) {
+                    let sequence = fast_rand(&mut rng) % PREPOPULATE_KEYS as u64 + 1;
+
+                    let start = pacer.start();
+                    let _ = strata.event_read(sequence);
+                    sampler.record(start.elapsed());
+                    ops += 1;
+                }
+
+                ThreadResult {
+                    ops,
+                    aborts: 0,
+                    latencies: sampler.into_latencies(),
+                }
+            });
+        print_table_row(&result);
+        emit_result("event_read", mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workload: Mixed reads/writes (configurable ratio, low contention)
+// ---------------------------------------------------------------------------
+
+/// `read_pct`% `kv_get` over pre-populated keys, the rest `kv_put` to
+/// thread-unique keys so writes don't contend.
+fn run_mixed_scaling(read_pct: u8, thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    let write_pct = 100 - read_pct;
+    let workload = format!("mixed_{}_{}", read_pct, write_pct);
+    eprintln!(
+        "\n=== MIXED {}/{} ({}% get, {}% put, low contention) | durability: {} ===",
+        read_pct,
+        write_pct,
+        read_pct,
+        write_pct,
         mode.label()
     );
 
@@ -210,25 +539,27 @@ fn run_mixed_90_10_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
 
     print_table_header();
 
+    let mut results = Vec::new();
     for &n in thread_sweep {
         let result =
             run_scaling_experiment(&bench_db.db, n, WARMUP_SECS, MEASURE_SECS, move |tid, strata, stop| {
-                let mut sampler = ReservoirSampler::with_seed(tid as u64);
+                let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                let mut pacer = Pacer::new();
                 let mut ops = 0u64;
-                let mut rng = tid as u64 ^ 0xfeedface;
+                let mut rng = thread_seed(seed, tid, 0xfeedface);
                 let mut seq = 0u64;
 
                 while !stop.load(Ordering::Relaxed) {
-                    let coin = fast_rand(&mut rng) % 10;
-                    let start = Instant::now();
+                    let coin = fast_rand(&mut rng) % 100;
+                    let start = pacer.start();
 
-                    if coin == 0 {
-                        // 10% writes -- thread-unique keys to avoid contention
+                    if coin >= read_pct as u64 {
+                        // Writes -- thread-unique keys to avoid contention
                         seq += 1;
                         let key = format!("mix_t{}_{}", tid, seq);
                         let _ = strata.kv_put(&key, Value::Int(seq as i64));
                     } else {
-                        // 90% reads -- random from pre-populated set
+                        // Reads -- random from pre-populated set
                         let idx = fast_rand(&mut rng) % PREPOPULATE_KEYS as u64;
                         let key = format!("key{:06}", idx);
                         let _ = strata.kv_get(&key);
@@ -241,10 +572,178 @@ fn run_mixed_90_10_scaling(thread_sweep: &[usize], mode: DurabilityConfig) {
                 ThreadResult {
                     ops,
                     aborts: 0,
-                    latencies: sampler.into_samples(),
+                    latencies: sampler.into_latencies(),
                 }
             });
         print_table_row(&result);
+        emit_result(&workload, mode, &result);
+        results.push(result);
+    }
+    if chart_enabled() {
+        print_ascii_chart(&results);
+    }
+}
+
+/// The default mixed workload: 90% get, 10% put.
+fn run_mixed_90_10_scaling(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    run_mixed_scaling(90, thread_sweep, mode, seed);
+}
+
+// ---------------------------------------------------------------------------
+// Workload: KV PUT under concurrent read load (reader-writer interference)
+// ---------------------------------------------------------------------------
+
+/// One writer thread overwrites random pre-populated keys while `readers`
+/// threads run `kv_get` over the same keyspace. Only the writer's latencies
+/// are sampled, so p50/p99 are write latency under that much read load.
+fn run_write_under_read_load(thread_sweep: &[usize], mode: DurabilityConfig, seed: u64) {
+    eprintln!(
+        "\n=== KV PUT latency under concurrent readers (1 writer) | durability: {} ===",
+        mode.label()
+    );
+
+    let bench_db = create_db(mode);
+
+    eprint!("  Pre-populating {} keys...", PREPOPULATE_KEYS);
+    for i in 0..PREPOPULATE_KEYS {
+        bench_db
+            .db
+            .kv_put(&format!("key{:06}", i), Value::Int(i as i64))
+            .expect("pre-populate failed");
+    }
+    eprintln!(" done.");
+
+    eprintln!(
+        "{:<8}| {:<12}| {:<9}| {:<9}| {:<9}| {:<10}| {:<12}",
+        "readers", "write ops/s", "p50", "p95", "p99", "p99 vs 0", "read ops/s"
+    );
+    eprintln!("{}", "-".repeat(80));
+
+    // Reader count 0 is the uncontended baseline
+    let mut reader_sweep = vec![0];
+    reader_sweep.extend_from_slice(thread_sweep);
+
+    let mut baseline_p99 = None;
+    for &readers in &reader_sweep {
+        // The measurement phase runs last, so its store overwrites the warmup's.
+        let writer_ops = Arc::new(AtomicU64::new(0));
+        let writer_ops_out = Arc::clone(&writer_ops);
+
+        let result = run_scaling_experiment(
+            &bench_db.db,
+            readers + 1,
+            WARMUP_SECS,
+            MEASURE_SECS,
+            move |tid, strata, stop| {
+                let mut rng = thread_seed(seed, tid, 0x5eed_cafe);
+                let mut ops = 0u64;
+
+                if tid == 0 {
+                    let mut sampler = Collector::for_thread(thread_seed(seed, tid, 0));
+                    let mut pacer = Pacer::new();
+                    let mut seq = 0u64;
+                    while !stop.load(Ordering::Relaxed) {
+                        let idx = fast_rand(&mut rng) % PREPOPULATE_KEYS as u64;
+                        let key = format!("key{:06}", idx);
+                        seq += 1;
+
+                        let start = pacer.start();
+                        let _ = strata.kv_put(&key, Value::Int(seq as i64));
+                        sampler.record(start.elapsed());
+                        ops += 1;
+                    }
+                    writer_ops_out.store(ops, Ordering::Relaxed);
+                    return ThreadResult {
+                        ops,
+                        aborts: 0,
+                        latencies: sampler.into_latencies(),
+                    };
+                }
+
+                // Readers: count ops but contribute no latency samples
+                while !stop.load(Ordering::Relaxed) {
+                    let idx = fast_rand(&mut rng) % PREPOPULATE_KEYS as u64;
+                    let key = format!("key{:06}", idx);
+                    let _ = strata.kv_get(&key);
+                    ops += 1;
+                }
+                ThreadResult {
+                    ops,
+                    aborts: 0,
+                    latencies: Latencies::none(),
+                }
+            },
+        );
+        emit_result("kv_put_under_read_load", mode, &result);
+
+        let secs = result.duration.as_secs_f64();
+        let write_ops = writer_ops.load(Ordering::Relaxed);
+        let read_ops = result.total_ops.saturating_sub(write_ops);
+        let base = *baseline_p99.get_or_insert(result.p99);
+        let degradation = if base.is_zero() {
+            "-".to_string()
+        } else {
+            format!("{:.2}x", result.p99.as_secs_f64() / base.as_secs_f64())
+        };
+
+        eprintln!(
+            "{:<8}| {:<12}| {:<9}| {:<9}| {:<9}| {:<10}| {:<12}",
+            readers,
+            fmt_ops(write_ops as f64 / secs),
+            fmt_duration(result.p50),
+            fmt_duration(result.p95),
+            fmt_duration(result.p99),
+            degradation,
+            fmt_ops(read_ops as f64 / secs),
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workload: hot-key PUT under a thread ramp
+// ---------------------------------------------------------------------------
+
+/// Hot-key `kv_put` while threads join stage by stage (one stage per thread
+/// count in the sweep), printing ops and aborts for each 1-second bucket.
+/// Shows how throughput and abort rate respond to each step in load.
+fn run_kv_put_hot_ramp(thread_sweep: &[usize], mode: DurabilityConfig) {
+    eprintln!(
+        "\n=== KV PUT hot-key ramp ({}s per stage, threads {:?}) | durability: {} ===",
+        RAMP_STAGE_SECS,
+        thread_sweep,
+        mode.label()
+    );
+
+    let bench_db = create_db(mode);
+    bench_db
+        .db
+        .kv_put("hot", Value::Int(0))
+        .expect("pre-populate hot key failed");
+
+    let buckets = run_ramp_experiment(&bench_db.db, thread_sweep, RAMP_STAGE_SECS, |_, strata, seq| {
+        strata.kv_put("hot", Value::Int(seq as i64)).is_ok()
+    });
+
+    eprintln!(
+        "{:<10}| {:<8}| {:<12}| {:<9}| {:<7}",
+        "elapsed_s", "threads", "ops/sec", "aborts", "abort%"
+    );
+    eprintln!("{}", "-".repeat(52));
+    for b in &buckets {
+        let attempts = b.ops + b.aborts;
+        let abort_pct = if attempts > 0 {
+            b.aborts as f64 / attempts as f64 * 100.0
+        } else {
+            0.0
+        };
+        eprintln!(
+            "{:<10}| {:<8}| {:<12}| {:<9}| {:<7.2}",
+            b.elapsed_sec,
+            b.active_threads,
+            fmt_ops(b.ops as f64),
+            b.aborts,
+            abort_pct
+        );
     }
 }
 
@@ -257,27 +756,188 @@ fn durability_modes() -> Vec<DurabilityConfig> {
 }
 
 // ---------------------------------------------------------------------------
-// Main
+// CLI parsing
 // ---------------------------------------------------------------------------
 
-fn main() {
-    // Parse --threads argument if provided
+fn print_usage() {
+    let default_sweep: Vec<String> = harness::scaling::thread_counts()
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+    println!(
+        "\
+Scaling benchmark: throughput and latency as a function of thread count.
+
+Usage: cargo bench --bench scaling -- [OPTIONS]
+
+Options:
+  --threads <list>      Comma-separated thread counts [default: {sweep}]
+                        (1, 2, 4, ... up to 2x available cores)
+  --mix <list>          Comma-separated read percentages for the mixed workload,
+                        e.g. 50,95,99 [default: 90]
+  --target-rate <n>     Pace each thread at n ops/sec and measure latency from each op's
+                        intended start; 0 = closed loop [default: 0]
+  --hot-keys <list>     Also sweep writes over N shared hot keys, e.g. 1,4,16,64
+  --seed <u64>          Base RNG seed for key selection and latency sampling [default: {seed}]
+  --csv                 Also print every result as CSV on stdout
+  --json                Also print every result as one JSON object per line on stdout
+  --chart               Draw ops/sec vs threads as a bar chart after each table, with a
+                        guide at the physical core count
+  --hdr                 Collect latencies in an HdrHistogram instead of a 10k-sample
+                        reservoir (needs --features hdr)
+  --strict              Exit with an error on unknown arguments instead of warning
+  -h, --help            Print this help and exit
+
+Each run is {warmup}s warmup + {measure}s measurement, in every durability mode.
+
+Examples:
+  cargo bench --bench scaling
+  cargo bench --bench scaling -- --threads 1,2,4",
+        sweep = default_sweep.join(","),
+        warmup = WARMUP_SECS,
+        measure = MEASURE_SECS,
+        seed = DEFAULT_SEED,
+    );
+}
+
+struct Args {
+    thread_sweep: Vec<usize>,
+    seed: u64,
+    /// `--target-rate` ops/sec per thread; 0 runs closed loop.
+    target_rate: u64,
+    /// `--mix` read percentages; `None` runs the default 90/10 mix.
+    mix: Option<Vec<u8>>,
+    /// `--hot-keys` key counts; `None` skips the hot-key contention sweep.
+    hot_keys: Option<Vec<usize>>,
+}
+
+/// Parse the thread sweep from `--threads` (defaulting to `thread_counts()`)
+/// and the base RNG seed from `--seed`, plus the pacing rate from `--target-rate`.
+fn parse_args() -> Args {
     let args: Vec<String> = std::env::args().collect();
-    let thread_sweep = if let Some(pos) = args.iter().position(|a| a == "--threads") {
-        if let Some(val) = args.get(pos + 1) {
-            parse_thread_counts(val)
-        } else {
-            harness::scaling::thread_counts()
+    let mut thread_sweep = None;
+    let mut seed = DEFAULT_SEED;
+    let mut target_rate = 0;
+    let mut mix = None;
+    let mut hot_keys = None;
+    let mut unknown = Vec::new();
+    let mut strict = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--threads" => {
+                i += 1;
+                thread_sweep = args.get(i).map(|val| parse_thread_counts(val));
+            }
+            "--mix" => {
+                i += 1;
+                mix = args.get(i).map(|val| parse_mix(val));
+            }
+            "--hot-keys" => {
+                i += 1;
+                hot_keys = args.get(i).map(|val| parse_hot_keys(val));
+            }
+            "--target-rate" => {
+                i += 1;
+                target_rate = match args.get(i).map(|val| val.parse()) {
+                    Some(Ok(val)) => val,
+                    _ => {
+                        eprintln!(
+                            "error: --target-rate expects ops/sec per thread (0 = closed loop)"
+                        );
+                        std::process::exit(2);
+                    }
+                };
+            }
+            "--seed" => {
+                i += 1;
+                seed = match args.get(i).map(|val| val.parse()) {
+                    Some(Ok(val)) => val,
+                    _ => {
+                        eprintln!("error: --seed expects an unsigned 64-bit integer");
+                        std::process::exit(2);
+                    }
+                };
+            }
+            "--csv" => set_output_format(OutputFormat::Csv),
+            "--json" => set_output_format(OutputFormat::Json),
+            "--chart" => set_chart(true),
+            "--hdr" => {
+                if !use_hdr(true) {
+                    eprintln!("error: --hdr requires building with --features hdr");
+                    std::process::exit(2);
+                }
+            }
+            "--strict" => strict = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => unknown.push(other.to_string()),
         }
-    } else {
-        harness::scaling::thread_counts()
-    };
+        i += 1;
+    }
+    check_unknown_args(&unknown, strict);
+
+    Args {
+        thread_sweep: thread_sweep.unwrap_or_else(harness::scaling::thread_counts),
+        seed,
+        target_rate,
+        mix,
+        hot_keys,
+    }
+}
+
+/// Parse `--mix` read percentages, exiting on anything outside 0..=100.
+fn parse_mix(val: &str) -> Vec<u8> {
+    val.split(',')
+        .map(|pct| match pct.trim().parse::<u8>() {
+            Ok(pct) if pct <= 100 => pct,
+            _ => {
+                eprintln!("error: --mix expects read percentages 0-100, got '{}'", pct);
+                std::process::exit(2);
+            }
+        })
+        .collect()
+}
+
+/// Parse `--hot-keys` counts, exiting on anything that isn't a positive integer.
+fn parse_hot_keys(val: &str) -> Vec<usize> {
+    val.split(',')
+        .map(|num| match num.trim().parse::<usize>() {
+            Ok(num) if num > 0 => num,
+            _ => {
+                eprintln!("error: --hot-keys expects positive key counts, got '{}'", num);
+                std::process::exit(2);
+            }
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() {
+    let args = parse_args();
+    set_target_rate(args.target_rate);
+    print_output_header();
 
     // Hardware info
     let cores = physical_cores();
     eprintln!("=== Scaling & Concurrency Benchmark Suite ===");
     eprintln!("Physical cores (available_parallelism): {}", cores);
-    eprintln!("Thread sweep: {:?}", thread_sweep);
+    eprintln!("Thread sweep: {:?}", args.thread_sweep);
+    eprintln!("Seed: {} (replay with --seed {})", args.seed, args.seed);
+    if args.target_rate > 0 {
+        eprintln!(
+            "Target rate: {} ops/sec per thread (latency from intended start)",
+            args.target_rate
+        );
+    } else {
+        eprintln!("Target rate: closed loop");
+    }
     eprintln!(
         "Measurement: {}s warmup + {}s measure per run",
         WARMUP_SECS, MEASURE_SECS
@@ -285,10 +945,25 @@ fn main() {
     eprintln!();
 
     for mode in durability_modes() {
-        run_kv_get_scaling(&thread_sweep, mode);
-        run_kv_put_independent_scaling(&thread_sweep, mode);
-        run_kv_put_hot_scaling(&thread_sweep, mode);
-        run_mixed_90_10_scaling(&thread_sweep, mode);
+        run_kv_get_scaling(&args.thread_sweep, mode, args.seed);
+        run_kv_put_independent_scaling(&args.thread_sweep, mode, args.seed);
+        run_kv_put_hot_scaling(&args.thread_sweep, mode, args.seed);
+        for &num_keys in args.hot_keys.iter().flatten() {
+            run_n_hot_keys_scaling(num_keys, &args.thread_sweep, mode, args.seed);
+        }
+        run_cas_counter_scaling(&args.thread_sweep, mode, args.seed);
+        run_event_append_scaling(&args.thread_sweep, mode, args.seed);
+        run_event_read_scaling(&args.thread_sweep, mode, args.seed);
+        match &args.mix {
+            None => run_mixed_90_10_scaling(&args.thread_sweep, mode, args.seed),
+            Some(pcts) => {
+                for &read_pct in pcts {
+                    run_mixed_scaling(read_pct, &args.thread_sweep, mode, args.seed);
+                }
+            }
+        }
+        run_write_under_read_load(&args.thread_sweep, mode, args.seed);
+        run_kv_put_hot_ramp(&args.thread_sweep, mode);
     }
 
     eprintln!("\n=== Benchmark complete ===");
@@ -1,4 +1,4 @@
-//! State primitive benchmarks: set, read, cas
+//! State primitive benchmarks: set, read, cas (success and fail-fast paths)
 //!
 //! All benchmarks report latency percentiles.
 
@@ -10,9 +10,10 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use harness::{
-    create_db, measure_with_counters, report_counters, report_percentiles, state_value,
-    DurabilityConfig, PERCENTILE_SAMPLES,
+    create_db, measure_with_counters, percentile_samples, report_counters, report_percentiles,
+    state_value, DurabilityConfig, ModeComparison,
 };
+use harness::scaling::fmt_duration;
 
 const CELL_POOL_SIZE: u64 = 100;
 
@@ -36,7 +37,7 @@ fn state_set(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("state/set/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed) % CELL_POOL_SIZE;
             bench_db
                 .db
@@ -44,7 +45,7 @@ fn state_set(c: &mut Criterion) {
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
@@ -53,6 +54,7 @@ fn state_read(c: &mut Criterion) {
     let mut group = c.benchmark_group("state/read");
     group.throughput(Throughput::Elements(1));
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: state/read ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
@@ -72,14 +74,16 @@ fn state_read(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("state/read/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed) % CELL_POOL_SIZE;
             bench_db.db.state_read(&format!("cell_{}", i)).unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        comparison.record("state/read", mode, &p);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
+    comparison.report("state/read");
 }
 
 fn state_cas(c: &mut Criterion) {
@@ -105,7 +109,7 @@ fn state_cas(c: &mut Criterion) {
         });
 
         let label = format!("state/cas/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
             let expected = version_counter.load(Ordering::Relaxed);
             let result = bench_db
                 .db
@@ -116,10 +120,84 @@ fn state_cas(c: &mut Criterion) {
             }
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
 }
 
-criterion_group!(benches, state_set, state_read, state_cas);
-criterion_main!(benches);
+/// CAS with a stale expected version, so every attempt fails.
+///
+/// Under contention most optimistic CAS attempts lose; this measures whether
+/// the failure path short-circuits before any write work. The percentile pass
+/// re-measures the success path on the same database so the two are directly
+/// comparable.
+fn state_cas_fail(c: &mut Criterion) {
+    let mut group = c.benchmark_group("state/cas_fail");
+    group.throughput(Throughput::Elements(1));
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: state/cas success vs fail ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let stale = bench_db.db.state_set("stale_cell", state_value()).unwrap();
+        bench_db.db.state_set("stale_cell", state_value()).unwrap();
+
+        group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
+            b.iter(|| {
+                let result = bench_db
+                    .db
+                    .state_cas("stale_cell", Some(stale), state_value())
+                    .unwrap();
+                assert!(result.is_none(), "CAS with stale version succeeded");
+            });
+        });
+
+        let label = format!("state/cas/success/{}", mode.label());
+        let mut version = bench_db.db.state_set("cas_cell", state_value()).unwrap();
+        let (success, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            if let Some(new_version) = bench_db
+                .db
+                .state_cas("cas_cell", Some(version), state_value())
+                .unwrap()
+            {
+                version = new_version;
+            }
+        });
+        report_percentiles(&label, &success);
+        report_counters(&label, &counters, percentile_samples() as u64);
+
+        let label = format!("state/cas/fail/{}", mode.label());
+        let (fail, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            let result = bench_db
+                .db
+                .state_cas("stale_cell", Some(stale), state_value())
+                .unwrap();
+            assert!(result.is_none(), "CAS with stale version succeeded");
+        });
+        report_percentiles(&label, &fail);
+        report_counters(&label, &counters, percentile_samples() as u64);
+
+        rows.push((mode, success.p50, fail.p50));
+    }
+    group.finish();
+
+    eprintln!("\n--- CAS success vs fail p50 ---");
+    eprintln!("  {:<10} {:>12} {:>12} {:>10}", "mode", "success", "fail", "fail/ok");
+    for (mode, success, fail) in rows {
+        let ratio = if success.as_nanos() > 0 {
+            fail.as_nanos() as f64 / success.as_nanos() as f64
+        } else {
+            0.0
+        };
+        eprintln!(
+            "  {:<10} {:>12} {:>12} {:>10}",
+            mode.label(),
+            fmt_duration(success),
+            fmt_duration(fail),
+            format!("{:.2}x", ratio),
+        );
+    }
+}
+
+criterion_group!(benches, state_set, state_read, state_cas, state_cas_fail);
+criterion_main!(benches, harness::baseline::check_baseline);
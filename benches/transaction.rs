@@ -0,0 +1,417 @@
+//! Cross-primitive transaction benchmarks: commit latency for session
+//! transactions that write to 1, 2 or all 4 of KV, State, Event and JSON.
+//!
+//! Each transaction writes one entry per primitive it touches, then commits.
+//! The summary compares every shape against the KV-only transaction, with
+//! WAL syncs, appends and bytes per commit, to show whether a cross-primitive
+//! commit costs the same as a single-primitive one or multiplies the WAL work.
+//!
+//! txn/size sweeps the number of `KvPut`s per committed transaction (1 to
+//! 10,000) and reports the whole transaction, the commit alone, and the
+//! amortized cost per put. The RSS column is resident memory just before
+//! commit relative to the start of the pass, so a transaction that buffers
+//! its writes badly shows up as a memory cliff at the large sizes.
+//!
+//! txn/rollback stages the same transaction sizes and times `TxnRollback`
+//! next to a commit of the same size. Rollback p50 flat across sizes means
+//! the pending writes are discarded as a buffer (O(1)); flat rollback per
+//! op means each write is undone (O(N)).
+//!
+//! State is written with `StateInit`, since `StateSet` bypasses transactions.
+//! All benchmarks report latency percentiles.
+
+#[allow(unused)]
+#[path = "harness/mod.rs"]
+mod harness;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use harness::metrics::{current_rss_bytes, fmt_mb};
+use harness::scaling::fmt_duration;
+use harness::{
+    counter_delta, create_db, event_payload, json_document, kv_key, kv_value,
+    measure_with_counters, percentile_samples, percentiles_from_timings, report_counters,
+    report_percentiles, snapshot_counters, state_value, DurabilityConfig, Percentiles,
+};
+use stratadb::{Command, Session, WalCounters};
+
+/// Which primitives a transaction writes to.
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    Kv,
+    KvState,
+    All,
+}
+
+impl Shape {
+    const ALL: [Self; 3] = [Self::Kv, Self::KvState, Self::All];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Kv => "kv",
+            Self::KvState => "kv+state",
+            Self::All => "kv+state+event+json",
+        }
+    }
+
+    fn primitives(&self) -> usize {
+        match self {
+            Self::Kv => 1,
+            Self::KvState => 2,
+            Self::All => 4,
+        }
+    }
+}
+
+/// Begin, write one entry per primitive in `shape`, and commit. `i` keeps
+/// keys and cells unique, since `StateInit` only creates a cell once.
+fn commit_txn(session: &mut Session, shape: Shape, i: u64) {
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .expect("txn begin failed");
+    session
+        .execute(Command::KvPut {
+            branch: None,
+            key: kv_key(i),
+            value: kv_value(),
+        })
+        .expect("txn kv_put failed");
+    if shape.primitives() >= 2 {
+        session
+            .execute(Command::StateInit {
+                branch: None,
+                cell: format!("cell_{}", i),
+                value: state_value(),
+            })
+            .expect("txn state_init failed");
+    }
+    if shape.primitives() >= 4 {
+        session
+            .execute(Command::EventAppend {
+                branch: None,
+                event_type: "txn_event".to_string(),
+                payload: event_payload(),
+            })
+            .expect("txn event_append failed");
+        session
+            .execute(Command::JsonSet {
+                branch: None,
+                key: format!("doc_{}", i),
+                path: "$".to_string(),
+                value: json_document(i),
+            })
+            .expect("txn json_set failed");
+    }
+    session.execute(Command::TxnCommit).expect("txn commit failed");
+}
+
+// =============================================================================
+// Commit latency — transaction shape × durability
+// =============================================================================
+
+struct ShapeResult {
+    shape: Shape,
+    mode: DurabilityConfig,
+    p: Percentiles,
+    counters: WalCounters,
+}
+
+fn txn_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("txn/commit");
+    group.throughput(Throughput::Elements(1));
+
+    let mut results = Vec::new();
+    eprintln!("\n--- Latency Percentiles: txn/commit ---");
+    for mode in DurabilityConfig::ALL {
+        for shape in Shape::ALL {
+            let bench_db = create_db(mode);
+            let mut session = bench_db.db.session();
+            // Shared by both passes so every transaction writes fresh keys
+            let counter = AtomicU64::new(0);
+            let id = format!("{}/{}", shape.label(), mode.label());
+
+            group.bench_function(BenchmarkId::new("shape", &id), |b| {
+                b.iter(|| {
+                    let i = counter.fetch_add(1, Ordering::Relaxed);
+                    commit_txn(&mut session, shape, i);
+                });
+            });
+
+            let label = format!("txn/commit/{}", id);
+            let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+                let i = counter.fetch_add(1, Ordering::Relaxed);
+                commit_txn(&mut session, shape, i);
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, percentile_samples() as u64);
+            results.push(ShapeResult { shape, mode, p, counters });
+        }
+    }
+    group.finish();
+    print_shape_summary(&results);
+}
+
+/// p50 and WAL work per commit for each shape, relative to KV-only in the
+/// same mode.
+fn print_shape_summary(results: &[ShapeResult]) {
+    let per_txn = |v: u64| v as f64 / percentile_samples() as f64;
+
+    eprintln!();
+    eprintln!("--- txn/commit: cost by primitives touched (vs kv-only) ---");
+    eprintln!(
+        "  {:<22} {:<9} {:>10} {:>8} {:>10} {:>12} {:>11}",
+        "shape", "mode", "p50", "vs kv", "syncs/txn", "appends/txn", "bytes/txn"
+    );
+    for r in results {
+        let base = results
+            .iter()
+            .find(|b| matches!(b.shape, Shape::Kv) && b.mode.label() == r.mode.label())
+            .map_or(r.p.p50, |b| b.p.p50);
+        let ratio = if base.is_zero() {
+            0.0
+        } else {
+            r.p.p50.as_secs_f64() / base.as_secs_f64()
+        };
+        eprintln!(
+            "  {:<22} {:<9} {:>10} {:>7.2}x {:>10.2} {:>12.2} {:>11.0}",
+            r.shape.label(),
+            r.mode.label(),
+            fmt_duration(r.p.p50),
+            ratio,
+            per_txn(r.counters.sync_calls),
+            per_txn(r.counters.wal_appends),
+            per_txn(r.counters.bytes_written),
+        );
+    }
+    eprintln!();
+}
+
+// =============================================================================
+// Transaction size — KvPuts per commit × durability
+// =============================================================================
+
+/// `KvPut`s per committed transaction.
+const TXN_SIZES: [u64; 5] = [1, 10, 100, 1_000, 10_000];
+
+/// Percentile samples for a transaction size: fewer samples for larger
+/// transactions, so every size writes about the same number of keys.
+fn txn_size_samples(ops: u64) -> usize {
+    let samples = percentile_samples();
+    (samples * 10 / ops as usize).clamp(10, samples)
+}
+
+/// Begin a transaction and stage `ops` puts starting at key `start`,
+/// leaving it open for the caller to commit.
+fn stage_puts(session: &mut Session, start: u64, ops: u64) {
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .expect("txn begin failed");
+    for i in start..start + ops {
+        session
+            .execute(Command::KvPut {
+                branch: None,
+                key: kv_key(i),
+                value: kv_value(),
+            })
+            .expect("txn kv_put failed");
+    }
+}
+
+struct SizeResult {
+    ops: u64,
+    mode: DurabilityConfig,
+    txn: Percentiles,
+    commit: Percentiles,
+    counters: WalCounters,
+    samples: usize,
+    /// Largest RSS growth seen just before a commit.
+    rss_growth: u64,
+}
+
+fn txn_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("txn/size");
+
+    let mut results = Vec::new();
+    eprintln!("\n--- Latency Percentiles: txn/size ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let mut session = bench_db.db.session();
+        // Shared across sizes so every transaction writes fresh keys
+        let counter = AtomicU64::new(0);
+
+        for ops in TXN_SIZES {
+            group.throughput(Throughput::Elements(ops));
+            group.sample_size(if ops >= 1_000 { 10 } else { 100 });
+            let id = format!("{}/{}", ops, mode.label());
+            group.bench_function(BenchmarkId::new("ops", &id), |b| {
+                b.iter(|| {
+                    stage_puts(&mut session, counter.fetch_add(ops, Ordering::Relaxed), ops);
+                    session.execute(Command::TxnCommit).expect("txn commit failed");
+                });
+            });
+
+            // Percentile pass, timed by hand so the commit can be split out
+            // and RSS read between staging and commit without being timed
+            let samples = txn_size_samples(ops);
+            let rss_start = current_rss_bytes();
+            let mut rss_growth = 0;
+            let mut txn_times = Vec::with_capacity(samples);
+            let mut commit_times = Vec::with_capacity(samples);
+            let before = snapshot_counters(&bench_db);
+            for _ in 0..samples {
+                let start = Instant::now();
+                stage_puts(&mut session, counter.fetch_add(ops, Ordering::Relaxed), ops);
+                let staged = start.elapsed();
+                rss_growth = rss_growth.max(current_rss_bytes().saturating_sub(rss_start));
+
+                let start = Instant::now();
+                session.execute(Command::TxnCommit).expect("txn commit failed");
+                let commit = start.elapsed();
+                txn_times.push(staged + commit);
+                commit_times.push(commit);
+            }
+            let counters = counter_delta(&before, &snapshot_counters(&bench_db));
+
+            let label = format!("txn/size/{}", id);
+            let txn = percentiles_from_timings(txn_times);
+            let commit = percentiles_from_timings(commit_times);
+            report_percentiles(&label, &txn);
+            report_percentiles(&format!("txn/size/commit/{}", id), &commit);
+            // Per put, so the amortization across the transaction shows directly
+            report_counters(&label, &counters, samples as u64 * ops);
+            results.push(SizeResult { ops, mode, txn, commit, counters, samples, rss_growth });
+        }
+    }
+    group.finish();
+    print_size_summary(&results);
+}
+
+fn print_size_summary(results: &[SizeResult]) {
+    let per_put = |d: Duration, ops: u64| fmt_duration(d / ops as u32);
+
+    eprintln!();
+    eprintln!("--- txn/size: amortized cost per put ---");
+    eprintln!(
+        "  {:<9} {:>7} {:>11} {:>11} {:>11} {:>10} {:>10} {:>11}",
+        "mode", "ops", "txn p50", "commit p50", "p50/put", "syncs/txn", "bytes/put", "rss+"
+    );
+    for r in results {
+        let txns = r.samples as f64;
+        eprintln!(
+            "  {:<9} {:>7} {:>11} {:>11} {:>11} {:>10.2} {:>10.0} {:>11}",
+            r.mode.label(),
+            r.ops,
+            fmt_duration(r.txn.p50),
+            fmt_duration(r.commit.p50),
+            per_put(r.txn.p50, r.ops),
+            r.counters.sync_calls as f64 / txns,
+            r.counters.bytes_written as f64 / (txns * r.ops as f64),
+            fmt_mb(r.rss_growth),
+        );
+    }
+    eprintln!();
+}
+
+// =============================================================================
+// Rollback — pending KvPuts × durability, against commit of the same size
+// =============================================================================
+
+struct RollbackResult {
+    ops: u64,
+    mode: DurabilityConfig,
+    rollback: Percentiles,
+    commit: Percentiles,
+    /// WAL counters over the rollback samples only; an abort should write
+    /// nothing.
+    counters: WalCounters,
+    samples: usize,
+}
+
+/// Stage `ops` puts and time only the closing `TxnRollback` or `TxnCommit`.
+fn time_close(session: &mut Session, counter: &AtomicU64, ops: u64, close: Command) -> Duration {
+    stage_puts(session, counter.fetch_add(ops, Ordering::Relaxed), ops);
+    let start = Instant::now();
+    session.execute(close).expect("txn close failed");
+    start.elapsed()
+}
+
+fn txn_rollback(c: &mut Criterion) {
+    let mut group = c.benchmark_group("txn/rollback");
+
+    let mut results = Vec::new();
+    eprintln!("\n--- Latency Percentiles: txn/rollback ---");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        let mut session = bench_db.db.session();
+        let counter = AtomicU64::new(0);
+
+        for ops in TXN_SIZES {
+            group.sample_size(if ops >= 1_000 { 10 } else { 100 });
+            let id = format!("{}/{}", ops, mode.label());
+            // Staging is untimed, so this is the rollback alone
+            group.bench_function(BenchmarkId::new("ops", &id), |b| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| time_close(&mut session, &counter, ops, Command::TxnRollback))
+                        .sum()
+                });
+            });
+
+            let samples = txn_size_samples(ops);
+            let before = snapshot_counters(&bench_db);
+            let rollback_times = (0..samples)
+                .map(|_| time_close(&mut session, &counter, ops, Command::TxnRollback))
+                .collect();
+            let counters = counter_delta(&before, &snapshot_counters(&bench_db));
+            let commit_times = (0..samples)
+                .map(|_| time_close(&mut session, &counter, ops, Command::TxnCommit))
+                .collect();
+
+            let rollback = percentiles_from_timings(rollback_times);
+            let commit = percentiles_from_timings(commit_times);
+            report_percentiles(&format!("txn/rollback/{}", id), &rollback);
+            report_percentiles(&format!("txn/rollback/commit/{}", id), &commit);
+            results.push(RollbackResult { ops, mode, rollback, commit, counters, samples });
+        }
+    }
+    group.finish();
+    print_rollback_summary(&results);
+}
+
+fn print_rollback_summary(results: &[RollbackResult]) {
+    eprintln!();
+    eprintln!("--- txn/rollback vs commit of the same size ---");
+    eprintln!(
+        "  {:<9} {:>7} {:>13} {:>11} {:>11} {:>15} {:>15}",
+        "mode", "ops", "rollback p50", "commit p50", "rb/commit", "rollback/op", "WAL bytes/rb"
+    );
+    for r in results {
+        let ratio = if r.commit.p50.is_zero() {
+            0.0
+        } else {
+            r.rollback.p50.as_secs_f64() / r.commit.p50.as_secs_f64()
+        };
+        eprintln!(
+            "  {:<9} {:>7} {:>13} {:>11} {:>10.2}x {:>15} {:>15.0}",
+            r.mode.label(),
+            r.ops,
+            fmt_duration(r.rollback.p50),
+            fmt_duration(r.commit.p50),
+            ratio,
+            fmt_duration(r.rollback.p50 / r.ops as u32),
+            r.counters.bytes_written as f64 / r.samples as f64,
+        );
+    }
+    eprintln!();
+}
+
+criterion_group!(benches, txn_commit, txn_size, txn_rollback);
+criterion_main!(benches, harness::baseline::check_baseline);
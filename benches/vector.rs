@@ -1,4 +1,5 @@
-//! Vector primitive benchmarks: upsert, search, get
+//! Vector primitive benchmarks: upsert, search, search_by_metric, search_top1_vs_topk,
+//! recall, get
 //!
 //! Reduced sample_size because vector operations are inherently slower.
 //! All benchmarks report latency percentiles.
@@ -11,9 +12,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use harness::{
-    create_db, measure_with_counters, report_counters, report_percentiles, vector_128d,
-    DurabilityConfig, PERCENTILE_SAMPLES, WARMUP_COUNT,
+    create_db, exact_top_k, measure_with_counters, percentile_samples, recall_at_k,
+    report_counters, report_percentiles, vector_128d, warmup_count, DurabilityConfig,
+    ModeComparison,
 };
+use harness::scaling::fmt_duration;
 use stratadb::DistanceMetric;
 
 fn vector_upsert(c: &mut Criterion) {
@@ -41,8 +44,8 @@ fn vector_upsert(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(u64::MAX / 2);
         let samples = match mode {
-            DurabilityConfig::Always => 200,
-            _ => PERCENTILE_SAMPLES,
+            DurabilityConfig::Always => 200.min(percentile_samples()),
+            _ => percentile_samples(),
         };
         let label = format!("vector/upsert/{}", mode.label());
         let (p, counters) = measure_with_counters(&bench_db, samples, || {
@@ -59,10 +62,12 @@ fn vector_upsert(c: &mut Criterion) {
 }
 
 fn vector_search(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("vector/search");
     group.throughput(Throughput::Elements(1));
     group.sample_size(20);
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: vector/search ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
@@ -70,7 +75,7 @@ fn vector_search(c: &mut Criterion) {
             .db
             .vector_create_collection("bench_col", 128, DistanceMetric::Cosine)
             .unwrap();
-        for i in 0..WARMUP_COUNT {
+        for i in 0..warmup {
             bench_db
                 .db
                 .vector_upsert("bench_col", &format!("vec_{}", i), vector_128d(i), None)
@@ -82,7 +87,7 @@ fn vector_search(c: &mut Criterion) {
                 let i = counter.fetch_add(1, Ordering::Relaxed);
                 bench_db
                     .db
-                    .vector_search("bench_col", vector_128d(WARMUP_COUNT + i), 10)
+                    .vector_search("bench_col", vector_128d(warmup + i), 10)
                     .unwrap();
             });
         });
@@ -93,20 +98,24 @@ fn vector_search(c: &mut Criterion) {
             let i = pct_counter.fetch_add(1, Ordering::Relaxed);
             bench_db
                 .db
-                .vector_search("bench_col", vector_128d(WARMUP_COUNT + i), 10)
+                .vector_search("bench_col", vector_128d(warmup + i), 10)
                 .unwrap();
         });
         report_percentiles(&label, &p);
+        comparison.record("vector/search", mode, &p);
         report_counters(&label, &counters, 200);
     }
     group.finish();
+    comparison.report("vector/search");
 }
 
 fn vector_get(c: &mut Criterion) {
+    let warmup = warmup_count();
     let mut group = c.benchmark_group("vector/get");
     group.throughput(Throughput::Elements(1));
     group.sample_size(50);
 
+    let mut comparison = ModeComparison::new();
     eprintln!("\n--- Latency Percentiles: vector/get ---");
     for mode in DurabilityConfig::ALL {
         let bench_db = create_db(mode);
@@ -114,7 +123,7 @@ fn vector_get(c: &mut Criterion) {
             .db
             .vector_create_collection("bench_col", 128, DistanceMetric::Cosine)
             .unwrap();
-        for i in 0..WARMUP_COUNT {
+        for i in 0..warmup {
             bench_db
                 .db
                 .vector_upsert("bench_col", &format!("vec_{}", i), vector_128d(i), None)
@@ -123,7 +132,7 @@ fn vector_get(c: &mut Criterion) {
         let counter = AtomicU64::new(0);
         group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
             b.iter(|| {
-                let i = counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+                let i = counter.fetch_add(1, Ordering::Relaxed) % warmup;
                 bench_db
                     .db
                     .vector_get("bench_col", &format!("vec_{}", i))
@@ -133,18 +142,277 @@ fn vector_get(c: &mut Criterion) {
 
         let pct_counter = AtomicU64::new(0);
         let label = format!("vector/get/{}", mode.label());
-        let (p, counters) = measure_with_counters(&bench_db, PERCENTILE_SAMPLES, || {
-            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % WARMUP_COUNT;
+        let (p, counters) = measure_with_counters(&bench_db, percentile_samples(), || {
+            let i = pct_counter.fetch_add(1, Ordering::Relaxed) % warmup;
             bench_db
                 .db
                 .vector_get("bench_col", &format!("vec_{}", i))
                 .unwrap();
         });
         report_percentiles(&label, &p);
-        report_counters(&label, &counters, PERCENTILE_SAMPLES as u64);
+        comparison.record("vector/get", mode, &p);
+        report_counters(&label, &counters, percentile_samples() as u64);
     }
     group.finish();
+    comparison.report("vector/get");
 }
 
-criterion_group!(benches, vector_upsert, vector_search, vector_get);
-criterion_main!(benches);
+/// Distance metrics swept by `vector_search_by_metric`, by dataset name.
+const METRICS: [&str; 3] = ["cosine", "euclidean", "dot_product"];
+
+/// Map a metric name to `DistanceMetric`, as `parse_metric` does for the
+/// dataset tests.
+fn parse_metric(name: &str) -> DistanceMetric {
+    match name {
+        "cosine" => DistanceMetric::Cosine,
+        "euclidean" => DistanceMetric::Euclidean,
+        "dot_product" => DistanceMetric::DotProduct,
+        other => panic!("unknown distance metric: {}", other),
+    }
+}
+
+/// `vector_search` cost per distance metric.
+///
+/// Search doesn't write, so durability mode doesn't affect it; every metric
+/// runs in cache mode over the same 128-d vectors and queries.
+fn vector_search_by_metric(c: &mut Criterion) {
+    let warmup = warmup_count();
+    let mut group = c.benchmark_group("vector/search_by_metric");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(20);
+
+    let mut p50s = Vec::new();
+    eprintln!("\n--- Latency Percentiles: vector/search_by_metric ---");
+    for metric in METRICS {
+        let bench_db = create_db(DurabilityConfig::Cache);
+        bench_db
+            .db
+            .vector_create_collection("bench_col", 128, parse_metric(metric))
+            .unwrap();
+        for i in 0..warmup {
+            bench_db
+                .db
+                .vector_upsert("bench_col", &format!("vec_{}", i), vector_128d(i), None)
+                .unwrap();
+        }
+        let counter = AtomicU64::new(0);
+        group.bench_function(BenchmarkId::new("metric", metric), |b| {
+            b.iter(|| {
+                let i = counter.fetch_add(1, Ordering::Relaxed);
+                bench_db
+                    .db
+                    .vector_search("bench_col", vector_128d(warmup + i), 10)
+                    .unwrap();
+            });
+        });
+
+        let pct_counter = AtomicU64::new(0);
+        let label = format!("vector/search/{}", metric);
+        let (p, counters) = measure_with_counters(&bench_db, 200, || {
+            let i = pct_counter.fetch_add(1, Ordering::Relaxed);
+            bench_db
+                .db
+                .vector_search("bench_col", vector_128d(warmup + i), 10)
+                .unwrap();
+        });
+        report_percentiles(&label, &p);
+        report_counters(&label, &counters, 200);
+        p50s.push((metric, p.p50));
+    }
+    group.finish();
+
+    eprintln!("\n--- vector/search p50 by metric (relative to cosine) ---");
+    let base = p50s[0].1.as_nanos() as f64;
+    for (metric, p50) in p50s {
+        let ratio = if base > 0.0 { p50.as_nanos() as f64 / base } else { 0.0 };
+        eprintln!(
+            "  {:<12} {:>12} {:>8}",
+            metric,
+            fmt_duration(p50),
+            format!("{:.2}x", ratio)
+        );
+    }
+}
+
+/// Result counts for the top-1 vs top-k comparison.
+const SEARCH_K_SWEEP: [u64; 3] = [1, 10, 100];
+
+/// Time-to-first-result versus time-to-complete for `vector_search`.
+///
+/// `vector_search` is eager: it returns a full `Vec` of k hits, so there is no
+/// way to observe the top hit before the rest. The k=1 search is the closest
+/// available proxy for time-to-first-result; if it is much cheaper than k=10
+/// or k=100, a streaming/iterator search API would pay off for interactive
+/// callers that render the top hit first.
+fn vector_search_top1_vs_topk(c: &mut Criterion) {
+    let warmup = warmup_count();
+    let mut group = c.benchmark_group("vector/search_top1_vs_topk");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(20);
+
+    let mut rows = Vec::new();
+    eprintln!("\n--- Latency Percentiles: vector/search_top1_vs_topk ---");
+    eprintln!("  note: vector_search is eager; k=1 approximates time-to-first-result");
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        bench_db
+            .db
+            .vector_create_collection("bench_col", 128, DistanceMetric::Cosine)
+            .unwrap();
+        for i in 0..warmup {
+            bench_db
+                .db
+                .vector_upsert("bench_col", &format!("vec_{}", i), vector_128d(i), None)
+                .unwrap();
+        }
+
+        let mut p50s = Vec::new();
+        for k in SEARCH_K_SWEEP {
+            let counter = AtomicU64::new(0);
+            let id = format!("k{}/{}", k, mode.label());
+            group.bench_function(BenchmarkId::new("durability", &id), |b| {
+                b.iter(|| {
+                    let i = counter.fetch_add(1, Ordering::Relaxed);
+                    bench_db
+                        .db
+                        .vector_search("bench_col", vector_128d(warmup + i), k)
+                        .unwrap();
+                });
+            });
+
+            let pct_counter = AtomicU64::new(0);
+            let label = format!("vector/search/k{}/{}", k, mode.label());
+            let (p, counters) = measure_with_counters(&bench_db, 200, || {
+                let i = pct_counter.fetch_add(1, Ordering::Relaxed);
+                bench_db
+                    .db
+                    .vector_search("bench_col", vector_128d(warmup + i), k)
+                    .unwrap();
+            });
+            report_percentiles(&label, &p);
+            report_counters(&label, &counters, 200);
+            p50s.push(p.p50);
+        }
+        rows.push((mode, p50s));
+    }
+    group.finish();
+
+    eprintln!("\n--- vector/search p50: top-1 (first-result proxy) vs top-k ---");
+    eprint!("  {:<10}", "mode");
+    for k in SEARCH_K_SWEEP {
+        eprint!(" {:>12}", format!("k={}", k));
+    }
+    eprintln!(" {:>12}", "k1/k_max");
+    for (mode, p50s) in rows {
+        eprint!("  {:<10}", mode.label());
+        for p50 in &p50s {
+            eprint!(" {:>12}", fmt_duration(*p50));
+        }
+        let first = p50s[0].as_nanos() as f64;
+        let full = p50s[p50s.len() - 1].as_nanos() as f64;
+        let ratio = if full > 0.0 { first / full } else { 0.0 };
+        eprintln!(" {:>12}", format!("{:.2}", ratio));
+    }
+}
+
+/// Number of queries for the recall check.
+const RECALL_QUERIES: u64 = 100;
+
+/// Result count for the recall check (recall@k).
+const RECALL_K: u64 = 10;
+
+/// recall@k of `vector_search` against exact brute-force top-k.
+///
+/// Search is exhaustive today, so recall should be 1.0. Reporting it guards
+/// against an approximate index that improves latency while silently dropping
+/// true neighbours. As a cross-check, each query's exact nearest neighbour is
+/// also compared with the first hit, like `SearchQuery.expected_top` in the
+/// dataset tests.
+fn vector_recall(c: &mut Criterion) {
+    let warmup = warmup_count();
+    let mut group = c.benchmark_group("vector/recall");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(20);
+
+    // Ground truth is independent of durability mode, so compute it once
+    let corpus: Vec<(String, Vec<f32>)> = (0..warmup)
+        .map(|i| (format!("vec_{}", i), vector_128d(i)))
+        .collect();
+    let queries: Vec<(Vec<f32>, Vec<String>)> = (0..RECALL_QUERIES)
+        .map(|i| {
+            let query = vector_128d(warmup + i);
+            let exact = exact_top_k(&corpus, &query, RECALL_K as usize);
+            (query, exact)
+        })
+        .collect();
+
+    eprintln!("\n--- Recall: vector/recall (k={}, {} queries) ---", RECALL_K, RECALL_QUERIES);
+    for mode in DurabilityConfig::ALL {
+        let bench_db = create_db(mode);
+        bench_db
+            .db
+            .vector_create_collection("bench_col", 128, DistanceMetric::Cosine)
+            .unwrap();
+        for (key, vector) in &corpus {
+            bench_db
+                .db
+                .vector_upsert("bench_col", key, vector.clone(), None)
+                .unwrap();
+        }
+
+        let counter = AtomicU64::new(0);
+        group.bench_function(BenchmarkId::new("durability", mode.label()), |b| {
+            b.iter(|| {
+                let i = counter.fetch_add(1, Ordering::Relaxed) % RECALL_QUERIES;
+                bench_db
+                    .db
+                    .vector_search("bench_col", queries[i as usize].0.clone(), RECALL_K)
+                    .unwrap();
+            });
+        });
+
+        let mut recall_sum = 0.0;
+        let mut top1_matches = 0;
+        for (query, exact) in &queries {
+            let got: Vec<String> = bench_db
+                .db
+                .vector_search("bench_col", query.clone(), RECALL_K)
+                .unwrap()
+                .into_iter()
+                .map(|hit| hit.key)
+                .collect();
+            recall_sum += recall_at_k(exact, &got);
+            if got.first() == exact.first() {
+                top1_matches += 1;
+            }
+        }
+        let recall = recall_sum / queries.len() as f64;
+        eprintln!(
+            "  vector/recall/{}: recall@{} = {:.4}, top-1 = exact nearest in {}/{} queries",
+            mode.label(),
+            RECALL_K,
+            recall,
+            top1_matches,
+            queries.len()
+        );
+        if top1_matches < queries.len() {
+            eprintln!(
+                "  warning: vector/recall/{}: {} queries missed their exact nearest neighbour",
+                mode.label(),
+                queries.len() - top1_matches
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    vector_upsert,
+    vector_search,
+    vector_search_by_metric,
+    vector_search_top1_vs_topk,
+    vector_recall,
+    vector_get
+);
+criterion_main!(benches, harness::baseline::check_baseline);
@@ -0,0 +1,85 @@
+//! Print the history of one benchmark metric from a `--store` results file.
+//!
+//! Record:  `cargo bench --bench redis_compare --features sqlite-compare -- --store results.db`
+//! Query:   `cargo run --example query_trends --features sqlite-compare -- results.db SET always p99_ms`
+//! Last 30: `cargo run --example query_trends --features sqlite-compare -- results.db SET always p99_ms --last 30`
+//! Series:  `cargo run --example query_trends --features sqlite-compare -- results.db`
+//!
+//! fill_level results are stored as operation `<test>@<fill_level>`, e.g.
+//! `kv_get@100000`.
+
+#[allow(unused)]
+#[path = "../benches/harness/store.rs"]
+mod store;
+
+const DEFAULT_LAST: usize = 30;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut positional = Vec::new();
+    let mut last = DEFAULT_LAST;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--last" => {
+                i += 1;
+                last = args.get(i).and_then(|n| n.parse().ok()).unwrap_or(DEFAULT_LAST);
+            }
+            other => positional.push(other.to_string()),
+        }
+        i += 1;
+    }
+
+    let Some(path) = positional.first() else {
+        eprintln!("usage: query_trends <results.db> [<operation> <mode> <metric>] [--last N]");
+        std::process::exit(2);
+    };
+
+    if positional.len() < 4 {
+        list_series(path);
+        return;
+    }
+    let (operation, mode, metric) = (&positional[1], &positional[2], &positional[3]);
+
+    let points = store::history(path, operation, mode, metric, last).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    if points.is_empty() {
+        eprintln!("no results for {}/{}/{} in {}", operation, mode, metric, path);
+        std::process::exit(1);
+    }
+
+    println!("{} / {} / {} (last {} runs)", operation, mode, metric, points.len());
+    println!();
+    println!("{:<12} {:<20} {:>14} {:>9}", "commit", "time (UTC)", metric, "change");
+    println!("{}", "-".repeat(58));
+    let mut prev: Option<f64> = None;
+    for p in &points {
+        let change = match prev {
+            Some(prev) if prev != 0.0 => format!("{:+.1}%", (p.value - prev) / prev * 100.0),
+            _ => "-".to_string(),
+        };
+        println!("{:<12} {:<20} {:>14.3} {:>9}", p.commit, p.time, p.value, change);
+        prev = Some(p.value);
+    }
+
+    // Results from different machines are not comparable; say so rather than
+    // letting a hardware change masquerade as a regression.
+    let first_hw = &points[0].hardware;
+    if points.iter().any(|p| &p.hardware != first_hw) {
+        println!();
+        println!("note: these runs span more than one machine; compare like with like");
+    }
+}
+
+fn list_series(path: &str) {
+    let series = store::series(path).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    println!("{} series in {}:", series.len(), path);
+    for (operation, mode, metric) in series {
+        println!("  {} {} {}", operation, mode, metric);
+    }
+}
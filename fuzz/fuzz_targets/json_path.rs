@@ -0,0 +1,83 @@
+//! Fuzz JSON path parsing through `json_set` / `json_get` / `json_delete`.
+//!
+//! Input layout: `<path bytes> 0x00 <json bytes>`. The path is taken lossily
+//! as UTF-8; the JSON part is parsed with serde_json and falls back to a
+//! small fixed object when it is missing or invalid, so every input still
+//! exercises the path parser. Same contract as `tests/dataset_dirty.rs`:
+//! an error is fine, a panic is a bug.
+//!
+//! Run with `cargo fuzz run json_path` from the repo root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value as Json;
+use std::sync::OnceLock;
+use stratadb::{Strata, Value};
+
+/// Larger inputs only slow the fuzzer down without reaching new parser states.
+const MAX_INPUT: usize = 4096;
+
+const DOC: &str = "fuzz";
+
+fn db() -> &'static Strata {
+    static DB: OnceLock<Strata> = OnceLock::new();
+    DB.get_or_init(|| Strata::open_temp().expect("failed to open temp db"))
+}
+
+fn json_to_value(v: &Json) -> Value {
+    match v {
+        Json::Null => Value::Null,
+        Json::Bool(b) => Value::Bool(*b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Int(i),
+            None => Value::Float(n.as_f64().unwrap_or(0.0)),
+        },
+        Json::String(s) => Value::String(s.clone()),
+        Json::Array(items) => Value::Array(items.iter().map(json_to_value).collect()),
+        Json::Object(fields) => Value::Object(
+            fields.iter().map(|(k, v)| (k.clone(), json_to_value(v))).collect(),
+        ),
+    }
+}
+
+/// A document with objects, arrays and scalars for paths to land in.
+fn base_doc() -> Value {
+    json_to_value(&serde_json::json!({
+        "a": { "b": { "c": 1 } },
+        "list": [0, { "x": "y" }, [true, null]],
+        "": { "0": "empty-key" },
+        "dotted.key": 2.5,
+    }))
+}
+
+/// What gets written at the path when the input has no valid JSON: a small
+/// object with a nested array, so later paths below it still navigate.
+fn fallback_value() -> Value {
+    json_to_value(&serde_json::json!({ "k": [7, { "n": null }] }))
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() > MAX_INPUT {
+        return;
+    }
+    let (path, json) = match data.iter().position(|&b| b == 0) {
+        Some(split) => (&data[..split], &data[split + 1..]),
+        None => (data, &[][..]),
+    };
+    let path = String::from_utf8_lossy(path);
+    let value = serde_json::from_slice::<Json>(json)
+        .map(|j| json_to_value(&j))
+        .unwrap_or_else(|_| fallback_value());
+
+    let db = db();
+    // Start every input from the same document; results are deliberately
+    // ignored, only a panic or abort counts as a failure
+    let _ = db.json_set(DOC, "$", base_doc());
+    let _ = db.json_get(DOC, &path);
+    let _ = db.json_set(DOC, &path, value);
+    let _ = db.json_get(DOC, &path);
+    let _ = db.json_get(DOC, "$");
+    let _ = db.json_delete(DOC, &path);
+    let _ = db.json_get(DOC, "$");
+});
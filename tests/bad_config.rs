@@ -0,0 +1,134 @@
+//! Black-box tests for opening a database with a malformed strata.toml.
+//!
+//! A bad config must surface as an `Error` that names the problem — never a
+//! panic, and never a silent fallback to the default durability mode.
+
+use stratadb::{Database, Error, Strata};
+use std::panic;
+use std::path::Path;
+
+/// Write `contents` as the data directory's strata.toml.
+fn write_config(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path).expect("create dir for config");
+    std::fs::write(path.join("strata.toml"), contents).expect("write config");
+}
+
+/// Open the database, failing the test on panic or on success.
+fn open_expect_err(path: &Path) -> Error {
+    let result = panic::catch_unwind(|| Database::open(path));
+    match result {
+        Err(panic_info) => panic!("[PANIC] Database::open panicked on bad config: {:?}", panic_info),
+        Ok(Ok(_)) => panic!("Database::open accepted a bad config instead of rejecting it"),
+        Ok(Err(e)) => e,
+    }
+}
+
+/// The error message should point the user at the config file.
+fn assert_mentions_config(err: &Error) {
+    let msg = err.to_string().to_lowercase();
+    assert!(
+        msg.contains("config") || msg.contains("strata.toml") || msg.contains("toml"),
+        "error does not mention the config: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Unknown durability value
+// =============================================================================
+
+#[test]
+fn bogus_durability_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = \"bogus\"\n");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+    assert!(
+        err.to_string().contains("bogus") || err.to_string().to_lowercase().contains("durability"),
+        "error does not name the bad durability value: {}",
+        err
+    );
+}
+
+#[test]
+fn durability_wrong_type_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = 42\n");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+}
+
+#[test]
+fn durability_wrong_case_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = \"ALWAYS_PLEASE\"\n");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+}
+
+// =============================================================================
+// Garbage TOML
+// =============================================================================
+
+#[test]
+fn garbage_toml_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "this is [[not toml = = \"\n");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+}
+
+#[test]
+fn unterminated_string_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = \"always\n");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+}
+
+#[test]
+fn binary_config_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("strata.toml"), [0xFF, 0xFE, 0x00, 0x13, 0x37])
+        .expect("write binary config");
+
+    let err = open_expect_err(dir.path());
+    assert_mentions_config(&err);
+}
+
+// =============================================================================
+// Strata::open goes through the same path
+// =============================================================================
+
+#[test]
+fn strata_open_rejects_bogus_durability() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = \"bogus\"\n");
+
+    let result = panic::catch_unwind(|| Strata::open(dir.path()));
+    match result {
+        Err(_) => panic!("[PANIC] Strata::open panicked on bad config"),
+        Ok(Ok(_)) => panic!("Strata::open accepted a bad config"),
+        Ok(Err(_)) => {}
+    }
+}
+
+// =============================================================================
+// Recovery after fixing the config
+// =============================================================================
+
+#[test]
+fn fixing_config_allows_open() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(dir.path(), "durability = \"bogus\"\n");
+    open_expect_err(dir.path());
+
+    write_config(dir.path(), "durability = \"always\"\n");
+    let db = Database::open(dir.path()).unwrap();
+    assert!(db.is_open());
+}
@@ -0,0 +1,179 @@
+//! Black-box tests for branches used from several threads at once.
+//!
+//! Each thread gets its own `new_handle()`, creates its own branch and writes
+//! through `set_branch`. Since `set_branch` takes `&mut self`, the current
+//! branch is per-handle state: switching on one handle must never redirect
+//! another handle's reads or writes, and no thread's data may show up on
+//! another thread's branch.
+
+use stratadb::{Strata, Value};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+const THREADS: usize = 8;
+const KEYS_PER_THREAD: usize = 200;
+/// Branch switches per thread in the ping-pong test.
+const SWITCHES: usize = 200;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn branch_name(t: usize) -> String {
+    format!("worker-{}", t)
+}
+
+/// Every thread writes the same keys, so a leak shows up as a wrong value
+/// rather than an extra key.
+fn key(i: usize) -> String {
+    format!("key:{}", i)
+}
+
+fn value(t: usize, i: usize) -> Value {
+    Value::String(format!("t{}-{}", t, i))
+}
+
+/// A fresh handle switched to `branch`.
+fn handle_on(db: &Strata, branch: &str) -> Strata {
+    let mut handle = db.new_handle().unwrap();
+    handle.set_branch(branch).unwrap();
+    handle
+}
+
+// =============================================================================
+// Per-thread branches
+// =============================================================================
+
+/// Spawn `THREADS` writers, each on its own branch, all writing at once.
+fn write_from_all_threads(db: &Strata) {
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let mut handle = db.new_handle().unwrap();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let branch = branch_name(t);
+                handle.create_branch(&branch).unwrap();
+                handle.set_branch(&branch).unwrap();
+                // Every handle has switched before anyone writes
+                barrier.wait();
+
+                for i in 0..KEYS_PER_THREAD {
+                    handle.kv_put(&key(i), value(t, i)).unwrap();
+                    assert_eq!(
+                        handle.current_branch(),
+                        branch,
+                        "thread {}: branch changed under it after {} writes",
+                        t,
+                        i + 1
+                    );
+                }
+                handle.state_set("owner", Value::Int(t as i64)).unwrap();
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn concurrent_branch_writes_stay_on_their_branch() {
+    let db = db();
+    write_from_all_threads(&db);
+
+    for t in 0..THREADS {
+        let handle = handle_on(&db, &branch_name(t));
+        assert_eq!(
+            handle.kv_list(None).unwrap().len(),
+            KEYS_PER_THREAD,
+            "{} should hold exactly its own keys",
+            branch_name(t)
+        );
+        for i in 0..KEYS_PER_THREAD {
+            assert_eq!(
+                handle.kv_get(&key(i)).unwrap(),
+                Some(value(t, i)),
+                "{}: {} leaked from another branch",
+                branch_name(t),
+                key(i)
+            );
+        }
+        assert_eq!(handle.state_read("owner").unwrap(), Some(Value::Int(t as i64)));
+    }
+}
+
+#[test]
+fn concurrent_branch_writes_do_not_touch_default() {
+    let db = db();
+    write_from_all_threads(&db);
+
+    assert_eq!(db.current_branch(), "default", "spawning handles moved the parent handle");
+    assert!(db.kv_list(None).unwrap().is_empty(), "branch writes leaked into default");
+    assert_eq!(db.state_read("owner").unwrap(), None);
+    for t in 0..THREADS {
+        assert!(db.branch_exists(&branch_name(t)).unwrap());
+    }
+}
+
+// =============================================================================
+// Independent branch selection
+// =============================================================================
+
+#[test]
+fn switching_one_handle_does_not_move_another() {
+    let db = db();
+    db.create_branch("other").unwrap();
+    let mut a = db.new_handle().unwrap();
+    let b = db.new_handle().unwrap();
+
+    a.set_branch("other").unwrap();
+    assert_eq!(a.current_branch(), "other");
+    assert_eq!(b.current_branch(), "default");
+    assert_eq!(db.current_branch(), "default");
+
+    a.kv_put("k", "on-other").unwrap();
+    b.kv_put("k", "on-default").unwrap();
+    assert_eq!(a.kv_get("k").unwrap(), Some(Value::String("on-other".into())));
+    assert_eq!(b.kv_get("k").unwrap(), Some(Value::String("on-default".into())));
+}
+
+#[test]
+fn concurrent_branch_switching_keeps_selection_per_handle() {
+    // Every thread flips between the shared "default" branch and its own,
+    // checking after each switch that reads follow its own selection only
+    let db = db();
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let mut handle = db.new_handle().unwrap();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let branch = branch_name(t);
+                handle.create_branch(&branch).unwrap();
+                handle.set_branch(&branch).unwrap();
+                handle.kv_put("mine", Value::Int(t as i64)).unwrap();
+                barrier.wait();
+
+                for n in 0..SWITCHES {
+                    let target = if n % 2 == 0 { "default" } else { branch.as_str() };
+                    handle.set_branch(target).unwrap();
+                    assert_eq!(handle.current_branch(), target, "thread {} switch {}", t, n);
+
+                    let expected = (target != "default").then_some(Value::Int(t as i64));
+                    assert_eq!(
+                        handle.kv_get("mine").unwrap(),
+                        expected,
+                        "thread {} on {} after {} switches",
+                        t,
+                        target,
+                        n + 1
+                    );
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+//! Black-box tests for read correctness across rapid branch switches.
+//!
+//! After `set_branch(b)`, every read must reflect branch `b`'s data — never a
+//! value carried over from the previously active branch.
+
+use stratadb::{Strata, Value};
+
+const SWITCHES: usize = 1_000;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+/// Create `names` and write `key = <branch name>` on each.
+fn setup_branches(db: &mut Strata, names: &[&str], key: &str) {
+    for name in names {
+        if *name != "default" {
+            db.create_branch(name).unwrap();
+        }
+        db.set_branch(name).unwrap();
+        db.kv_put(key, Value::String(name.to_string())).unwrap();
+    }
+}
+
+// =============================================================================
+// Two-branch alternation
+// =============================================================================
+
+#[test]
+fn alternating_switch_reads_active_branch() {
+    let mut db = db();
+    setup_branches(&mut db, &["branch-a", "branch-b"], "shared");
+
+    for i in 0..SWITCHES {
+        let branch = if i % 2 == 0 { "branch-a" } else { "branch-b" };
+        db.set_branch(branch).unwrap();
+        assert_eq!(db.current_branch(), branch);
+        assert_eq!(
+            db.kv_get("shared").unwrap(),
+            Some(Value::String(branch.to_string())),
+            "switch {}: read on {} returned another branch's value",
+            i,
+            branch
+        );
+    }
+}
+
+#[test]
+fn switch_to_same_branch_is_stable() {
+    let mut db = db();
+    setup_branches(&mut db, &["branch-a", "branch-b"], "shared");
+
+    db.set_branch("branch-b").unwrap();
+    for _ in 0..100 {
+        db.set_branch("branch-b").unwrap();
+        assert_eq!(db.kv_get("shared").unwrap(), Some(Value::String("branch-b".into())));
+    }
+}
+
+#[test]
+fn default_branch_participates_in_switching() {
+    let mut db = db();
+    setup_branches(&mut db, &["default", "feature"], "shared");
+
+    for i in 0..SWITCHES {
+        let branch = if i % 2 == 0 { "default" } else { "feature" };
+        db.set_branch(branch).unwrap();
+        assert_eq!(db.kv_get("shared").unwrap(), Some(Value::String(branch.to_string())));
+    }
+}
+
+// =============================================================================
+// Keys present on only one branch
+// =============================================================================
+
+#[test]
+fn key_absent_on_active_branch_reads_none() {
+    let mut db = db();
+    db.create_branch("branch-a").unwrap();
+    db.create_branch("branch-b").unwrap();
+    db.set_branch("branch-a").unwrap();
+    db.kv_put("only-a", "a").unwrap();
+
+    for i in 0..SWITCHES {
+        let on_a = i % 2 == 0;
+        db.set_branch(if on_a { "branch-a" } else { "branch-b" }).unwrap();
+        let got = db.kv_get("only-a").unwrap();
+        if on_a {
+            assert_eq!(got, Some(Value::String("a".into())));
+        } else {
+            assert_eq!(got, None, "switch {}: branch-b saw branch-a's key", i);
+        }
+    }
+}
+
+#[test]
+fn listing_reflects_active_branch() {
+    let mut db = db();
+    db.create_branch("branch-a").unwrap();
+    db.create_branch("branch-b").unwrap();
+    db.set_branch("branch-a").unwrap();
+    db.kv_put("item:a", "a").unwrap();
+    db.set_branch("branch-b").unwrap();
+    db.kv_put("item:b", "b").unwrap();
+
+    for i in 0..100 {
+        let (branch, expected) = if i % 2 == 0 {
+            ("branch-a", "item:a")
+        } else {
+            ("branch-b", "item:b")
+        };
+        db.set_branch(branch).unwrap();
+        assert_eq!(db.kv_list(Some("item:")).unwrap(), vec![expected.to_string()]);
+    }
+}
+
+// =============================================================================
+// Writes interleaved with switches
+// =============================================================================
+
+#[test]
+fn writes_between_switches_stay_on_their_branch() {
+    let mut db = db();
+    setup_branches(&mut db, &["branch-a", "branch-b"], "counter");
+
+    let mut expected_a = 0i64;
+    let mut expected_b = 0i64;
+    for i in 0..SWITCHES {
+        if i % 2 == 0 {
+            db.set_branch("branch-a").unwrap();
+            expected_a += 1;
+            db.kv_put("counter", Value::Int(expected_a)).unwrap();
+        } else {
+            db.set_branch("branch-b").unwrap();
+            expected_b -= 1;
+            db.kv_put("counter", Value::Int(expected_b)).unwrap();
+        }
+    }
+
+    db.set_branch("branch-a").unwrap();
+    assert_eq!(db.kv_get("counter").unwrap(), Some(Value::Int(expected_a)));
+    db.set_branch("branch-b").unwrap();
+    assert_eq!(db.kv_get("counter").unwrap(), Some(Value::Int(expected_b)));
+}
+
+#[test]
+fn round_robin_across_many_branches() {
+    let names: Vec<String> = (0..8).map(|i| format!("rr-{}", i)).collect();
+    let refs: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    let mut db = db();
+    setup_branches(&mut db, &refs, "shared");
+
+    for i in 0..SWITCHES {
+        let branch = refs[i % refs.len()];
+        db.set_branch(branch).unwrap();
+        assert_eq!(db.kv_get("shared").unwrap(), Some(Value::String(branch.to_string())));
+    }
+}
+
+#[test]
+fn other_primitives_follow_switch() {
+    let mut db = db();
+    db.create_branch("branch-a").unwrap();
+    db.create_branch("branch-b").unwrap();
+    for branch in ["branch-a", "branch-b"] {
+        db.set_branch(branch).unwrap();
+        db.state_set("cell", branch).unwrap();
+    }
+
+    for i in 0..100 {
+        let branch = if i % 2 == 0 { "branch-a" } else { "branch-b" };
+        db.set_branch(branch).unwrap();
+        assert_eq!(db.state_read("cell").unwrap(), Some(Value::String(branch.to_string())));
+    }
+}
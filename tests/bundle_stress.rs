@@ -0,0 +1,163 @@
+//! Stress test for large bundles.
+//!
+//! Exports a branch with ~1M KV entries, validates the bundle from a fresh
+//! database and imports it there, checking that `checksums_valid` holds and
+//! that `entry_count` round-trips through validate and a re-export. A
+//! sampler thread tracks resident memory during validation, so a validator
+//! that decompresses the whole bundle into memory fails the test.
+//!
+//! Takes minutes and a few hundred MB of disk, so it is ignored by default:
+//! `cargo test --release --test bundle_stress -- --ignored --nocapture`
+
+#[allow(unused)]
+#[path = "../benches/harness/mod.rs"]
+mod harness;
+
+use harness::metrics::current_rss_bytes;
+use harness::scaling::fmt_duration;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use stratadb::{Strata, Value};
+
+const ENTRIES: usize = 1_000_000;
+const BRANCH: &str = "bundle-stress";
+
+/// Bytes per KV value. Each value is distinct so the bundle cannot compress
+/// down to nothing.
+const VALUE_BYTES: usize = 100;
+
+/// Allowed RSS growth while validating, as a fraction of the logical data
+/// in the bundle. Holding the decompressed bundle would take all of it.
+const VALIDATE_RSS_FRACTION: f64 = 0.25;
+
+fn disk_db(path: &Path) -> Strata {
+    Strata::open(path).expect("failed to open disk db")
+}
+
+fn key(i: usize) -> String {
+    format!("stress:{:012}", i)
+}
+
+fn value(i: usize) -> Value {
+    Value::String(format!("{:0>width$}", i, width = VALUE_BYTES))
+}
+
+/// `current_rss_bytes`, or `None` where it cannot be read (it reports 0).
+fn rss_bytes() -> Option<u64> {
+    Some(current_rss_bytes()).filter(|&rss| rss > 0)
+}
+
+/// Run `f` while a background thread samples RSS every millisecond.
+/// Returns `f`'s result and the peak RSS seen (`None` without procfs).
+fn with_peak_rss<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let done = Arc::new(AtomicBool::new(false));
+    let peak = Arc::new(AtomicU64::new(rss_bytes().unwrap_or(0)));
+    let sampler = {
+        let (done, peak) = (done.clone(), peak.clone());
+        std::thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                if let Some(rss) = rss_bytes() {
+                    peak.fetch_max(rss, Ordering::Relaxed);
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        })
+    };
+    let result = f();
+    done.store(true, Ordering::Relaxed);
+    sampler.join().unwrap();
+    let peak = rss_bytes().map(|rss| peak.load(Ordering::Relaxed).max(rss));
+    (result, peak)
+}
+
+fn mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+#[test]
+#[ignore = "writes ~1M entries; run explicitly with --ignored"]
+fn million_entry_bundle_validates_and_roundtrips() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle_path = dir.path().join(format!("{}.runbundle.tar.zst", BRANCH));
+    let bundle = bundle_path.to_str().unwrap();
+
+    // Source: populate and export, then drop so its memory is released
+    // before validation is measured
+    let (export, logical_bytes) = {
+        let mut db = disk_db(&dir.path().join("source"));
+        db.create_branch(BRANCH).unwrap();
+        db.set_branch(BRANCH).unwrap();
+        let mut logical_bytes = 0u64;
+        for i in 0..ENTRIES {
+            let k = key(i);
+            logical_bytes += (k.len() + VALUE_BYTES) as u64;
+            db.kv_put(&k, value(i)).unwrap();
+        }
+
+        let start = Instant::now();
+        let export = db.branch_export(BRANCH, bundle).unwrap();
+        let elapsed = start.elapsed();
+        eprintln!(
+            "  export:   {:>8}  {:>8.1} MB/s  bundle {:.1} MB ({:.1} MB logical)",
+            fmt_duration(elapsed),
+            mb(logical_bytes) / elapsed.as_secs_f64(),
+            mb(export.bundle_size),
+            mb(logical_bytes)
+        );
+        (export, logical_bytes)
+    };
+
+    // Validate from a fresh database that has never seen the data
+    let mut target = disk_db(&dir.path().join("target"));
+    let rss_before = rss_bytes();
+    let start = Instant::now();
+    let (validate, peak) = with_peak_rss(|| target.branch_validate_bundle(bundle).unwrap());
+    let elapsed = start.elapsed();
+    eprintln!(
+        "  validate: {:>8}  {:>8.1} MB/s",
+        fmt_duration(elapsed),
+        mb(logical_bytes) / elapsed.as_secs_f64()
+    );
+
+    assert!(validate.checksums_valid, "1M-entry bundle failed checksum validation");
+    assert_eq!(validate.branch_id, BRANCH);
+    assert_eq!(validate.entry_count, export.entry_count, "validate disagrees with export");
+
+    if let (Some(before), Some(peak)) = (rss_before, peak) {
+        let growth = peak.saturating_sub(before);
+        let limit = (logical_bytes as f64 * VALIDATE_RSS_FRACTION) as u64;
+        eprintln!("  validate RSS growth: {:.1} MB (limit {:.1} MB)", mb(growth), mb(limit));
+        assert!(
+            growth <= limit,
+            "validation grew RSS by {:.1} MB for {:.1} MB of logical data; \
+             it looks like the whole bundle is loaded into memory",
+            mb(growth),
+            mb(logical_bytes)
+        );
+    }
+
+    // Import, spot-check values, and re-export: entry_count must round-trip
+    let start = Instant::now();
+    let import = target.branch_import(bundle).unwrap();
+    let elapsed = start.elapsed();
+    eprintln!(
+        "  import:   {:>8}  {:>8.1} MB/s",
+        fmt_duration(elapsed),
+        mb(logical_bytes) / elapsed.as_secs_f64()
+    );
+    assert_eq!(import.branch_id, BRANCH);
+
+    target.set_branch(BRANCH).unwrap();
+    for i in [0, 1, ENTRIES / 2, ENTRIES - 1] {
+        assert_eq!(target.kv_get(&key(i)).unwrap(), Some(value(i)), "key {} after import", i);
+    }
+
+    let reexport_path = dir.path().join("reexport.runbundle.tar.zst");
+    let reexport = target.branch_export(BRANCH, reexport_path.to_str().unwrap()).unwrap();
+    assert_eq!(
+        reexport.entry_count, export.entry_count,
+        "entry_count changed across export -> import -> export"
+    );
+}
@@ -0,0 +1,153 @@
+//! Black-box tests for bundle integrity checking.
+//!
+//! Exports a valid bundle, corrupts it on disk, and checks that
+//! `branch_validate_bundle` reports the damage (invalid checksums or an
+//! error) and that `branch_import` refuses to load it.
+
+use std::path::{Path, PathBuf};
+use stratadb::{Strata, Value};
+
+fn disk_db(path: &Path) -> Strata {
+    Strata::open(path).expect("failed to open disk db")
+}
+
+/// Export a branch with enough data that the bundle has a real payload.
+fn export_bundle(dir: &Path, branch: &str) -> PathBuf {
+    let mut db = disk_db(&dir.join("source"));
+    db.create_branch(branch).unwrap();
+    db.set_branch(branch).unwrap();
+    for i in 0..200 {
+        db.kv_put(&format!("key:{}", i), Value::String(format!("value-{:0>64}", i)))
+            .unwrap();
+    }
+    db.state_set("cell", 7i64).unwrap();
+
+    let bundle_path = dir.join(format!("{}.runbundle.tar.zst", branch));
+    db.branch_export(branch, bundle_path.to_str().unwrap()).unwrap();
+
+    let validate = db.branch_validate_bundle(bundle_path.to_str().unwrap()).unwrap();
+    assert!(validate.checksums_valid, "freshly exported bundle should validate");
+    bundle_path
+}
+
+/// Flip every bit of `len` bytes starting at the middle of the file.
+fn flip_middle_bytes(path: &Path, len: usize) {
+    let mut bytes = std::fs::read(path).unwrap();
+    assert!(bytes.len() > len * 2, "bundle too small to tamper with");
+    let start = bytes.len() / 2;
+    for b in &mut bytes[start..start + len] {
+        *b ^= 0xFF;
+    }
+    std::fs::write(path, bytes).unwrap();
+}
+
+/// Cut the file to half its length.
+fn truncate_half(path: &Path) {
+    let bytes = std::fs::read(path).unwrap();
+    std::fs::write(path, &bytes[..bytes.len() / 2]).unwrap();
+}
+
+/// Validation must not report a tampered bundle as valid.
+fn assert_validation_fails(db: &Strata, path: &Path) {
+    // Rejecting the bundle outright with an error is also acceptable
+    if let Ok(result) = db.branch_validate_bundle(path.to_str().unwrap()) {
+        assert!(!result.checksums_valid, "tampered bundle passed checksum validation");
+    }
+}
+
+/// Import must fail and must not leave the branch behind.
+fn assert_import_refused(dir: &Path, path: &Path, branch: &str) {
+    let target = disk_db(&dir.join("target"));
+    let result = target.branch_import(path.to_str().unwrap());
+    assert!(result.is_err(), "tampered bundle was imported");
+    assert!(
+        !target.list_branches().unwrap().contains(&branch.to_string()),
+        "failed import left branch '{}' behind",
+        branch
+    );
+}
+
+// =============================================================================
+// Flipped bytes
+// =============================================================================
+
+#[test]
+fn flipped_bytes_fail_validation() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "flipped");
+    flip_middle_bytes(&bundle, 16);
+
+    let db = disk_db(&dir.path().join("checker"));
+    assert_validation_fails(&db, &bundle);
+}
+
+#[test]
+fn single_flipped_byte_fails_validation() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "one-byte");
+    flip_middle_bytes(&bundle, 1);
+
+    let db = disk_db(&dir.path().join("checker"));
+    assert_validation_fails(&db, &bundle);
+}
+
+#[test]
+fn flipped_bytes_refuse_import() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "flipped-import");
+    flip_middle_bytes(&bundle, 16);
+
+    assert_import_refused(dir.path(), &bundle, "flipped-import");
+}
+
+// =============================================================================
+// Truncation
+// =============================================================================
+
+#[test]
+fn truncated_bundle_fails_validation() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "truncated");
+    truncate_half(&bundle);
+
+    let db = disk_db(&dir.path().join("checker"));
+    assert_validation_fails(&db, &bundle);
+}
+
+#[test]
+fn truncated_bundle_refuses_import() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "truncated-import");
+    truncate_half(&bundle);
+
+    assert_import_refused(dir.path(), &bundle, "truncated-import");
+}
+
+#[test]
+fn empty_bundle_file_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = dir.path().join("empty.runbundle.tar.zst");
+    std::fs::write(&bundle, b"").unwrap();
+
+    let db = disk_db(&dir.path().join("checker"));
+    assert_validation_fails(&db, &bundle);
+    assert!(db.branch_import(bundle.to_str().unwrap()).is_err());
+}
+
+// =============================================================================
+// Untampered control
+// =============================================================================
+
+#[test]
+fn untampered_bundle_still_imports() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = export_bundle(dir.path(), "control");
+
+    let mut target = disk_db(&dir.path().join("target"));
+    target.branch_import(bundle.to_str().unwrap()).unwrap();
+    target.set_branch("control").unwrap();
+    assert_eq!(
+        target.kv_get("key:0").unwrap(),
+        Some(Value::String(format!("value-{:0>64}", 0)))
+    );
+}
@@ -0,0 +1,151 @@
+//! Black-box tests for the state CAS counter contract.
+//!
+//! The counter returned by a successful `state_cas` (or by `state_set`) is the
+//! exact value the next `state_cas` must pass as `expected` to succeed. Any
+//! other value — stale, off by one, or never issued — must fail and leave the
+//! cell untouched. `dataset_state.rs` and the CAS benchmarks chain counters
+//! this way; these tests pin it down explicitly.
+
+use stratadb::{Strata, Value};
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+/// Counters near `counter` that must never be accepted in its place.
+fn wrong_counters(counter: u64) -> Vec<u64> {
+    let mut wrong = vec![counter.wrapping_sub(1), counter.wrapping_add(1), counter + 1000, 0];
+    wrong.retain(|&c| c != counter);
+    wrong
+}
+
+/// Assert every wrong counter fails and leaves `expected_value` in place.
+fn assert_only_counter_accepted(db: &Strata, cell: &str, counter: u64, expected_value: &Value) {
+    for wrong in wrong_counters(counter) {
+        let result = db.state_cas(cell, Some(wrong), "wrong").unwrap();
+        assert!(
+            result.is_none(),
+            "CAS with counter {} succeeded but current counter is {}",
+            wrong,
+            counter
+        );
+        assert_eq!(
+            db.state_read(cell).unwrap().as_ref(),
+            Some(expected_value),
+            "failed CAS with counter {} modified the cell",
+            wrong
+        );
+    }
+}
+
+// =============================================================================
+// Counter returned by state_cas
+// =============================================================================
+
+#[test]
+fn cas_counter_is_next_expected_value() {
+    let db = db();
+    let mut counter = db.state_cas("cell", None, Value::Int(0)).unwrap().unwrap();
+
+    for i in 1..=50i64 {
+        let expected = Value::Int(i - 1);
+        assert_only_counter_accepted(&db, "cell", counter, &expected);
+
+        counter = db
+            .state_cas("cell", Some(counter), Value::Int(i))
+            .unwrap()
+            .unwrap_or_else(|| panic!("CAS with returned counter failed at step {}", i));
+        assert_eq!(db.state_read("cell").unwrap(), Some(Value::Int(i)));
+    }
+}
+
+#[test]
+fn cas_counter_is_consumed_by_use() {
+    let db = db();
+    let c1 = db.state_cas("cell", None, "a").unwrap().unwrap();
+    let c2 = db.state_cas("cell", Some(c1), "b").unwrap().unwrap();
+
+    assert_ne!(c1, c2, "successful CAS returned the counter it consumed");
+    assert!(db.state_cas("cell", Some(c1), "stale").unwrap().is_none());
+    assert_eq!(db.state_read("cell").unwrap(), Some(Value::String("b".into())));
+}
+
+#[test]
+fn failed_cas_does_not_advance_counter() {
+    let db = db();
+    let counter = db.state_cas("cell", None, "a").unwrap().unwrap();
+
+    for _ in 0..10 {
+        assert!(db.state_cas("cell", Some(counter + 1), "wrong").unwrap().is_none());
+    }
+    let next = db.state_cas("cell", Some(counter), "b").unwrap();
+    assert!(next.is_some(), "failed CAS attempts invalidated the current counter");
+}
+
+#[test]
+fn cas_none_fails_once_cell_exists() {
+    let db = db();
+    db.state_cas("cell", None, "first").unwrap().unwrap();
+    assert!(db.state_cas("cell", None, "second").unwrap().is_none());
+    assert_eq!(db.state_read("cell").unwrap(), Some(Value::String("first".into())));
+}
+
+// =============================================================================
+// Counter returned by state_set
+// =============================================================================
+
+#[test]
+fn set_counter_is_usable_as_cas_expected() {
+    let db = db();
+    let counter = db.state_set("cell", Value::Int(1)).unwrap();
+
+    assert_only_counter_accepted(&db, "cell", counter, &Value::Int(1));
+    let next = db.state_cas("cell", Some(counter), Value::Int(2)).unwrap();
+    assert!(next.is_some(), "CAS with counter from state_set failed");
+    assert_eq!(db.state_read("cell").unwrap(), Some(Value::Int(2)));
+}
+
+#[test]
+fn set_invalidates_earlier_cas_counter() {
+    let db = db();
+    let cas_counter = db.state_cas("cell", None, "a").unwrap().unwrap();
+    let set_counter = db.state_set("cell", "b").unwrap();
+
+    assert!(db.state_cas("cell", Some(cas_counter), "stale").unwrap().is_none());
+    assert!(db.state_cas("cell", Some(set_counter), "c").unwrap().is_some());
+    assert_eq!(db.state_read("cell").unwrap(), Some(Value::String("c".into())));
+}
+
+#[test]
+fn set_and_cas_counters_chain_interchangeably() {
+    let db = db();
+    let mut counter = db.state_set("cell", Value::Int(0)).unwrap();
+
+    for i in 1..=20i64 {
+        counter = if i % 2 == 0 {
+            db.state_set("cell", Value::Int(i)).unwrap()
+        } else {
+            db.state_cas("cell", Some(counter), Value::Int(i)).unwrap().unwrap()
+        };
+    }
+    assert_only_counter_accepted(&db, "cell", counter, &Value::Int(20));
+    assert!(db.state_cas("cell", Some(counter), Value::Int(21)).unwrap().is_some());
+}
+
+// =============================================================================
+// Counters are per cell
+// =============================================================================
+
+#[test]
+fn counter_from_one_cell_does_not_unlock_another() {
+    let db = db();
+    let a = db.state_set("a", "a0").unwrap();
+    let b = db.state_set("b", "b0").unwrap();
+
+    if a != b {
+        assert!(db.state_cas("b", Some(a), "wrong").unwrap().is_none());
+        assert_eq!(db.state_read("b").unwrap(), Some(Value::String("b0".into())));
+    }
+    assert!(db.state_cas("a", Some(a), "a1").unwrap().is_some());
+    assert!(db.state_cas("b", Some(b), "b1").unwrap().is_some());
+}
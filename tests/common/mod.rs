@@ -1,8 +1,10 @@
 //! Shared test utilities for loading JSONL dataset fixtures.
 
+#![allow(dead_code)] // each test binary uses only some loaders and fields
+
 use std::collections::HashMap;
 use std::io::BufRead;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 use stratadb::{DistanceMetric, Strata, Value};
@@ -27,6 +29,8 @@ pub struct KvDataset {
 }
 
 pub struct KvEntry {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub value: JsonValue,
 }
@@ -56,11 +60,15 @@ pub struct StateDataset {
 }
 
 pub struct StateCell {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub cell: String,
     pub value: JsonValue,
 }
 
 pub struct CasSequence {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub cell: String,
     pub steps: Vec<CasStep>,
 }
@@ -71,6 +79,8 @@ pub struct CasStep {
 }
 
 pub struct CasConflict {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub cell: String,
     pub description: String,
     pub setup: JsonValue,
@@ -116,6 +126,8 @@ pub struct EventDataset {
 }
 
 pub struct EventEntry {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub event_type: String,
     pub payload: serde_json::Value,
 }
@@ -147,23 +159,31 @@ pub struct JsonDataset {
 }
 
 pub struct JsonDoc {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub doc: serde_json::Value,
 }
 
 pub struct PathQuery {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub path: String,
     pub expected: serde_json::Value,
 }
 
 pub struct PathMutation {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub path: String,
     pub new_value: serde_json::Value,
 }
 
 pub struct JsonDeletion {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub path: String,
 }
@@ -208,12 +228,16 @@ pub struct VectorCollection {
 }
 
 pub struct VectorEntry {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub key: String,
     pub embedding: Vec<f32>,
     pub metadata: Option<serde_json::Value>,
 }
 
 pub struct SearchQuery {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub collection: String,
     pub query: Vec<f32>,
     pub k: u64,
@@ -223,7 +247,7 @@ pub struct SearchQuery {
 
 #[derive(Deserialize)]
 #[serde(tag = "_s")]
-enum VectorRecord {
+pub enum VectorRecord {
     #[serde(rename = "collection")]
     Collection {
         name: String,
@@ -265,6 +289,8 @@ pub struct BranchData {
 }
 
 pub struct IsolationCheck {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub description: String,
     pub on_branch: String,
     pub key: Option<String>,
@@ -336,30 +362,40 @@ pub struct DirtyDataset {
 }
 
 pub struct DirtyKv {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub key: String,
     pub value: JsonValue,
 }
 
 pub struct DirtyState {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub cell: String,
     pub value: JsonValue,
 }
 
 pub struct DirtyEvent {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub event_type: String,
     pub payload: serde_json::Value,
 }
 
 pub struct DirtyJson {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub key: String,
     pub doc: serde_json::Value,
 }
 
 pub struct DirtyVector {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub collection: String,
     pub key: String,
@@ -367,6 +403,8 @@ pub struct DirtyVector {
 }
 
 pub struct DirtyCrossKvJson {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub key: String,
     pub kv_value: JsonValue,
@@ -374,6 +412,8 @@ pub struct DirtyCrossKvJson {
 }
 
 pub struct DirtyCrossBranch {
+    /// 1-based line of the record in its `.jsonl` fixture.
+    pub line: usize,
     pub desc: String,
     pub branch: String,
     pub key: String,
@@ -530,24 +570,66 @@ pub fn value_to_json(v: &Value) -> serde_json::Value {
 // JSONL reader helper
 // =============================================================================
 
-fn read_jsonl<T: serde::de::DeserializeOwned>(filename: &str) -> Vec<T> {
+/// Compressed variants tried, in order, when a plain `.jsonl` fixture is absent.
+const COMPRESSED_SUFFIXES: [&str; 2] = [".zst", ".gz"];
+
+/// Resolve `filename` in `data_dir()`, falling back to `filename.zst` then
+/// `filename.gz`, so a fixture can be swapped for a compressed one without
+/// touching its loader. Returns the name actually found, for error messages.
+fn resolve_fixture(filename: &str) -> (String, PathBuf) {
     let path = data_dir().join(filename);
-    let file = std::fs::File::open(&path).unwrap_or_else(|e| panic!("failed to open {}: {}", filename, e));
-    let reader = std::io::BufReader::new(file);
+    if path.exists() {
+        return (filename.to_string(), path);
+    }
+    for suffix in COMPRESSED_SUFFIXES {
+        let name = format!("{}{}", filename, suffix);
+        let path = data_dir().join(&name);
+        if path.exists() {
+            return (name, path);
+        }
+    }
+    (filename.to_string(), path)
+}
+
+/// Open a fixture as a line reader, decompressing `.zst` / `.gz` on the fly.
+fn open_fixture(filename: &str, path: &Path) -> Box<dyn BufRead> {
+    let file = std::fs::File::open(path).unwrap_or_else(|e| panic!("failed to open {}: {}", filename, e));
+    if filename.ends_with(".zst") {
+        let decoder = zstd::Decoder::new(file)
+            .unwrap_or_else(|e| panic!("{}: zstd decoder error: {}", filename, e));
+        Box::new(std::io::BufReader::new(decoder))
+    } else if filename.ends_with(".gz") {
+        Box::new(std::io::BufReader::new(flate2::read::GzDecoder::new(file)))
+    } else {
+        Box::new(std::io::BufReader::new(file))
+    }
+}
+
+/// Parse every non-blank line of a fixture, paired with its 1-based line
+/// number so loaders can record where each dataset item came from.
+fn read_jsonl<T: serde::de::DeserializeOwned>(filename: &str) -> Vec<(usize, T)> {
+    stream_jsonl(filename).collect()
+}
+
+/// Lazy `read_jsonl`: parses one line per `next()`, so only the current
+/// record is held in memory.
+fn stream_jsonl<T: serde::de::DeserializeOwned>(filename: &str) -> impl Iterator<Item = (usize, T)> {
+    let (filename, path) = resolve_fixture(filename);
+    let reader = open_fixture(&filename, &path);
     reader
         .lines()
         .enumerate()
-        .filter_map(|(line_num, line)| {
+        .filter_map(move |(line_num, line)| {
             let line = line.unwrap_or_else(|e| panic!("{}:{}: read error: {}", filename, line_num + 1, e));
             let trimmed = line.trim();
             if trimmed.is_empty() {
                 return None;
             }
-            Some(serde_json::from_str(trimmed).unwrap_or_else(|e| {
+            let record = serde_json::from_str(trimmed).unwrap_or_else(|e| {
                 panic!("{}:{}: parse error: {}\n  line: {}", filename, line_num + 1, e, trimmed)
-            }))
+            });
+            Some((line_num + 1, record))
         })
-        .collect()
 }
 
 // =============================================================================
@@ -555,35 +637,35 @@ fn read_jsonl<T: serde::de::DeserializeOwned>(filename: &str) -> Vec<T> {
 // =============================================================================
 
 pub fn load_kv_dataset() -> KvDataset {
-    let records: Vec<KvRecord> = read_jsonl("kv.jsonl");
+    let records: Vec<(usize, KvRecord)> = read_jsonl("kv.jsonl");
     let mut ds = KvDataset {
         entries: Vec::new(),
         prefixes: HashMap::new(),
         deletions: Vec::new(),
         overwrites: Vec::new(),
     };
-    for r in records {
+    for (line, r) in records {
         match r {
-            KvRecord::Entry { key, value } => ds.entries.push(KvEntry { key, value }),
+            KvRecord::Entry { key, value } => ds.entries.push(KvEntry { line, key, value }),
             KvRecord::Prefix { prefix, count } => { ds.prefixes.insert(prefix, count); }
             KvRecord::Deletion { key } => ds.deletions.push(key),
-            KvRecord::Overwrite { key, value } => ds.overwrites.push(KvEntry { key, value }),
+            KvRecord::Overwrite { key, value } => ds.overwrites.push(KvEntry { line, key, value }),
         }
     }
     ds
 }
 
 pub fn load_state_dataset() -> StateDataset {
-    let records: Vec<StateRecord> = read_jsonl("state.jsonl");
+    let records: Vec<(usize, StateRecord)> = read_jsonl("state.jsonl");
     let mut ds = StateDataset {
         cells: Vec::new(),
         cas_sequences: Vec::new(),
         cas_conflicts: Vec::new(),
         init_cells: Vec::new(),
     };
-    for r in records {
+    for (line, r) in records {
         match r {
-            StateRecord::Cell { cell, value } => ds.cells.push(StateCell { cell, value }),
+            StateRecord::Cell { cell, value } => ds.cells.push(StateCell { line, cell, value }),
             StateRecord::CasSequence { cell, steps } => {
                 let steps = steps
                     .into_iter()
@@ -592,30 +674,30 @@ pub fn load_state_dataset() -> StateDataset {
                         new_value: s.new_value,
                     })
                     .collect();
-                ds.cas_sequences.push(CasSequence { cell, steps });
+                ds.cas_sequences.push(CasSequence { line, cell, steps });
             }
             StateRecord::CasConflict {
                 cell, description, setup, agent_1, agent_2, expected_winner,
             } => ds.cas_conflicts.push(CasConflict {
-                cell, description, setup, agent_1, agent_2, expected_winner,
+                line, cell, description, setup, agent_1, agent_2, expected_winner,
             }),
-            StateRecord::InitCell { cell, value } => ds.init_cells.push(StateCell { cell, value }),
+            StateRecord::InitCell { cell, value } => ds.init_cells.push(StateCell { line, cell, value }),
         }
     }
     ds
 }
 
 pub fn load_event_dataset() -> EventDataset {
-    let records: Vec<EventRecord> = read_jsonl("events.jsonl");
+    let records: Vec<(usize, EventRecord)> = read_jsonl("events.jsonl");
     let mut ds = EventDataset {
         events: Vec::new(),
         expected_counts: HashMap::new(),
         total: 0,
     };
-    for r in records {
+    for (line, r) in records {
         match r {
             EventRecord::Event { event_type, payload } => {
-                ds.events.push(EventEntry { event_type, payload });
+                ds.events.push(EventEntry { line, event_type, payload });
             }
             EventRecord::ExpectedCount { event_type, count } => {
                 ds.expected_counts.insert(event_type, count);
@@ -627,7 +709,7 @@ pub fn load_event_dataset() -> EventDataset {
 }
 
 pub fn load_json_dataset() -> JsonDataset {
-    let records: Vec<JsonRecord> = read_jsonl("json_docs.jsonl");
+    let records: Vec<(usize, JsonRecord)> = read_jsonl("json_docs.jsonl");
     let mut ds = JsonDataset {
         documents: Vec::new(),
         path_queries: Vec::new(),
@@ -635,16 +717,16 @@ pub fn load_json_dataset() -> JsonDataset {
         deletions: Vec::new(),
         prefixes: HashMap::new(),
     };
-    for r in records {
+    for (line, r) in records {
         match r {
-            JsonRecord::Document { key, doc } => ds.documents.push(JsonDoc { key, doc }),
+            JsonRecord::Document { key, doc } => ds.documents.push(JsonDoc { line, key, doc }),
             JsonRecord::PathQuery { key, path, expected } => {
-                ds.path_queries.push(PathQuery { key, path, expected });
+                ds.path_queries.push(PathQuery { line, key, path, expected });
             }
             JsonRecord::Mutation { key, path, new_value } => {
-                ds.mutations.push(PathMutation { key, path, new_value });
+                ds.mutations.push(PathMutation { line, key, path, new_value });
             }
-            JsonRecord::Deletion { key, path } => ds.deletions.push(JsonDeletion { key, path }),
+            JsonRecord::Deletion { key, path } => ds.deletions.push(JsonDeletion { line, key, path }),
             JsonRecord::Prefix { prefix, count } => { ds.prefixes.insert(prefix, count); }
         }
     }
@@ -652,11 +734,11 @@ pub fn load_json_dataset() -> JsonDataset {
 }
 
 pub fn load_vector_dataset() -> VectorDataset {
-    let records: Vec<VectorRecord> = read_jsonl("vectors.jsonl");
+    let records: Vec<(usize, VectorRecord)> = read_jsonl("vectors.jsonl");
     let mut collections: HashMap<String, VectorCollection> = HashMap::new();
     let mut search_queries = Vec::new();
 
-    for r in records {
+    for (line, r) in records {
         match r {
             VectorRecord::Collection { name, dimension, metric } => {
                 collections.insert(
@@ -669,10 +751,10 @@ pub fn load_vector_dataset() -> VectorDataset {
                     .get_mut(&collection)
                     .unwrap_or_else(|| panic!("vector references unknown collection: {}", collection))
                     .vectors
-                    .push(VectorEntry { key, embedding, metadata });
+                    .push(VectorEntry { line, key, embedding, metadata });
             }
             VectorRecord::SearchQuery { collection, query, k, description, expected_top } => {
-                search_queries.push(SearchQuery { collection, query, k, description, expected_top });
+                search_queries.push(SearchQuery { line, collection, query, k, description, expected_top });
             }
         }
     }
@@ -684,14 +766,61 @@ pub fn load_vector_dataset() -> VectorDataset {
     VectorDataset { collections: colls, search_queries }
 }
 
+/// Vector fixture records, parsed lazily with their line numbers. For corpora
+/// too large for `load_vector_dataset` to hold in memory.
+pub fn stream_vector_records() -> impl Iterator<Item = (usize, VectorRecord)> {
+    stream_jsonl("vectors.jsonl")
+}
+
+/// What `load_vector_dataset_streaming` loaded. Only the search queries are
+/// kept; vectors go straight into the database.
+pub struct StreamedVectorDataset {
+    /// `(name, vector count)` per collection, in fixture order.
+    pub collections: Vec<(String, usize)>,
+    pub search_queries: Vec<SearchQuery>,
+}
+
+/// Create each collection and upsert each vector into `db` as the fixture is
+/// read, without holding the corpus in memory.
+pub fn load_vector_dataset_streaming(db: &Strata) -> StreamedVectorDataset {
+    let mut collections: Vec<(String, usize)> = Vec::new();
+    let mut search_queries = Vec::new();
+
+    for (line, r) in stream_vector_records() {
+        match r {
+            VectorRecord::Collection { name, dimension, metric } => {
+                db.vector_create_collection(&name, dimension, parse_metric(&metric))
+                    .unwrap_or_else(|e| panic!("vectors.jsonl:{}: create {}: {}", line, name, e));
+                collections.push((name, 0));
+            }
+            VectorRecord::Vector { collection, key, embedding, metadata } => {
+                let count = collections
+                    .iter_mut()
+                    .find(|(name, _)| *name == collection)
+                    .map(|(_, count)| count)
+                    .unwrap_or_else(|| panic!("vector references unknown collection: {}", collection));
+                let meta = metadata.as_ref().map(json_to_value);
+                db.vector_upsert(&collection, &key, embedding, meta)
+                    .unwrap_or_else(|e| panic!("vectors.jsonl:{}: upsert {}: {}", line, key, e));
+                *count += 1;
+            }
+            VectorRecord::SearchQuery { collection, query, k, description, expected_top } => {
+                search_queries.push(SearchQuery { line, collection, query, k, description, expected_top });
+            }
+        }
+    }
+
+    StreamedVectorDataset { collections, search_queries }
+}
+
 pub fn load_branch_dataset() -> BranchDataset {
-    let records: Vec<BranchRecord> = read_jsonl("branches.jsonl");
+    let records: Vec<(usize, BranchRecord)> = read_jsonl("branches.jsonl");
     let mut branches = Vec::new();
     let mut per_branch_data: HashMap<String, BranchData> = HashMap::new();
     let mut isolation_checks = Vec::new();
     let mut cross_branch_comparison = None;
 
-    for r in records {
+    for (line, r) in records {
         match r {
             BranchRecord::Branch { name } => {
                 branches.push(name.clone());
@@ -706,27 +835,27 @@ pub fn load_branch_dataset() -> BranchDataset {
                     .entry(branch)
                     .or_insert_with(|| BranchData { kv: Vec::new(), state: Vec::new(), events: Vec::new() })
                     .kv
-                    .push(KvEntry { key, value });
+                    .push(KvEntry { line, key, value });
             }
             BranchRecord::BranchState { branch, cell, value } => {
                 per_branch_data
                     .entry(branch)
                     .or_insert_with(|| BranchData { kv: Vec::new(), state: Vec::new(), events: Vec::new() })
                     .state
-                    .push(StateCell { cell, value });
+                    .push(StateCell { line, cell, value });
             }
             BranchRecord::BranchEvent { branch, event_type, payload } => {
                 per_branch_data
                     .entry(branch)
                     .or_insert_with(|| BranchData { kv: Vec::new(), state: Vec::new(), events: Vec::new() })
                     .events
-                    .push(EventEntry { event_type, payload });
+                    .push(EventEntry { line, event_type, payload });
             }
             BranchRecord::IsolationCheck {
                 description, on_branch, key, expected_value, expected_event_count,
             } => {
                 isolation_checks.push(IsolationCheck {
-                    description, on_branch, key, expected_value, expected_event_count,
+                    line, description, on_branch, key, expected_value, expected_event_count,
                 });
             }
             BranchRecord::CrossBranchComparison { cell, expected, winner } => {
@@ -744,7 +873,7 @@ pub fn load_branch_dataset() -> BranchDataset {
 }
 
 pub fn load_dirty_dataset() -> DirtyDataset {
-    let records: Vec<DirtyRecord> = read_jsonl("dirty.jsonl");
+    let records: Vec<(usize, DirtyRecord)> = read_jsonl("dirty.jsonl");
     let mut ds = DirtyDataset {
         kv_roundtrips: Vec::new(),
         kv_rejects: Vec::new(),
@@ -755,34 +884,34 @@ pub fn load_dirty_dataset() -> DirtyDataset {
         cross_kv_json: Vec::new(),
         cross_branch_dirty: Vec::new(),
     };
-    for r in records {
+    for (line, r) in records {
         match r {
             DirtyRecord::Kv { desc, key, value } => {
-                ds.kv_roundtrips.push(DirtyKv { desc, key, value });
+                ds.kv_roundtrips.push(DirtyKv { line, desc, key, value });
             }
             DirtyRecord::KvReject { desc, key, value } => {
-                ds.kv_rejects.push(DirtyKv { desc, key, value });
+                ds.kv_rejects.push(DirtyKv { line, desc, key, value });
             }
             DirtyRecord::State { desc, cell, value } => {
-                ds.state_roundtrips.push(DirtyState { desc, cell, value });
+                ds.state_roundtrips.push(DirtyState { line, desc, cell, value });
             }
             DirtyRecord::Event { desc, event_type, payload } => {
-                ds.event_roundtrips.push(DirtyEvent { desc, event_type, payload });
+                ds.event_roundtrips.push(DirtyEvent { line, desc, event_type, payload });
             }
             DirtyRecord::Json { desc, key, doc } => {
-                ds.json_roundtrips.push(DirtyJson { desc, key, doc });
+                ds.json_roundtrips.push(DirtyJson { line, desc, key, doc });
             }
             DirtyRecord::Vector { desc, collection, key, embedding } => {
-                ds.vector_roundtrips.push(DirtyVector { desc, collection, key, embedding });
+                ds.vector_roundtrips.push(DirtyVector { line, desc, collection, key, embedding });
             }
             DirtyRecord::CrossKvJson { desc, key, kv_value, json_doc } => {
-                ds.cross_kv_json.push(DirtyCrossKvJson { desc, key, kv_value, json_doc });
+                ds.cross_kv_json.push(DirtyCrossKvJson { line, desc, key, kv_value, json_doc });
             }
             DirtyRecord::CrossBranchDirty {
                 desc, branch, key, kv_value, cell, state_value, event_type, event_payload,
             } => {
                 ds.cross_branch_dirty.push(DirtyCrossBranch {
-                    desc, branch, key, kv_value, cell, state_value, event_type, event_payload,
+                    line, desc, branch, key, kv_value, cell, state_value, event_type, event_payload,
                 });
             }
         }
@@ -790,6 +919,149 @@ pub fn load_dirty_dataset() -> DirtyDataset {
     ds
 }
 
+// =============================================================================
+// Crash injection (WAL truncation)
+// =============================================================================
+
+/// Write a strata.toml with always durability before opening.
+pub fn write_always_config(path: &Path) {
+    std::fs::create_dir_all(path).expect("create dir for config");
+    std::fs::write(path.join("strata.toml"), "durability = \"always\"\n")
+        .expect("write always config");
+}
+
+/// Check that a reopened db holds a prefix of the writes `key(i)` = `i` for
+/// `i` in `0..num_ops`: `Ok(n)` if exactly the first `n` are present with
+/// the right values and the rest are absent. `Err` describes the first
+/// violation (a wrong value, a gap, or a read that failed or panicked),
+/// i.e. corruption that recovery did not detect.
+pub fn recovered_prefix(
+    strata: &Strata,
+    num_ops: usize,
+    key: impl Fn(usize) -> String,
+) -> Result<usize, String> {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut recovered = 0;
+    for i in 0..num_ops {
+        let k = key(i);
+        let got = catch_unwind(AssertUnwindSafe(|| strata.kv_get(&k)))
+            .map_err(|_| format!("kv_get({}) panicked", k))?
+            .map_err(|e| format!("kv_get({}) failed: {}", k, e))?;
+        match got {
+            Some(Value::Int(v)) if v == i as i64 && recovered == i => recovered += 1,
+            None => {}
+            other => {
+                return Err(format!(
+                    "{} = {:?} after recovering a prefix of {} writes",
+                    k, other, recovered
+                ))
+            }
+        }
+    }
+    Ok(recovered)
+}
+
+/// Whether `path` (under `db_dir`) is a WAL file: some component of its
+/// path *relative to* `db_dir` mentions `wal`. Only the relative part is
+/// checked, since the tempdir name and `$TMPDIR` can contain "wal" too.
+fn is_wal_path(db_dir: &Path, path: &Path) -> bool {
+    path.strip_prefix(db_dir).is_ok_and(|rel| {
+        rel.components()
+            .any(|c| c.as_os_str().to_string_lossy().to_lowercase().contains("wal"))
+    })
+}
+
+/// Find the WAL file under a disk database directory: the largest file
+/// whose path below `db_dir` has a `wal` component. Panics if there is none,
+/// or if the largest is empty, since a test that truncates or corrupts
+/// nothing (or the wrong file) would pass vacuously.
+pub fn find_wal_file(db_dir: &Path) -> PathBuf {
+    fn walk(db_dir: &Path, dir: &Path, out: &mut Vec<(PathBuf, u64)>) {
+        for entry in std::fs::read_dir(dir).expect("read db dir") {
+            let path = entry.expect("read db dir entry").path();
+            if path.is_dir() {
+                walk(db_dir, &path, out);
+            } else if is_wal_path(db_dir, &path) {
+                let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                out.push((path, len));
+            }
+        }
+    }
+
+    let mut candidates = Vec::new();
+    walk(db_dir, db_dir, &mut candidates);
+    let (wal, len) = candidates
+        .into_iter()
+        .max_by_key(|(_, len)| *len)
+        .unwrap_or_else(|| panic!("no WAL file found under {}", db_dir.display()));
+    assert!(is_wal_path(db_dir, &wal), "{} is not a WAL segment", wal.display());
+    assert!(len > 0, "WAL segment {} is empty", wal.display());
+    wal
+}
+
+/// Result of one [`crash_with_truncated_wal`] run.
+pub struct WalCrash {
+    /// WAL size before truncation.
+    pub wal_len: u64,
+    /// Offset the WAL was cut at (clamped to `wal_len`).
+    pub cut_at: u64,
+    /// `Ok(n)`: reopen recovered exactly the first `n` writes, intact.
+    /// `Err(msg)`: reopen refused the truncated WAL with an error.
+    pub outcome: Result<usize, String>,
+}
+
+/// Key written by op `i` of [`crash_with_truncated_wal`].
+pub fn crash_key(i: usize) -> String {
+    format!("crash:{:06}", i)
+}
+
+/// Open an always-durability disk db, do `num_ops` puts (`crash_key(i)` =
+/// `i`), drop it without `flush()`, truncate the WAL at `cut_at(wal_len)`
+/// and reopen.
+///
+/// Panics — with the cut offset in the message — if reopen or a read panics,
+/// if any key comes back with the wrong value, or if the recovered keys are
+/// not a prefix of the writes. Otherwise reports what recovery did.
+pub fn crash_with_truncated_wal(num_ops: usize, cut_at: impl FnOnce(u64) -> u64) -> WalCrash {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use stratadb::Database;
+
+    let dir = tempfile::tempdir().expect("create temp dir");
+    write_always_config(dir.path());
+
+    {
+        let db = Database::open(dir.path()).expect("open disk db");
+        let strata = Strata::from_database(db).expect("wrap disk db");
+        for i in 0..num_ops {
+            strata.kv_put(&crash_key(i), Value::Int(i as i64)).expect("kv_put before crash");
+        }
+        // NO flush — crash
+    }
+
+    let wal = find_wal_file(dir.path());
+    let wal_len = std::fs::metadata(&wal).expect("stat WAL").len();
+    let cut_at = cut_at(wal_len).min(wal_len);
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&wal)
+        .and_then(|f| f.set_len(cut_at))
+        .expect("truncate WAL");
+
+    let reopened = catch_unwind(AssertUnwindSafe(|| {
+        Database::open(dir.path()).and_then(Strata::from_database)
+    }))
+    .unwrap_or_else(|_| panic!("reopen panicked with WAL cut at {}/{}", cut_at, wal_len));
+    let strata = match reopened {
+        Ok(strata) => strata,
+        Err(e) => return WalCrash { wal_len, cut_at, outcome: Err(e.to_string()) },
+    };
+
+    let recovered = recovered_prefix(&strata, num_ops, crash_key)
+        .unwrap_or_else(|e| panic!("WAL cut at {}/{}: {}", cut_at, wal_len, e));
+    WalCrash { wal_len, cut_at, outcome: Ok(recovered) }
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
@@ -0,0 +1,160 @@
+//! Black-box tests for overlapping maintenance operations.
+//!
+//! One thread writes continuously while others call `flush()` and `compact()`
+//! on their own handles. Nothing may deadlock, and every acknowledged write
+//! must be readable afterwards — including after a reopen.
+
+use stratadb::{Strata, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the writer and maintenance threads run together.
+const RUN_TIME: Duration = Duration::from_secs(3);
+/// How long to wait for a thread to finish after stop before calling it a deadlock.
+const DEADLOCK_TIMEOUT: Duration = Duration::from_secs(30);
+/// Writes rotate over this many keys, so later writes overwrite earlier ones
+/// and give compaction superseded versions to reclaim.
+const KEYSPACE: u64 = 2_000;
+
+/// Outcome of one worker thread.
+enum Finished {
+    Writer(Vec<i64>),
+    Flusher(u64),
+    Compactor(u64),
+}
+
+/// Spawn a worker that reports its result over `done`.
+fn spawn_worker(
+    done: &mpsc::Sender<Finished>,
+    work: impl FnOnce() -> Finished + Send + 'static,
+) -> thread::JoinHandle<()> {
+    let done = done.clone();
+    thread::spawn(move || {
+        let _ = done.send(work());
+    })
+}
+
+/// Run writer + flusher + compactor against `db` and return the final value
+/// written for each key slot.
+fn run_concurrent_maintenance(db: &Strata) -> Vec<i64> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let (done_tx, done_rx) = mpsc::channel();
+
+    let writer_db = db.new_handle().unwrap();
+    let writer_stop = stop.clone();
+    let writer = spawn_worker(&done_tx, move || {
+        let mut last = vec![-1i64; KEYSPACE as usize];
+        let mut i = 0i64;
+        while !writer_stop.load(Ordering::Relaxed) {
+            let slot = (i as u64 % KEYSPACE) as usize;
+            writer_db.kv_put(&format!("key:{}", slot), Value::Int(i)).unwrap();
+            last[slot] = i;
+            i += 1;
+        }
+        Finished::Writer(last)
+    });
+
+    let flush_db = db.new_handle().unwrap();
+    let flush_stop = stop.clone();
+    let flusher = spawn_worker(&done_tx, move || {
+        let mut flushes = 0;
+        while !flush_stop.load(Ordering::Relaxed) {
+            flush_db.flush().unwrap();
+            flushes += 1;
+            thread::sleep(Duration::from_millis(10));
+        }
+        Finished::Flusher(flushes)
+    });
+
+    let compact_db = db.new_handle().unwrap();
+    let compact_stop = stop.clone();
+    let compactor = spawn_worker(&done_tx, move || {
+        let mut compactions = 0;
+        while !compact_stop.load(Ordering::Relaxed) {
+            compact_db.compact().unwrap();
+            compactions += 1;
+            thread::sleep(Duration::from_millis(25));
+        }
+        Finished::Compactor(compactions)
+    });
+    drop(done_tx);
+
+    thread::sleep(RUN_TIME);
+    stop.store(true, Ordering::Relaxed);
+
+    // A panicking worker drops its sender without reporting; a deadlocked
+    // one never reports. Either way recv_timeout tells us which.
+    let deadline = Instant::now() + DEADLOCK_TIMEOUT;
+    let mut last = None;
+    let (mut flushes, mut compactions) = (0, 0);
+    for _ in 0..3 {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match done_rx.recv_timeout(remaining) {
+            Ok(Finished::Writer(l)) => last = Some(l),
+            Ok(Finished::Flusher(n)) => flushes = n,
+            Ok(Finished::Compactor(n)) => compactions = n,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                panic!("maintenance threads did not stop within {:?} (deadlock?)", DEADLOCK_TIMEOUT)
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    for (name, handle) in [("writer", writer), ("flusher", flusher), ("compactor", compactor)] {
+        handle.join().unwrap_or_else(|_| panic!("{} thread panicked", name));
+    }
+
+    assert!(flushes > 0, "flusher never ran");
+    assert!(compactions > 0, "compactor never ran");
+    let last = last.expect("writer did not report");
+    assert!(last.iter().any(|&v| v >= 0), "writer made no progress");
+    last
+}
+
+fn assert_all_intact(db: &Strata, last: &[i64]) {
+    for (slot, &expected) in last.iter().enumerate() {
+        let key = format!("key:{}", slot);
+        let got = db.kv_get(&key).unwrap();
+        if expected < 0 {
+            assert_eq!(got, None, "{} was never written but reads {:?}", key, got);
+        } else {
+            assert_eq!(got, Some(Value::Int(expected)), "{} lost its last write", key);
+        }
+    }
+}
+
+// =============================================================================
+// Concurrent flush + compact + writes
+// =============================================================================
+
+#[test]
+fn concurrent_flush_and_compact_preserve_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Strata::open(dir.path()).unwrap();
+
+    let last = run_concurrent_maintenance(&db);
+    assert_all_intact(&db, &last);
+}
+
+#[test]
+fn concurrent_maintenance_survives_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let last = {
+        let db = Strata::open(dir.path()).unwrap();
+        let last = run_concurrent_maintenance(&db);
+        db.flush().unwrap();
+        last
+    };
+
+    let db = Strata::open(dir.path()).unwrap();
+    assert_all_intact(&db, &last);
+}
+
+#[test]
+fn concurrent_maintenance_on_temp_db() {
+    let db = Strata::open_temp().expect("failed to open temp db");
+    let last = run_concurrent_maintenance(&db);
+    assert_all_intact(&db, &last);
+}
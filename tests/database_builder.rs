@@ -3,16 +3,12 @@
 //! Tests exercise Database::open(), Database::cache(), config file behavior,
 //! DurabilityMode enum, and persistence/recovery across reopens.
 
+mod common;
+
+use common::{crash_with_truncated_wal, write_always_config};
 use stratadb::{Database, DurabilityMode, Strata, StrataConfig, Value};
 use std::sync::Arc;
 
-/// Write a strata.toml with always durability before opening.
-fn write_always_config(path: &std::path::Path) {
-    std::fs::create_dir_all(path).expect("create dir for config");
-    std::fs::write(path.join("strata.toml"), "durability = \"always\"\n")
-        .expect("write always config");
-}
-
 // =============================================================================
 // Database::open() basics
 // =============================================================================
@@ -497,6 +493,54 @@ fn crash_after_many_operations_recovers_consistently() {
     }
 }
 
+// =============================================================================
+// Crash injection (WAL truncated mid-write)
+// =============================================================================
+
+const TRUNCATION_OPS: usize = 50;
+
+#[test]
+fn untruncated_wal_recovers_every_write() {
+    let crash = crash_with_truncated_wal(TRUNCATION_OPS, |len| len);
+    assert!(crash.wal_len > 0, "always mode should have written a WAL");
+    assert_eq!(crash.outcome, Ok(TRUNCATION_OPS));
+}
+
+#[test]
+fn truncated_wal_sweep_recovers_prefix_or_errors() {
+    const CUTS: u64 = 64;
+
+    let wal_len = crash_with_truncated_wal(TRUNCATION_OPS, |len| len).wal_len;
+    let mut last_recovered = 0;
+    for step in 0..=CUTS {
+        let crash = crash_with_truncated_wal(TRUNCATION_OPS, |len| len * step / CUTS);
+        assert_eq!(crash.wal_len, wal_len, "same writes should produce the same WAL");
+        // Errors are an acceptable answer; a shorter WAL recovering more is not
+        if let Ok(recovered) = crash.outcome {
+            assert!(
+                recovered >= last_recovered,
+                "cut at {}/{} recovered {} writes, an earlier cut recovered {}",
+                crash.cut_at, wal_len, recovered, last_recovered
+            );
+            last_recovered = recovered;
+        }
+    }
+}
+
+#[test]
+fn truncated_wal_random_cuts_recover_prefix_or_errors() {
+    let mut state: u64 = 0x5eed_c4a5;
+    for _ in 0..32 {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let r = state >> 33;
+        let crash = crash_with_truncated_wal(TRUNCATION_OPS, |len| r % len.max(1));
+        // The helper panics on corrupt or non-prefix recovery; both outcomes are fine here
+        if let Ok(recovered) = crash.outcome {
+            assert!(recovered <= TRUNCATION_OPS);
+        }
+    }
+}
+
 // =============================================================================
 // Shutdown behavior
 // =============================================================================
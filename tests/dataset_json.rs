@@ -37,14 +37,14 @@ fn path_queries() {
         let got = db.json_get(&q.key, &q.path).unwrap();
         assert!(
             got.is_some(),
-            "path query returned None: key={} path={}",
-            q.key, q.path
+            "path query returned None: key={} path={} (json_docs.jsonl:{})",
+            q.key, q.path, q.line
         );
         let got_json = value_to_json(&got.unwrap());
         assert_eq!(
             got_json, q.expected,
-            "path query mismatch: key={} path={}",
-            q.key, q.path
+            "path query mismatch: key={} path={} (json_docs.jsonl:{})",
+            q.key, q.path, q.line
         );
     }
 }
@@ -113,6 +113,49 @@ fn field_deletion() {
     assert!(parent.is_some(), "parent doc should still exist after field delete");
 }
 
+#[test]
+fn every_deletion_removes_only_its_path() {
+    let ds = load_json_dataset();
+    assert!(!ds.deletions.is_empty(), "dataset has no deletions");
+
+    for d in &ds.deletions {
+        // Fresh db per deletion so each one is checked against the original docs
+        let db = fresh_db();
+        for doc in &ds.documents {
+            db.json_set(&doc.key, "$", json_to_value(&doc.doc)).unwrap();
+        }
+
+        db.json_delete(&d.key, &d.path).unwrap();
+
+        let result = db.json_get(&d.key, &d.path).unwrap();
+        assert!(
+            result.is_none(),
+            "deleted path still present: key={} path={} (json_docs.jsonl:{})",
+            d.key, d.path, d.line
+        );
+
+        if d.path != "$" {
+            let parent = db.json_get(&d.key, "$").unwrap();
+            assert!(
+                parent.is_some(),
+                "doc should survive field delete: key={} path={}",
+                d.key, d.path
+            );
+        }
+
+        // Every other document is untouched
+        for doc in ds.documents.iter().filter(|doc| doc.key != d.key) {
+            let got = db.json_get(&doc.key, "$").unwrap();
+            assert_eq!(
+                got.map(|v| value_to_json(&v)),
+                Some(doc.doc.clone()),
+                "unrelated doc changed after deleting key={} path={}",
+                d.key, d.path
+            );
+        }
+    }
+}
+
 #[test]
 fn prefix_listing_counts() {
     let ds = load_json_dataset();
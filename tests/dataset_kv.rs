@@ -18,8 +18,13 @@ fn insert_and_readback_all_entries() {
 
     for entry in &ds.entries {
         let got = db.kv_get(&entry.key).unwrap();
-        assert!(got.is_some(), "missing key: {}", entry.key);
-        assert_eq!(got.unwrap(), entry.value.to_value(), "mismatch for key: {}", entry.key);
+        assert!(got.is_some(), "missing key: {} (kv.jsonl:{})", entry.key, entry.line);
+        assert_eq!(
+            got.unwrap(),
+            entry.value.to_value(),
+            "mismatch for key: {} (kv.jsonl:{})",
+            entry.key, entry.line
+        );
     }
 }
 
@@ -91,6 +96,46 @@ fn overwrite_sequence() {
     assert_eq!(debug, stratadb::Value::Bool(true));
 }
 
+#[test]
+fn each_overwrite_bumps_version() {
+    let ds = load_kv_dataset();
+    let db = fresh_db();
+
+    for entry in &ds.entries {
+        db.kv_put(&entry.key, entry.value.to_value()).unwrap();
+    }
+
+    for ow in &ds.overwrites {
+        let before = db.kv_getv(&ow.key).unwrap().unwrap_or_default();
+        db.kv_put(&ow.key, ow.value.to_value()).unwrap();
+
+        let got = db.kv_get(&ow.key).unwrap();
+        assert_eq!(
+            got,
+            Some(ow.value.to_value()),
+            "overwrite not visible: {} (kv.jsonl:{})",
+            ow.key, ow.line
+        );
+
+        let after = db.kv_getv(&ow.key).unwrap().unwrap();
+        assert_eq!(
+            after.len(),
+            before.len() + 1,
+            "overwrite of '{}' should add one version",
+            ow.key
+        );
+        // Newest first
+        assert_eq!(after[0].value, ow.value.to_value());
+        if let Some(prev) = before.first() {
+            assert!(
+                after[0].version > prev.version,
+                "version of '{}' should increase: {} -> {}",
+                ow.key, prev.version, after[0].version
+            );
+        }
+    }
+}
+
 #[test]
 fn delete_then_reinsert() {
     let ds = load_kv_dataset();
@@ -48,15 +48,24 @@ fn cas_sequence_counter_increment() {
     assert_eq!(seq.cell, "counter:operations");
 
     let mut ver = *versions.get(&seq.cell).unwrap();
-    for step in &seq.steps {
+    for (i, step) in seq.steps.iter().enumerate() {
         let result = db.state_cas(&seq.cell, Some(ver), step.new_value.to_value()).unwrap();
-        assert!(result.is_some(), "CAS step failed for {}", seq.cell);
+        assert!(
+            result.is_some(),
+            "CAS step {} failed for {} (state.jsonl:{})",
+            i, seq.cell, seq.line
+        );
         ver = result.unwrap();
     }
 
     let final_val = db.state_read(&seq.cell).unwrap().unwrap();
     let last_step = seq.steps.last().unwrap();
-    assert_eq!(final_val, last_step.new_value.to_value());
+    assert_eq!(
+        final_val,
+        last_step.new_value.to_value(),
+        "final value mismatch for {} (state.jsonl:{})",
+        seq.cell, seq.line
+    );
 }
 
 #[test]
@@ -76,7 +85,7 @@ fn cas_sequence_phase_transitions() {
     let mut ver = *versions.get(&seq.cell).unwrap();
     for step in &seq.steps {
         let result = db.state_cas(&seq.cell, Some(ver), step.new_value.to_value()).unwrap();
-        assert!(result.is_some(), "CAS phase transition failed");
+        assert!(result.is_some(), "CAS phase transition failed (state.jsonl:{})", seq.line);
         ver = result.unwrap();
     }
 
@@ -156,7 +165,12 @@ fn init_creates_new_cells() {
 
     for init in &ds.init_cells {
         let got = db.state_read(&init.cell).unwrap().unwrap();
-        assert_eq!(got, init.value.to_value(), "init cell mismatch: {}", init.cell);
+        assert_eq!(
+            got,
+            init.value.to_value(),
+            "init cell mismatch: {} (state.jsonl:{})",
+            init.cell, init.line
+        );
     }
 }
 
@@ -5,7 +5,9 @@
 
 mod common;
 
-use common::{load_vector_dataset, parse_metric, json_to_value, fresh_db};
+use common::{
+    fresh_db, json_to_value, load_vector_dataset, load_vector_dataset_streaming, parse_metric,
+};
 
 #[test]
 fn create_collections_and_upsert() {
@@ -56,8 +58,8 @@ fn search_returns_expected_top_result() {
 
         assert_eq!(
             results[0].key, q.expected_top,
-            "search '{}': expected top={}, got top={}",
-            q.description, q.expected_top, results[0].key
+            "search '{}' (vectors.jsonl:{}): expected top={}, got top={}",
+            q.description, q.line, q.expected_top, results[0].key
         );
     }
 }
@@ -157,3 +159,36 @@ fn collection_list() {
         );
     }
 }
+
+#[test]
+fn streaming_loader_matches_eager_loader() {
+    let ds = load_vector_dataset();
+    let db = fresh_db();
+
+    let streamed = load_vector_dataset_streaming(&db);
+
+    assert_eq!(streamed.collections.len(), ds.collections.len());
+    for coll in &ds.collections {
+        let count = streamed
+            .collections
+            .iter()
+            .find(|(name, _)| *name == coll.name)
+            .map(|(_, count)| *count);
+        assert_eq!(count, Some(coll.vectors.len()), "vector count for '{}'", coll.name);
+        for vec_entry in &coll.vectors {
+            let got = db.vector_get(&coll.name, &vec_entry.key).unwrap();
+            assert!(got.is_some(), "missing vector: {}/{}", coll.name, vec_entry.key);
+        }
+    }
+
+    assert_eq!(streamed.search_queries.len(), ds.search_queries.len());
+    for q in &streamed.search_queries {
+        let results = db.vector_search(&q.collection, q.query.clone(), q.k).unwrap();
+        assert_eq!(
+            results.first().map(|r| r.key.as_str()),
+            Some(q.expected_top.as_str()),
+            "search '{}' (vectors.jsonl:{})",
+            q.description, q.line
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Black-box tests for zero-length byte values.
+//!
+//! An empty `Value::Bytes` is a real value: the key exists, is listed, and
+//! reads back as empty bytes. It must never be confused with `Value::Null`
+//! (a tombstone: a Null put deletes the key) or with an empty string.
+
+use stratadb::{Strata, Value};
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn empty() -> Value {
+    Value::Bytes(vec![])
+}
+
+// =============================================================================
+// KV round-trip
+// =============================================================================
+
+#[test]
+fn empty_bytes_round_trips() {
+    let db = db();
+    db.kv_put("key", empty()).unwrap();
+    assert_eq!(db.kv_get("key").unwrap(), Some(empty()));
+}
+
+#[test]
+fn empty_bytes_key_is_listed() {
+    let db = db();
+    db.kv_put("empty:a", empty()).unwrap();
+    db.kv_put("empty:b", Value::Bytes(vec![0x00])).unwrap();
+
+    let mut keys = db.kv_list(Some("empty:")).unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["empty:a".to_string(), "empty:b".to_string()]);
+}
+
+#[test]
+fn empty_bytes_is_not_empty_string() {
+    let db = db();
+    db.kv_put("bytes", empty()).unwrap();
+    db.kv_put("string", "").unwrap();
+
+    assert_eq!(db.kv_get("bytes").unwrap(), Some(empty()));
+    assert_eq!(db.kv_get("string").unwrap(), Some(Value::String("".into())));
+    assert_ne!(db.kv_get("bytes").unwrap(), db.kv_get("string").unwrap());
+}
+
+#[test]
+fn empty_bytes_is_not_null() {
+    let db = db();
+    db.kv_put("bytes", empty()).unwrap();
+    db.kv_put("null", Value::Null).unwrap();
+
+    assert_eq!(db.kv_get("bytes").unwrap(), Some(empty()));
+    // Null is a tombstone; empty bytes are a value
+    assert_eq!(db.kv_get("null").unwrap(), None);
+}
+
+#[test]
+fn null_put_deletes_but_empty_bytes_put_does_not() {
+    let db = db();
+    db.kv_put("a", "value").unwrap();
+    db.kv_put("b", "value").unwrap();
+
+    db.kv_put("a", empty()).unwrap();
+    db.kv_put("b", Value::Null).unwrap();
+
+    assert_eq!(db.kv_get("b").unwrap(), None);
+    assert!(!db.kv_list(None).unwrap().contains(&"b".to_string()));
+
+    assert_eq!(db.kv_get("a").unwrap(), Some(empty()));
+    assert!(db.kv_list(None).unwrap().contains(&"a".to_string()));
+}
+
+#[test]
+fn overwrite_with_empty_bytes_keeps_key() {
+    let db = db();
+    db.kv_put("key", Value::Bytes(vec![1, 2, 3])).unwrap();
+    db.kv_put("key", empty()).unwrap();
+
+    assert_eq!(db.kv_get("key").unwrap(), Some(empty()));
+    let history = db.kv_getv("key").unwrap().expect("history should exist");
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn empty_bytes_can_be_deleted() {
+    let db = db();
+    db.kv_put("key", empty()).unwrap();
+
+    assert!(db.kv_delete("key").unwrap(), "existing empty-bytes key should report deleted");
+    assert_eq!(db.kv_get("key").unwrap(), None);
+}
+
+// =============================================================================
+// Other primitives
+// =============================================================================
+
+#[test]
+fn state_empty_bytes_round_trips() {
+    let db = db();
+    db.state_set("cell", empty()).unwrap();
+    assert_eq!(db.state_read("cell").unwrap(), Some(empty()));
+}
+
+#[test]
+fn event_payload_with_empty_bytes_field() {
+    let db = db();
+    let payload = Value::Object([("data".to_string(), empty())].into_iter().collect());
+    let seq = db.event_append("stream", payload.clone()).unwrap();
+
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+    assert_eq!(event.value, payload);
+}
+
+#[test]
+fn empty_bytes_persists_across_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+
+    {
+        let db = Strata::open(path).unwrap();
+        db.kv_put("key", empty()).unwrap();
+        db.flush().unwrap();
+    }
+
+    let db = Strata::open(path).unwrap();
+    assert_eq!(db.kv_get("key").unwrap(), Some(empty()));
+}
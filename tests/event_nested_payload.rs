@@ -0,0 +1,178 @@
+//! Black-box tests for deeply nested event payloads.
+//!
+//! Structured audit-log style events nest objects and arrays several levels
+//! deep. Every level — including floats, bytes, and empty containers — must
+//! round-trip exactly through `event_read` and `event_read_by_type`.
+
+use stratadb::{Strata, Value};
+use std::collections::HashMap;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn obj(pairs: &[(&str, Value)]) -> Value {
+    let map: HashMap<String, Value> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    Value::Object(map)
+}
+
+/// A 5-level nested object with mixed scalar types and arrays at each level.
+fn nested_payload(seed: i64) -> Value {
+    let level5 = obj(&[
+        ("float", Value::Float(seed as f64 * 0.125 + 1e-9)),
+        ("bytes", Value::Bytes(vec![0x00, 0xFF, seed as u8, 0x7F])),
+        ("null", Value::Null),
+    ]);
+    let level4 = obj(&[
+        ("level5", level5),
+        (
+            "list",
+            Value::Array(vec![Value::Float(-0.0), Value::Float(f64::MAX), Value::Int(i64::MIN)]),
+        ),
+    ]);
+    let level3 = obj(&[
+        ("level4", level4),
+        ("flag", Value::Bool(seed % 2 == 0)),
+        ("objects", Value::Array(vec![obj(&[("k", Value::Int(seed))]), obj(&[])])),
+    ]);
+    let level2 = obj(&[
+        ("level3", level3),
+        ("name", Value::String(format!("actor-{}", seed))),
+        ("empty_array", Value::Array(vec![])),
+    ]);
+    obj(&[
+        ("level2", level2),
+        ("seq_hint", Value::Int(seed)),
+        ("empty_object", obj(&[])),
+        (
+            "nested_arrays",
+            Value::Array(vec![Value::Array(vec![Value::Array(vec![Value::Int(1)])])]),
+        ),
+    ])
+}
+
+// =============================================================================
+// event_read round-trip
+// =============================================================================
+
+#[test]
+fn nested_payload_round_trips_via_event_read() {
+    let db = db();
+    let payload = nested_payload(7);
+    let seq = db.event_append("audit", payload.clone()).unwrap();
+
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+    assert_eq!(event.value, payload);
+}
+
+#[test]
+fn nested_float_and_bytes_are_exact() {
+    let db = db();
+    let seq = db.event_append("audit", nested_payload(3)).unwrap();
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+
+    let level5 = ["level2", "level3", "level4", "level5"]
+        .iter()
+        .fold(&event.value, |v, key| match v {
+            Value::Object(map) => map.get(*key).unwrap_or_else(|| panic!("missing {}", key)),
+            other => panic!("expected object at {}, got {:?}", key, other),
+        });
+    match level5 {
+        Value::Object(map) => {
+            assert_eq!(map.get("float"), Some(&Value::Float(3.0 * 0.125 + 1e-9)));
+            assert_eq!(map.get("bytes"), Some(&Value::Bytes(vec![0x00, 0xFF, 3, 0x7F])));
+            assert_eq!(map.get("null"), Some(&Value::Null));
+        }
+        other => panic!("expected level5 object, got {:?}", other),
+    }
+}
+
+#[test]
+fn many_nested_payloads_round_trip() {
+    let db = db();
+    let seqs: Vec<u64> = (0..50)
+        .map(|i| db.event_append("audit", nested_payload(i)).unwrap())
+        .collect();
+
+    for (i, seq) in seqs.into_iter().enumerate() {
+        let event = db.event_read(seq).unwrap().expect("event should exist");
+        assert_eq!(event.value, nested_payload(i as i64), "event {} did not round-trip", i);
+    }
+}
+
+// =============================================================================
+// event_read_by_type round-trip
+// =============================================================================
+
+#[test]
+fn nested_payloads_round_trip_via_read_by_type() {
+    let db = db();
+    for i in 0..10 {
+        db.event_append("audit", nested_payload(i)).unwrap();
+        db.event_append("other", obj(&[("i", Value::Int(i))])).unwrap();
+    }
+
+    let events = db.event_read_by_type("audit").unwrap();
+    assert_eq!(events.len(), 10);
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event.value, nested_payload(i as i64));
+    }
+}
+
+// =============================================================================
+// Empty containers
+// =============================================================================
+
+#[test]
+fn empty_object_payload_round_trips() {
+    let db = db();
+    let seq = db.event_append("empty", obj(&[])).unwrap();
+
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+    assert_eq!(event.value, obj(&[]));
+    assert_eq!(db.event_read_by_type("empty").unwrap()[0].value, obj(&[]));
+}
+
+#[test]
+fn empty_array_field_round_trips() {
+    let db = db();
+    let payload = obj(&[("items", Value::Array(vec![]))]);
+    let seq = db.event_append("empty", payload.clone()).unwrap();
+
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+    assert_eq!(event.value, payload);
+}
+
+#[test]
+fn empty_array_payload_round_trips_or_is_rejected() {
+    let db = db();
+    // A non-object root may be rejected, but if accepted it must round-trip
+    if let Ok(seq) = db.event_append("empty", Value::Array(vec![])) {
+        let event = db.event_read(seq).unwrap().expect("event should exist");
+        assert_eq!(event.value, Value::Array(vec![]));
+    }
+}
+
+// =============================================================================
+// Persistence
+// =============================================================================
+
+#[test]
+fn nested_payload_persists_across_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+
+    let seq = {
+        let db = Strata::open(path).unwrap();
+        let seq = db.event_append("audit", nested_payload(11)).unwrap();
+        db.flush().unwrap();
+        seq
+    };
+
+    let db = Strata::open(path).unwrap();
+    let event = db.event_read(seq).unwrap().expect("event should exist");
+    assert_eq!(event.value, nested_payload(11));
+}
@@ -0,0 +1,152 @@
+//! Black-box tests for event sequence allocation.
+//!
+//! Single-threaded appends on a fresh database must return strictly
+//! increasing, gapless sequence numbers starting at 1, and `event_len()` must track the number of successful
+//! appends. A rejected append must not consume a sequence number.
+
+use stratadb::{Strata, Value};
+use std::collections::HashMap;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn obj(pairs: &[(&str, Value)]) -> Value {
+    let map: HashMap<String, Value> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    Value::Object(map)
+}
+
+const EVENT_COUNT: u64 = 1_000;
+
+// =============================================================================
+// Gapless, monotonic sequences
+// =============================================================================
+
+#[test]
+fn sequences_are_gapless_and_increasing() {
+    let db = db();
+    let mut seqs = Vec::with_capacity(EVENT_COUNT as usize);
+
+    for i in 0..EVENT_COUNT {
+        let seq = db
+            .event_append("stream", obj(&[("i", Value::Int(i as i64))]))
+            .unwrap();
+        seqs.push(seq);
+        assert_eq!(db.event_len().unwrap(), i + 1, "event_len after {} appends", i + 1);
+    }
+
+    // Sequences are 1-indexed, as the event_read benches assume
+    assert_eq!(seqs[0], 1, "first append on a fresh database");
+    for (i, seq) in seqs.iter().enumerate() {
+        assert_eq!(*seq, 1 + i as u64, "gap or repeat at append {}", i);
+    }
+}
+
+#[test]
+fn every_returned_sequence_reads_back_its_payload() {
+    let db = db();
+    let mut seqs = Vec::with_capacity(EVENT_COUNT as usize);
+    for i in 0..EVENT_COUNT {
+        seqs.push(
+            db.event_append("stream", obj(&[("i", Value::Int(i as i64))]))
+                .unwrap(),
+        );
+    }
+
+    for (i, seq) in seqs.iter().enumerate() {
+        let event = db.event_read(*seq).unwrap().expect("event missing");
+        assert_eq!(event.value, obj(&[("i", Value::Int(i as i64))]));
+    }
+}
+
+#[test]
+fn sequences_are_shared_across_event_types() {
+    let db = db();
+    let s1 = db.event_append("a", obj(&[("x", Value::Int(1))])).unwrap();
+    let s2 = db.event_append("b", obj(&[("x", Value::Int(2))])).unwrap();
+    let s3 = db.event_append("a", obj(&[("x", Value::Int(3))])).unwrap();
+
+    assert_eq!(s2, s1 + 1);
+    assert_eq!(s3, s2 + 1);
+    assert_eq!(db.event_len().unwrap(), 3);
+}
+
+#[test]
+fn no_sequence_exists_past_the_last_append() {
+    let db = db();
+    let mut last = 0;
+    for i in 0..10 {
+        last = db.event_append("stream", obj(&[("i", Value::Int(i))])).unwrap();
+    }
+    assert!(db.event_read(last + 1).unwrap().is_none());
+}
+
+// =============================================================================
+// Rejected appends
+// =============================================================================
+
+/// Appends that the engine may reject: non-object payloads and an empty type.
+fn dirty_appends() -> Vec<(&'static str, Value)> {
+    vec![
+        ("stream", Value::Int(42)),
+        ("stream", Value::String("not an object".into())),
+        ("stream", Value::Null),
+        ("", obj(&[("x", Value::Int(1))])),
+    ]
+}
+
+#[test]
+fn rejected_append_does_not_consume_sequence() {
+    for (event_type, payload) in dirty_appends() {
+        let db = db();
+        let before = db.event_append("stream", obj(&[("n", Value::Int(0))])).unwrap();
+
+        let dirty = db.event_append(event_type, payload.clone());
+        let after = db.event_append("stream", obj(&[("n", Value::Int(1))])).unwrap();
+
+        match dirty {
+            Err(_) => {
+                assert_eq!(
+                    after,
+                    before + 1,
+                    "rejected append ({:?}, {:?}) consumed a sequence",
+                    event_type,
+                    payload
+                );
+                assert_eq!(db.event_len().unwrap(), 2);
+            }
+            Ok(seq) => {
+                // Accepted: it must occupy exactly the slot between its neighbours
+                assert_eq!(seq, before + 1);
+                assert_eq!(after, before + 2);
+                assert_eq!(db.event_len().unwrap(), 3);
+            }
+        }
+    }
+}
+
+#[test]
+fn interleaved_rejections_keep_sequences_gapless() {
+    let db = db();
+    let mut accepted = Vec::new();
+
+    for i in 0..100i64 {
+        if let Ok(seq) = db.event_append("stream", obj(&[("i", Value::Int(i))])) {
+            accepted.push(seq);
+        }
+        for (event_type, payload) in dirty_appends() {
+            if let Ok(seq) = db.event_append(event_type, payload) {
+                accepted.push(seq);
+            }
+        }
+    }
+
+    let first = accepted[0];
+    for (i, seq) in accepted.iter().enumerate() {
+        assert_eq!(*seq, first + i as u64, "gap or repeat at accepted append {}", i);
+    }
+    assert_eq!(db.event_len().unwrap(), accepted.len() as u64);
+}
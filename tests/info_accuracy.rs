@@ -0,0 +1,157 @@
+//! Black-box tests for the accuracy of `Strata::info()`.
+//!
+//! `DatabaseInfo` exposes `branch_count` and `total_keys` alongside the
+//! version string. These tests populate a known amount of data and check
+//! that both counts track writes and deletes. Counts are compared against a
+//! baseline taken on the fresh database so system-owned entries (e.g. the
+//! default branch) don't make the assertions brittle.
+
+use stratadb::Strata;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+// =============================================================================
+// Branch count
+// =============================================================================
+
+#[test]
+fn fresh_db_counts_default_branch() {
+    let db = db();
+    let info = db.info().unwrap();
+    assert!(info.branch_count >= 1, "default branch should be counted");
+}
+
+#[test]
+fn branch_count_tracks_creates() {
+    let db = db();
+    let baseline = db.info().unwrap().branch_count;
+
+    for i in 0..5 {
+        db.create_branch(&format!("branch-{}", i)).unwrap();
+    }
+
+    assert_eq!(db.info().unwrap().branch_count, baseline + 5);
+}
+
+#[test]
+fn branch_count_tracks_deletes() {
+    let db = db();
+    let baseline = db.info().unwrap().branch_count;
+
+    for i in 0..5 {
+        db.create_branch(&format!("branch-{}", i)).unwrap();
+    }
+    db.delete_branch("branch-0").unwrap();
+    db.delete_branch("branch-1").unwrap();
+
+    assert_eq!(db.info().unwrap().branch_count, baseline + 3);
+}
+
+#[test]
+fn branch_count_matches_list_branches() {
+    let db = db();
+    db.create_branch("a").unwrap();
+    db.create_branch("b").unwrap();
+
+    let listed = db.list_branches().unwrap().len() as u64;
+    assert_eq!(db.info().unwrap().branch_count, listed);
+}
+
+// =============================================================================
+// Key count
+// =============================================================================
+
+#[test]
+fn total_keys_tracks_kv_puts() {
+    let db = db();
+    let baseline = db.info().unwrap().total_keys;
+
+    for i in 0..100 {
+        db.kv_put(&format!("key:{}", i), i as i64).unwrap();
+    }
+
+    assert_eq!(db.info().unwrap().total_keys, baseline + 100);
+}
+
+#[test]
+fn total_keys_ignores_overwrites() {
+    let db = db();
+    let baseline = db.info().unwrap().total_keys;
+
+    for version in 0..10i64 {
+        db.kv_put("same", version).unwrap();
+    }
+
+    assert_eq!(db.info().unwrap().total_keys, baseline + 1);
+}
+
+#[test]
+fn total_keys_tracks_kv_deletes() {
+    let db = db();
+    let baseline = db.info().unwrap().total_keys;
+
+    for i in 0..100 {
+        db.kv_put(&format!("key:{}", i), i as i64).unwrap();
+    }
+    for i in 0..40 {
+        assert!(db.kv_delete(&format!("key:{}", i)).unwrap());
+    }
+
+    assert_eq!(db.info().unwrap().total_keys, baseline + 60);
+}
+
+#[test]
+fn total_keys_unchanged_by_deleting_missing_key() {
+    let db = db();
+    db.kv_put("present", 1i64).unwrap();
+    let before = db.info().unwrap().total_keys;
+
+    assert!(!db.kv_delete("absent").unwrap());
+
+    assert_eq!(db.info().unwrap().total_keys, before);
+}
+
+#[test]
+fn total_keys_counts_other_primitives() {
+    let db = db();
+    let baseline = db.info().unwrap().total_keys;
+
+    db.kv_put("kv", 1i64).unwrap();
+    db.state_set("cell", 1i64).unwrap();
+    db.json_set("doc", "$", stratadb::Value::Int(1)).unwrap();
+
+    let after = db.info().unwrap().total_keys;
+    assert!(
+        after >= baseline + 3,
+        "expected at least 3 new keys across kv/state/json, got {}",
+        after - baseline
+    );
+}
+
+// =============================================================================
+// Persistence
+// =============================================================================
+
+#[test]
+fn counts_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+
+    let (branches, keys) = {
+        let db = Strata::open(path).unwrap();
+        db.create_branch("persisted").unwrap();
+        for i in 0..50 {
+            db.kv_put(&format!("key:{}", i), i as i64).unwrap();
+        }
+        db.flush().unwrap();
+        let info = db.info().unwrap();
+        (info.branch_count, info.total_keys)
+    };
+
+    let db = Strata::open(path).unwrap();
+    let info = db.info().unwrap();
+    assert_eq!(info.branch_count, branches);
+    assert_eq!(info.total_keys, keys);
+}
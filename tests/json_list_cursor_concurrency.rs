@@ -0,0 +1,219 @@
+//! Black-box tests for json_list cursor stability under concurrent inserts.
+//!
+//! The cursor contract these tests pin is the weaker "live" one: documents
+//! inserted mid-pagination may or may not appear, but every document that
+//! existed when pagination began is returned exactly once, no key is ever
+//! returned twice, and pagination always terminates.
+
+use stratadb::{Strata, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const PAGE_SIZE: u64 = 3;
+
+/// Upper bound on writer-thread inserts, so live pagination always has an end.
+const WRITER_INSERTS: u64 = 500;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn doc(n: i64) -> Value {
+    let map: HashMap<String, Value> = [("n".to_string(), Value::Int(n))].into_iter().collect();
+    Value::Object(map)
+}
+
+fn seed(db: &Strata, prefix: &str, count: i64) -> HashSet<String> {
+    (0..count)
+        .map(|i| {
+            let key = format!("{}{:04}", prefix, i);
+            db.json_set(&key, "$", doc(i)).unwrap();
+            key
+        })
+        .collect()
+}
+
+/// Page through `prefix`, calling `between` after every page.
+///
+/// Returns every key in the order it was returned. Panics if pagination
+/// does not terminate within a generous page bound.
+fn paginate(db: &Strata, prefix: &str, mut between: impl FnMut(usize)) -> Vec<String> {
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    for page in 0..10_000 {
+        let (keys, next) = db.json_list(Some(prefix.to_string()), cursor, PAGE_SIZE).unwrap();
+        assert!(keys.len() as u64 <= PAGE_SIZE, "page {} exceeded limit: {}", page, keys.len());
+        seen.extend(keys);
+        match next {
+            Some(c) => cursor = Some(c),
+            None => return seen,
+        }
+        between(page);
+    }
+    panic!("pagination did not terminate after 10000 pages ({} keys seen)", seen.len());
+}
+
+fn assert_no_duplicates(keys: &[String]) {
+    let mut unique = HashSet::new();
+    for key in keys {
+        assert!(unique.insert(key), "key returned twice during pagination: {}", key);
+    }
+}
+
+fn assert_all_present(keys: &[String], expected: &HashSet<String>) {
+    let seen: HashSet<&String> = keys.iter().collect();
+    for key in expected {
+        assert!(seen.contains(key), "pre-existing doc missing from pagination: {}", key);
+    }
+}
+
+/// New docs may appear, but only ones that were actually inserted.
+fn assert_only_known(keys: &[String], original: &HashSet<String>, inserted: &HashSet<String>) {
+    for key in keys {
+        assert!(
+            original.contains(key) || inserted.contains(key),
+            "pagination returned a key that was never written: {}",
+            key
+        );
+    }
+}
+
+// =============================================================================
+// Static baseline
+// =============================================================================
+
+#[test]
+fn pagination_without_mutation_returns_each_doc_once() {
+    let db = db();
+    let original = seed(&db, "doc:", 20);
+
+    let keys = paginate(&db, "doc:", |_| {});
+    assert_no_duplicates(&keys);
+    assert_eq!(keys.len(), original.len());
+    assert_all_present(&keys, &original);
+}
+
+// =============================================================================
+// Inserts between page fetches
+// =============================================================================
+
+#[test]
+fn inserts_after_cursor_position() {
+    let db = db();
+    let original = seed(&db, "doc:", 20);
+    let mut inserted = HashSet::new();
+
+    // "doc:9..." sorts after every seeded "doc:00NN" key
+    let keys = paginate(&db, "doc:", |page| {
+        let key = format!("doc:9{:04}", page);
+        db.json_set(&key, "$", doc(-1)).unwrap();
+        inserted.insert(key);
+    });
+
+    assert_no_duplicates(&keys);
+    assert_all_present(&keys, &original);
+    assert_only_known(&keys, &original, &inserted);
+}
+
+#[test]
+fn inserts_before_cursor_position() {
+    let db = db();
+    let original = seed(&db, "doc:1", 20);
+    let mut inserted = HashSet::new();
+
+    // "doc:0..." sorts before every seeded "doc:1NNNN" key, i.e. behind the cursor
+    let keys = paginate(&db, "doc:", |page| {
+        let key = format!("doc:0{:04}", page);
+        db.json_set(&key, "$", doc(-1)).unwrap();
+        inserted.insert(key);
+    });
+
+    assert_no_duplicates(&keys);
+    assert_all_present(&keys, &original);
+    assert_only_known(&keys, &original, &inserted);
+}
+
+#[test]
+fn inserts_interleaved_with_existing_keys() {
+    let db = db();
+    let original = seed(&db, "doc:", 20);
+    let mut inserted = HashSet::new();
+
+    // "doc:0005a" sorts between "doc:0005" and "doc:0006"
+    let keys = paginate(&db, "doc:", |page| {
+        for i in (0..20).step_by(4) {
+            let key = format!("doc:{:04}{}", i, (b'a' + page as u8 % 26) as char);
+            db.json_set(&key, "$", doc(-1)).unwrap();
+            inserted.insert(key);
+        }
+    });
+
+    assert_no_duplicates(&keys);
+    assert_all_present(&keys, &original);
+    assert_only_known(&keys, &original, &inserted);
+}
+
+#[test]
+fn overwrites_during_pagination_do_not_duplicate() {
+    let db = db();
+    let original = seed(&db, "doc:", 20);
+
+    // Rewrite every seeded doc between pages; keys don't change, versions do
+    let keys = paginate(&db, "doc:", |page| {
+        for key in &original {
+            db.json_set(key, "$", doc(page as i64)).unwrap();
+        }
+    });
+
+    assert_no_duplicates(&keys);
+    assert_eq!(keys.len(), original.len());
+    assert_all_present(&keys, &original);
+}
+
+#[test]
+fn inserts_under_other_prefix_are_not_listed() {
+    let db = db();
+    let original = seed(&db, "doc:", 20);
+
+    let keys = paginate(&db, "doc:", |page| {
+        db.json_set(&format!("other:{:04}", page), "$", doc(-1)).unwrap();
+    });
+
+    assert_no_duplicates(&keys);
+    assert_eq!(keys.len(), original.len());
+    assert_all_present(&keys, &original);
+}
+
+// =============================================================================
+// Concurrent writer thread
+// =============================================================================
+
+#[test]
+fn pagination_with_concurrent_writer_thread() {
+    let db = db();
+    let original = seed(&db, "doc:", 50);
+
+    let writer = db.new_handle().unwrap();
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer_stop = stop.clone();
+    let handle = std::thread::spawn(move || {
+        let mut inserted = HashSet::new();
+        let mut i = 0u64;
+        while i < WRITER_INSERTS && !writer_stop.load(Ordering::Relaxed) {
+            let key = format!("doc:{:04}-new{}", i % 50, i);
+            writer.json_set(&key, "$", doc(i as i64)).unwrap();
+            inserted.insert(key);
+            i += 1;
+        }
+        inserted
+    });
+
+    let keys = paginate(&db, "doc:", |_| std::thread::yield_now());
+    stop.store(true, Ordering::Relaxed);
+    let inserted = handle.join().expect("writer thread panicked");
+
+    assert_no_duplicates(&keys);
+    assert_all_present(&keys, &original);
+    assert_only_known(&keys, &original, &inserted);
+}
@@ -0,0 +1,135 @@
+//! Black-box tests for non-object JSON document roots.
+//!
+//! The JSON store accepts any JSON value at `$`, not just objects (other
+//! tests already set `Value::Int` roots). Every root must round-trip exactly
+//! and be replaceable by a root of a different type.
+
+use stratadb::{Strata, Value};
+use std::collections::HashMap;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+fn obj(pairs: &[(&str, Value)]) -> Value {
+    let map: HashMap<String, Value> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    Value::Object(map)
+}
+
+/// Set `value` at the root and check it reads back exactly.
+fn assert_root_round_trips(db: &Strata, key: &str, value: Value) {
+    db.json_set(key, "$", value.clone()).unwrap();
+    assert_eq!(
+        db.json_get(key, "$").unwrap(),
+        Some(value.clone()),
+        "root {:?} did not round-trip",
+        value
+    );
+}
+
+// =============================================================================
+// Scalar roots
+// =============================================================================
+
+#[test]
+fn int_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Int(5));
+}
+
+#[test]
+fn float_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Float(2.5));
+}
+
+#[test]
+fn string_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::String("plain".into()));
+}
+
+#[test]
+fn bool_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Bool(true));
+}
+
+#[test]
+fn null_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Null);
+}
+
+// =============================================================================
+// Array roots
+// =============================================================================
+
+#[test]
+fn array_root() {
+    let db = db();
+    let array = Value::Array(vec![Value::Int(10), Value::String("b".into()), Value::Bool(false)]);
+    assert_root_round_trips(&db, "doc", array);
+    assert_eq!(db.json_get("doc", "[0]").unwrap(), Some(Value::Int(10)));
+    assert_eq!(db.json_get("doc", "[1]").unwrap(), Some(Value::String("b".into())));
+    assert_eq!(db.json_get("doc", "[2]").unwrap(), Some(Value::Bool(false)));
+}
+
+#[test]
+fn array_root_out_of_range_index() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Array(vec![Value::Int(1)]));
+    // Reading past the end is either None or an error, never a panic
+    if let Ok(got) = db.json_get("doc", "[5]") {
+        assert_eq!(got, None);
+    }
+}
+
+#[test]
+fn empty_array_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Array(vec![]));
+}
+
+#[test]
+fn array_of_objects_root() {
+    let db = db();
+    let array = Value::Array(vec![obj(&[("n", Value::Int(1))]), obj(&[("n", Value::Int(2))])]);
+    assert_root_round_trips(&db, "doc", array);
+    assert_eq!(db.json_get("doc", "[1].n").unwrap(), Some(Value::Int(2)));
+}
+
+// =============================================================================
+// Replacing an existing object root
+// =============================================================================
+
+#[test]
+fn scalar_over_object_root_replaces_it() {
+    let db = db();
+    let original = obj(&[("name", Value::String("Alice".into()))]);
+    db.json_set("doc", "$", original).unwrap();
+
+    assert_root_round_trips(&db, "doc", Value::Int(5));
+}
+
+#[test]
+fn object_over_scalar_root() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Int(5));
+    let replacement = obj(&[("n", Value::Int(1))]);
+    db.json_set("doc", "$", replacement.clone()).unwrap();
+    assert_eq!(db.json_get("doc", "$").unwrap(), Some(replacement));
+    assert_eq!(db.json_get("doc", "n").unwrap(), Some(Value::Int(1)));
+}
+
+#[test]
+fn scalar_root_has_version_history() {
+    let db = db();
+    assert_root_round_trips(&db, "doc", Value::Int(1));
+    db.json_set("doc", "$", Value::Int(2)).unwrap();
+    let history = db.json_getv("doc").unwrap().expect("history should exist");
+    assert_eq!(history.len(), 2);
+}
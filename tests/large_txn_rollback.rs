@@ -0,0 +1,180 @@
+//! Black-box tests for rolling back large transactions.
+//!
+//! Stages thousands of KV and State writes inside a Session transaction,
+//! rolls back, and checks that nothing is visible afterward and that
+//! resident memory does not grow across repeated stage/rollback cycles. The
+//! memory check reads process-wide RSS, so it is `#[ignore]`d and meant to
+//! be run on its own with `--test-threads=1`.
+
+use stratadb::{Command, Database, Output, Session, Strata, Value};
+use std::sync::Arc;
+
+/// Writes staged per primitive in each transaction.
+const TXN_WRITES: usize = 10_000;
+
+/// Stage/rollback cycles used to detect accumulation.
+const CYCLES: usize = 8;
+
+/// Allowed RSS growth over the post-warmup baseline. A leak of a single
+/// cycle's staged data (10k × 1KB values plus keys) would exceed this
+/// after a few cycles.
+const RSS_SLACK_BYTES: u64 = 32 * 1024 * 1024;
+
+fn db() -> Arc<Database> {
+    Database::cache().unwrap()
+}
+
+/// Resident set size of this process in bytes, from /proc/self/status.
+///
+/// Returns `None` on platforms without procfs.
+fn rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Begin a transaction, stage `TXN_WRITES` KvPuts and StateInits, roll back.
+fn stage_and_rollback(s: &mut Session, cycle: usize) {
+    s.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    for i in 0..TXN_WRITES {
+        s.execute(Command::KvPut {
+            branch: None,
+            key: format!("txn:{}:kv:{}", cycle, i),
+            value: Value::Bytes(vec![0x42; 1024]),
+        })
+        .unwrap();
+        s.execute(Command::StateInit {
+            branch: None,
+            cell: format!("txn:{}:cell:{}", cycle, i),
+            value: Value::Int(i as i64),
+        })
+        .unwrap();
+    }
+    let output = s.execute(Command::TxnRollback).unwrap();
+    assert!(matches!(output, Output::TxnAborted));
+    assert!(!s.in_transaction());
+}
+
+fn assert_nothing_visible(strata: &Strata, cycle: usize) {
+    assert!(
+        strata.kv_list(Some(&format!("txn:{}:", cycle))).unwrap().is_empty(),
+        "cycle {}: rolled-back keys are visible",
+        cycle
+    );
+    for i in [0, TXN_WRITES / 2, TXN_WRITES - 1] {
+        assert_eq!(
+            strata.kv_get(&format!("txn:{}:kv:{}", cycle, i)).unwrap(),
+            None
+        );
+        assert_eq!(
+            strata.state_read(&format!("txn:{}:cell:{}", cycle, i)).unwrap(),
+            None
+        );
+    }
+}
+
+// =============================================================================
+// Visibility after rollback
+// =============================================================================
+
+#[test]
+fn large_rollback_leaves_no_state() {
+    let db = db();
+    let mut s = Session::new(db.clone());
+    stage_and_rollback(&mut s, 0);
+
+    let strata = Strata::from_database(db).unwrap();
+    assert_nothing_visible(&strata, 0);
+}
+
+#[test]
+fn session_usable_after_large_rollback() {
+    let db = db();
+    let mut s = Session::new(db.clone());
+    stage_and_rollback(&mut s, 0);
+
+    s.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    s.execute(Command::KvPut {
+        branch: None,
+        key: "after".into(),
+        value: Value::Int(1),
+    })
+    .unwrap();
+    s.execute(Command::TxnCommit).unwrap();
+
+    let strata = Strata::from_database(db).unwrap();
+    assert_eq!(strata.kv_get("after").unwrap(), Some(Value::Int(1)));
+    assert_nothing_visible(&strata, 0);
+}
+
+#[test]
+fn large_rollback_preserves_existing_data() {
+    let db = db();
+    let strata = Strata::from_database(db.clone()).unwrap();
+    strata.kv_put("txn:0:kv:0", "original").unwrap();
+
+    let mut s = Session::new(db);
+    s.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    for i in 0..TXN_WRITES {
+        s.execute(Command::KvPut {
+            branch: None,
+            key: format!("txn:0:kv:{}", i),
+            value: Value::Int(i as i64),
+        })
+        .unwrap();
+    }
+    s.execute(Command::TxnRollback).unwrap();
+
+    assert_eq!(
+        strata.kv_get("txn:0:kv:0").unwrap(),
+        Some(Value::String("original".into()))
+    );
+    assert_eq!(strata.kv_list(Some("txn:0:")).unwrap().len(), 1);
+}
+
+// =============================================================================
+// Resource reclamation across repeated rollbacks
+// =============================================================================
+
+#[test]
+fn repeated_large_rollbacks_leave_nothing_visible() {
+    let db = db();
+    let strata = Strata::from_database(db.clone()).unwrap();
+    let mut s = Session::new(db);
+
+    for cycle in 0..=CYCLES {
+        stage_and_rollback(&mut s, cycle);
+        assert_nothing_visible(&strata, cycle);
+    }
+}
+
+/// Asserts on process-wide RSS, which the other tests in this binary move
+/// when they run in parallel, so it only runs on request and single-threaded.
+#[test]
+#[ignore = "measures process RSS; run alone: cargo test --test large_txn_rollback \
+            -- --ignored --test-threads=1"]
+fn repeated_large_rollbacks_do_not_accumulate() {
+    let db = db();
+    let strata = Strata::from_database(db.clone()).unwrap();
+    let mut s = Session::new(db);
+
+    // One warmup cycle so allocator arenas reach steady state before the baseline
+    stage_and_rollback(&mut s, 0);
+    let baseline = rss_bytes();
+
+    for cycle in 1..=CYCLES {
+        stage_and_rollback(&mut s, cycle);
+        assert_nothing_visible(&strata, cycle);
+    }
+
+    if let (Some(before), Some(after)) = (baseline, rss_bytes()) {
+        assert!(
+            after <= before + RSS_SLACK_BYTES,
+            "RSS grew from {} to {} bytes over {} rollbacks",
+            before,
+            after,
+            CYCLES
+        );
+    }
+}
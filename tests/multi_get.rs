@@ -0,0 +1,112 @@
+//! Black-box tests for batched multi-key reads.
+//!
+//! The engine has no native multi-get yet; the benchmark harness batches gets
+//! inside one session transaction (`harness::kv_get_many`). These tests run
+//! that helper, so the code the benches measure is the code under test, and
+//! pin the contract any multi-get must satisfy: results in request order,
+//! `None` for missing keys.
+
+#[allow(unused)]
+#[path = "../benches/harness/mod.rs"]
+mod harness;
+
+use stratadb::{Strata, Value};
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+/// `harness::kv_get_many` over borrowed keys.
+fn get_many(db: &Strata, keys: &[&str]) -> Vec<Option<Value>> {
+    let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+    harness::kv_get_many(db, &keys)
+}
+
+// =============================================================================
+// Ordering and missing keys
+// =============================================================================
+
+#[test]
+fn returns_values_in_request_order() {
+    let db = db();
+    for i in 0..10 {
+        db.kv_put(&format!("k{}", i), Value::Int(i)).unwrap();
+    }
+
+    let keys = ["k7", "k2", "k9", "k0", "k5"];
+    let values = get_many(&db, &keys);
+    assert_eq!(
+        values,
+        vec![
+            Some(Value::Int(7)),
+            Some(Value::Int(2)),
+            Some(Value::Int(9)),
+            Some(Value::Int(0)),
+            Some(Value::Int(5)),
+        ]
+    );
+}
+
+#[test]
+fn missing_keys_are_none_in_place() {
+    let db = db();
+    db.kv_put("a", Value::Int(1)).unwrap();
+    db.kv_put("c", Value::Int(3)).unwrap();
+
+    let values = get_many(&db, &["a", "b", "c", "d"]);
+    assert_eq!(values, vec![Some(Value::Int(1)), None, Some(Value::Int(3)), None]);
+}
+
+#[test]
+fn all_missing_returns_all_none() {
+    let db = db();
+    let values = get_many(&db, &["x", "y", "z"]);
+    assert_eq!(values, vec![None, None, None]);
+}
+
+#[test]
+fn empty_batch_returns_empty() {
+    let db = db();
+    db.kv_put("a", Value::Int(1)).unwrap();
+    assert!(get_many(&db, &[]).is_empty());
+}
+
+#[test]
+fn duplicate_keys_return_duplicate_values() {
+    let db = db();
+    db.kv_put("a", Value::Int(1)).unwrap();
+
+    let values = get_many(&db, &["a", "missing", "a"]);
+    assert_eq!(values, vec![Some(Value::Int(1)), None, Some(Value::Int(1))]);
+}
+
+#[test]
+fn deleted_key_is_none() {
+    let db = db();
+    db.kv_put("a", Value::Int(1)).unwrap();
+    db.kv_put("b", Value::Int(2)).unwrap();
+    db.kv_delete("a").unwrap();
+
+    let values = get_many(&db, &["a", "b"]);
+    assert_eq!(values, vec![None, Some(Value::Int(2))]);
+}
+
+// =============================================================================
+// Agreement with individual gets
+// =============================================================================
+
+#[test]
+fn batch_matches_individual_gets() {
+    let db = db();
+    for i in 0..1000 {
+        if i % 3 != 0 {
+            db.kv_put(&format!("key:{}", i), Value::Int(i)).unwrap();
+        }
+    }
+
+    let owned: Vec<String> = (0..1000).rev().map(|i| format!("key:{}", i)).collect();
+    let keys: Vec<&str> = owned.iter().map(|k| k.as_str()).collect();
+    let batched = get_many(&db, &keys);
+    let individual: Vec<Option<Value>> = keys.iter().map(|k| db.kv_get(k).unwrap()).collect();
+    assert_eq!(batched, individual);
+}
@@ -0,0 +1,139 @@
+//! Black-box tests for `Strata::open_temp` isolation.
+//!
+//! Every test in this suite calls `Strata::open_temp()` for a fresh database
+//! and assumes nothing leaks between them. These tests pin that assumption:
+//! separate calls never share state, dropping one never affects another, and
+//! nothing is left behind in the temp directory after drop.
+
+use stratadb::{Strata, Value};
+use std::process::Command;
+
+/// Set in the child process spawned by `dropped_temp_db_leaves_no_files`.
+const CHILD_ENV: &str = "STRATA_OPEN_TEMP_CLEANUP_CHILD";
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+// =============================================================================
+// Isolation between instances
+// =============================================================================
+
+#[test]
+fn writes_are_not_visible_across_temp_dbs() {
+    let a = db();
+    let b = db();
+
+    a.kv_put("key", "from-a").unwrap();
+    assert_eq!(a.kv_get("key").unwrap(), Some(Value::String("from-a".into())));
+    assert_eq!(b.kv_get("key").unwrap(), None);
+    assert!(b.kv_list(None).unwrap().is_empty());
+}
+
+#[test]
+fn same_key_holds_independent_values() {
+    let a = db();
+    let b = db();
+
+    a.kv_put("key", Value::Int(1)).unwrap();
+    b.kv_put("key", Value::Int(2)).unwrap();
+    assert_eq!(a.kv_get("key").unwrap(), Some(Value::Int(1)));
+    assert_eq!(b.kv_get("key").unwrap(), Some(Value::Int(2)));
+    assert_eq!(a.kv_getv("key").unwrap().unwrap().len(), 1);
+}
+
+#[test]
+fn all_primitives_are_isolated() {
+    let a = db();
+    let b = db();
+
+    a.state_set("cell", Value::Int(1)).unwrap();
+    let payload = Value::Object([("x".to_string(), Value::Int(1))].into_iter().collect());
+    a.event_append("stream", payload).unwrap();
+    a.create_branch("feature").unwrap();
+
+    assert_eq!(b.state_read("cell").unwrap(), None);
+    assert_eq!(b.event_len().unwrap(), 0);
+    assert!(!b.list_branches().unwrap().contains(&"feature".to_string()));
+}
+
+#[test]
+fn fresh_db_is_empty_after_others_were_used() {
+    for i in 0..5 {
+        let db = db();
+        assert!(db.kv_list(None).unwrap().is_empty(), "temp db {} was not empty", i);
+        db.kv_put("leftover", Value::Int(i)).unwrap();
+    }
+}
+
+// =============================================================================
+// Drop independence
+// =============================================================================
+
+#[test]
+fn dropping_one_temp_db_does_not_affect_another() {
+    let a = db();
+    let b = db();
+    a.kv_put("a", "1").unwrap();
+    b.kv_put("b", "2").unwrap();
+
+    drop(a);
+
+    assert_eq!(b.kv_get("b").unwrap(), Some(Value::String("2".into())));
+    b.kv_put("b2", "3").unwrap();
+    b.flush().unwrap();
+    assert_eq!(b.kv_list(None).unwrap().len(), 2);
+}
+
+#[test]
+fn new_temp_db_after_drop_does_not_resurrect_data() {
+    {
+        let a = db();
+        a.kv_put("ghost", "boo").unwrap();
+        a.flush().unwrap();
+    }
+    let b = db();
+    assert_eq!(b.kv_get("ghost").unwrap(), None);
+}
+
+// =============================================================================
+// Cleanup on drop
+// =============================================================================
+
+/// Body of the cleanup check, run in a child process with TMPDIR pointed at a
+/// private directory so no other test's temp files can interfere. Ignored so
+/// it never shows up as a passing test in normal runs; the parent runs it
+/// with `--ignored`.
+#[test]
+#[ignore = "child process of dropped_temp_db_leaves_no_files"]
+fn open_temp_cleanup_child() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    let db = db();
+    for i in 0..100 {
+        db.kv_put(&format!("key:{}", i), Value::Int(i)).unwrap();
+    }
+    db.flush().unwrap();
+    drop(db);
+}
+
+#[test]
+fn dropped_temp_db_leaves_no_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let exe = std::env::current_exe().unwrap();
+    let status = Command::new(exe)
+        .args(["--exact", "open_temp_cleanup_child", "--ignored", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .env("TMPDIR", tmp.path())
+        .status()
+        .expect("failed to spawn child test process");
+    assert!(status.success(), "child process failed: {}", status);
+
+    // Either open_temp is purely in-memory or it removed its directory
+    let leftovers: Vec<_> = std::fs::read_dir(tmp.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert!(leftovers.is_empty(), "open_temp left files behind after drop: {:?}", leftovers);
+}
@@ -0,0 +1,228 @@
+//! Property-based tests for dotted JSON paths.
+//!
+//! Generates a random nested object, stores it with `json_set(key, "$")`,
+//! then checks `json_get` on a random existing path against the same lookup
+//! done on the original `serde_json::Value`, and that paths which do not
+//! exist read back as `None`. Object keys are drawn to hit the awkward cases:
+//! numeric-looking keys and keys containing dots.
+//!
+//! `arb_key` never draws an empty key, since in dotted form it becomes an
+//! empty segment ("a..b", "x."). `empty_key_lookup_is_err_or_value` covers
+//! that case on its own: the lookup may fail cleanly or return the value
+//! under `""`, but nothing else.
+//!
+//! Behind the `proptest` feature: `cargo test --features proptest`.
+
+mod common;
+
+use common::json_to_value;
+use proptest::prelude::*;
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use stratadb::Strata;
+
+const CASES: u32 = 256;
+
+fn db() -> &'static Strata {
+    static DB: OnceLock<Strata> = OnceLock::new();
+    DB.get_or_init(|| Strata::open_temp().expect("failed to open temp db"))
+}
+
+fn fresh_key() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    format!("doc:{}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+// =============================================================================
+// Generators
+// =============================================================================
+
+/// Object keys: plain words, numeric-looking, and dotted. Never empty; see
+/// `empty_key_lookup_is_err_or_value`.
+fn arb_key() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => "[a-z]{1,8}",
+        2 => "[0-9]{1,3}",
+        1 => "[a-z]{1,4}\\.[a-z]{1,4}",
+    ]
+}
+
+/// A key that `arb_key` never produces, so it is absent from every object.
+fn arb_missing_key() -> impl Strategy<Value = String> {
+    prop_oneof!["absent-[a-z]{1,4}", "[0-9]{4}"]
+}
+
+fn arb_leaf() -> impl Strategy<Value = Json> {
+    prop_oneof![
+        Just(Json::Null),
+        any::<bool>().prop_map(Json::Bool),
+        any::<i64>().prop_map(Json::from),
+        "[ -~]{0,16}".prop_map(Json::String),
+        prop::collection::vec(any::<i64>().prop_map(Json::from), 0..4).prop_map(Json::Array),
+    ]
+}
+
+fn object(fields: BTreeMap<String, Json>) -> Json {
+    Json::Object(fields.into_iter().collect())
+}
+
+/// A non-empty object nested up to 4 levels deep.
+fn arb_doc() -> impl Strategy<Value = Json> {
+    let node = arb_leaf().prop_recursive(4, 48, 5, |inner| {
+        prop::collection::btree_map(arb_key(), inner, 1..5).prop_map(object)
+    });
+    prop::collection::btree_map(arb_key(), node, 1..6).prop_map(object)
+}
+
+// =============================================================================
+// Reference model
+// =============================================================================
+
+/// Every path to a node below the root, as key segments.
+fn all_paths(doc: &Json) -> Vec<Vec<String>> {
+    fn walk(node: &Json, prefix: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+        if let Json::Object(fields) = node {
+            for (k, v) in fields {
+                prefix.push(k.clone());
+                out.push(prefix.clone());
+                walk(v, prefix, out);
+                prefix.pop();
+            }
+        }
+    }
+    let mut out = Vec::new();
+    walk(doc, &mut Vec::new(), &mut out);
+    out
+}
+
+/// Paths to the root and every object below it, as key segments.
+fn object_paths(doc: &Json) -> Vec<Vec<String>> {
+    let mut out = vec![Vec::new()];
+    out.extend(
+        all_paths(doc)
+            .into_iter()
+            .filter(|p| value_at(doc, p).is_some_and(Json::is_object)),
+    );
+    out
+}
+
+fn value_at<'a, S: AsRef<str>>(doc: &'a Json, segments: &[S]) -> Option<&'a Json> {
+    segments
+        .iter()
+        .try_fold(doc, |node, seg| node.as_object()?.get(seg.as_ref()))
+}
+
+fn value_at_mut<'a>(doc: &'a mut Json, segments: &[String]) -> Option<&'a mut Json> {
+    segments
+        .iter()
+        .try_fold(doc, |node, seg| node.as_object_mut()?.get_mut(seg))
+}
+
+/// Dotted-path form of `segments`. A key containing a dot cannot be told
+/// apart from two keys here, so such paths only get the weaker check.
+fn dotted(segments: &[String]) -> (String, bool) {
+    let ambiguous = segments.iter().any(|s| s.contains('.'));
+    (segments.join("."), ambiguous)
+}
+
+fn store(doc: &Json) -> String {
+    let key = fresh_key();
+    db().json_set(&key, "$", json_to_value(doc)).unwrap();
+    key
+}
+
+// =============================================================================
+// Properties
+// =============================================================================
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn get_existing_path_matches_model(doc in arb_doc(), pick in any::<prop::sample::Index>()) {
+        let paths = all_paths(&doc);
+        let segments = &paths[pick.index(paths.len())];
+        let (path, ambiguous) = dotted(segments);
+
+        let key = store(&doc);
+        let got = db().json_get(&key, &path);
+        if ambiguous {
+            // Whatever comes back must be what the dots literally spell out
+            if let Ok(got) = got {
+                let split: Vec<&str> = path.split('.').collect();
+                prop_assert_eq!(got, value_at(&doc, &split).map(json_to_value), "path {:?}", path);
+            }
+        } else {
+            let expected = value_at(&doc, segments).map(json_to_value);
+            prop_assert!(expected.is_some());
+            prop_assert_eq!(got.unwrap(), expected, "path {:?}", path);
+        }
+    }
+
+    #[test]
+    fn get_missing_path_is_none(
+        doc in arb_doc(),
+        pick in any::<prop::sample::Index>(),
+        missing in arb_missing_key(),
+    ) {
+        let parents = object_paths(&doc);
+        let mut segments = parents[pick.index(parents.len())].clone();
+        segments.push(missing);
+        let (path, ambiguous) = dotted(&segments);
+        prop_assume!(!ambiguous);
+
+        let key = store(&doc);
+        prop_assert_eq!(db().json_get(&key, &path).unwrap(), None, "path {:?}", path);
+    }
+
+    #[test]
+    fn delete_existing_path_removes_only_it(
+        doc in arb_doc(),
+        pick in any::<prop::sample::Index>(),
+    ) {
+        let paths = all_paths(&doc);
+        let segments = &paths[pick.index(paths.len())];
+        let (path, ambiguous) = dotted(segments);
+        prop_assume!(!ambiguous);
+
+        let key = store(&doc);
+        db().json_delete(&key, &path).unwrap();
+        prop_assert_eq!(db().json_get(&key, &path).unwrap(), None, "path {:?}", path);
+
+        let mut expected = doc.clone();
+        let (last, parent) = segments.split_last().unwrap();
+        let parent = parent
+            .iter()
+            .try_fold(&mut expected, |node, seg| node.as_object_mut()?.get_mut(seg))
+            .unwrap();
+        parent.as_object_mut().unwrap().remove(last);
+        prop_assert_eq!(db().json_get(&key, "$").unwrap(), Some(json_to_value(&expected)));
+    }
+
+    #[test]
+    fn empty_key_lookup_is_err_or_value(
+        doc in arb_doc(),
+        pick in any::<prop::sample::Index>(),
+        leaf in arb_leaf(),
+    ) {
+        let parents = object_paths(&doc);
+        let mut segments = parents[pick.index(parents.len())].clone();
+        let mut doc = doc;
+        value_at_mut(&mut doc, &segments)
+            .and_then(Json::as_object_mut)
+            .unwrap()
+            .insert(String::new(), leaf.clone());
+        // Nest under "w" so the path never collapses to "" (the root)
+        segments.insert(0, "w".to_string());
+        segments.push(String::new());
+        let (path, ambiguous) = dotted(&segments);
+        prop_assume!(!ambiguous);
+
+        let key = store(&object(BTreeMap::from([("w".to_string(), doc)])));
+        if let Ok(got) = db().json_get(&key, &path) {
+            prop_assert_eq!(got, Some(json_to_value(&leaf)), "path {:?}", path);
+        }
+    }
+}
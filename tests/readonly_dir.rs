@@ -0,0 +1,136 @@
+//! Black-box tests for opening a database in a read-only directory.
+//!
+//! Opening writes strata.toml into the data directory. When that directory is
+//! not writable, open must return an `Error` that explains why — never panic.
+//!
+//! Permission bits are ignored for root, so each test first probes whether
+//! the directory is really read-only and skips itself if it is not.
+
+#![cfg(unix)]
+
+use stratadb::{Database, Error, Strata};
+use std::os::unix::fs::PermissionsExt;
+use std::panic;
+use std::path::{Path, PathBuf};
+
+/// Makes a directory read-only and restores its permissions on drop, so the
+/// temp dir can always be cleaned up even if an assertion fails.
+struct ReadOnlyGuard {
+    path: PathBuf,
+    original: std::fs::Permissions,
+}
+
+impl ReadOnlyGuard {
+    fn new(path: &Path) -> Self {
+        let original = std::fs::metadata(path).unwrap().permissions();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o555)).unwrap();
+        Self {
+            path: path.to_path_buf(),
+            original,
+        }
+    }
+
+    /// Whether the permission change actually prevents writes (false for root).
+    fn is_enforced(&self) -> bool {
+        let probe = self.path.join(".write_probe");
+        match std::fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&probe);
+                false
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+impl Drop for ReadOnlyGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::set_permissions(&self.path, self.original.clone());
+    }
+}
+
+/// Open the database, failing the test on panic or on success.
+fn open_expect_err(path: &Path) -> Error {
+    let result = panic::catch_unwind(|| Database::open(path));
+    match result {
+        Err(panic_info) => panic!("[PANIC] Database::open panicked on read-only dir: {:?}", panic_info),
+        Ok(Ok(_)) => panic!("Database::open succeeded in a read-only directory"),
+        Ok(Err(e)) => e,
+    }
+}
+
+/// The error should name the permission failure, e.g. by carrying the OS
+/// "Permission denied" text.
+fn assert_explains_permission(err: &Error) {
+    let msg = err.to_string().to_lowercase();
+    assert!(
+        msg.contains("permission") || msg.contains("read-only") || msg.contains("denied"),
+        "error does not explain the permission failure: {}",
+        err
+    );
+}
+
+macro_rules! skip_unless_enforced {
+    ($guard:expr) => {
+        if !$guard.is_enforced() {
+            eprintln!("skipping: directory permissions not enforced (running as root?)");
+            return;
+        }
+    };
+}
+
+// =============================================================================
+// Fresh read-only directory
+// =============================================================================
+
+#[test]
+fn open_readonly_dir_returns_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let guard = ReadOnlyGuard::new(dir.path());
+    skip_unless_enforced!(guard);
+
+    let err = open_expect_err(dir.path());
+    assert_explains_permission(&err);
+}
+
+#[test]
+fn open_subdir_of_readonly_dir_returns_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let guard = ReadOnlyGuard::new(dir.path());
+    skip_unless_enforced!(guard);
+
+    // The data directory itself must be created inside the read-only parent
+    let err = open_expect_err(&dir.path().join("db"));
+    assert_explains_permission(&err);
+}
+
+#[test]
+fn strata_open_readonly_dir_returns_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let guard = ReadOnlyGuard::new(dir.path());
+    skip_unless_enforced!(guard);
+
+    let result = panic::catch_unwind(|| Strata::open(dir.path()));
+    match result {
+        Err(_) => panic!("[PANIC] Strata::open panicked on read-only dir"),
+        Ok(Ok(_)) => panic!("Strata::open succeeded in a read-only directory"),
+        Ok(Err(e)) => assert_explains_permission(&e),
+    }
+}
+
+// =============================================================================
+// Recovery after restoring permissions
+// =============================================================================
+
+#[test]
+fn restoring_permissions_allows_open() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let guard = ReadOnlyGuard::new(dir.path());
+        skip_unless_enforced!(guard);
+        open_expect_err(dir.path());
+    }
+
+    let db = Database::open(dir.path()).unwrap();
+    assert!(db.is_open());
+}
@@ -270,6 +270,109 @@ fn rollback_discards_state_writes() {
     assert_eq!(strata.state_read("temp").unwrap(), None);
 }
 
+// =============================================================================
+// Isolation between concurrent sessions (no dirty reads)
+// =============================================================================
+
+fn put_in(s: &mut Session, key: &str, value: Value) {
+    s.execute(Command::KvPut {
+        branch: None,
+        key: key.into(),
+        value,
+    }).unwrap();
+}
+
+fn get_in(s: &mut Session, key: &str) -> Option<Value> {
+    match s.execute(Command::KvGet { branch: None, key: key.into() }).unwrap() {
+        Output::Maybe(val) => val,
+        other => panic!("Expected Maybe from KvGet, got {:?}", other),
+    }
+}
+
+#[test]
+fn uncommitted_kv_write_invisible_to_other_session() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+
+    a.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    put_in(&mut a, "key", Value::Int(1));
+
+    assert_eq!(get_in(&mut b, "key"), None, "Session B saw A's uncommitted write");
+
+    a.execute(Command::TxnCommit).unwrap();
+    assert_eq!(get_in(&mut b, "key"), Some(Value::Int(1)), "Commit should publish A's write");
+}
+
+#[test]
+fn uncommitted_kv_overwrite_shows_old_value_to_other_session() {
+    let db = db();
+    Strata::from_database(db.clone()).unwrap().kv_put("key", Value::Int(0)).unwrap();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+
+    a.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    put_in(&mut a, "key", Value::Int(1));
+    assert_eq!(get_in(&mut a, "key"), Some(Value::Int(1)));
+
+    assert_eq!(get_in(&mut b, "key"), Some(Value::Int(0)), "Session B saw A's uncommitted write");
+    let strata = Strata::from_database(db).unwrap();
+    assert_eq!(strata.kv_get("key").unwrap(), Some(Value::Int(0)));
+
+    a.execute(Command::TxnCommit).unwrap();
+    assert_eq!(get_in(&mut b, "key"), Some(Value::Int(1)));
+}
+
+#[test]
+fn uncommitted_write_invisible_inside_other_transaction() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+
+    a.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    b.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    put_in(&mut a, "key", Value::Int(1));
+
+    assert_eq!(get_in(&mut b, "key"), None, "B's transaction saw A's uncommitted write");
+    b.execute(Command::TxnCommit).unwrap();
+    a.execute(Command::TxnCommit).unwrap();
+}
+
+#[test]
+fn rolled_back_write_never_visible_to_other_session() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+
+    a.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    put_in(&mut a, "key", Value::Int(1));
+    assert_eq!(get_in(&mut b, "key"), None);
+
+    a.execute(Command::TxnRollback).unwrap();
+    assert_eq!(get_in(&mut b, "key"), None);
+}
+
+#[test]
+fn uncommitted_state_init_invisible_to_other_session() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+
+    a.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+    a.execute(Command::StateInit {
+        branch: None,
+        cell: "cell".into(),
+        value: Value::Int(7),
+    }).unwrap();
+
+    let output = b.execute(Command::StateRead { branch: None, cell: "cell".into() }).unwrap();
+    assert!(matches!(output, Output::Maybe(None)), "Session B saw A's uncommitted StateInit");
+
+    a.execute(Command::TxnCommit).unwrap();
+    let output = b.execute(Command::StateRead { branch: None, cell: "cell".into() }).unwrap();
+    assert!(matches!(output, Output::Maybe(Some(Value::Int(7)))));
+}
+
 // =============================================================================
 // Session drop auto-rollback
 // =============================================================================
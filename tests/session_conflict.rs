@@ -0,0 +1,140 @@
+//! Black-box tests for concurrent commits across sessions.
+//!
+//! Two `Session`s on the same `Database` run overlapping transactions that
+//! touch the same key. Strata is first-committer-wins: the second commit is
+//! rejected with a conflict and leaves no trace. These tests pin that, so a
+//! change to last-writer-wins (or anything in between: a lost update or a
+//! half-applied transaction) fails here instead of passing silently.
+
+use stratadb::{Command, Database, Output, Session, Strata, Value};
+use std::sync::Arc;
+
+fn db() -> Arc<Database> {
+    Database::cache().unwrap()
+}
+
+fn begin(s: &mut Session) {
+    s.execute(Command::TxnBegin { branch: None, options: None }).unwrap();
+}
+
+fn put(s: &mut Session, key: &str, value: Value) {
+    s.execute(Command::KvPut {
+        branch: None,
+        key: key.into(),
+        value,
+    }).unwrap();
+}
+
+fn get(s: &mut Session, key: &str) -> Option<Value> {
+    match s.execute(Command::KvGet { branch: None, key: key.into() }).unwrap() {
+        Output::Maybe(val) => val,
+        other => panic!("Expected Maybe from KvGet, got {:?}", other),
+    }
+}
+
+/// Commit and return the commit version, or the error as a string.
+fn commit(s: &mut Session) -> Result<u64, String> {
+    match s.execute(Command::TxnCommit) {
+        Ok(Output::TxnCommitted { version }) => Ok(version),
+        Ok(other) => panic!("Expected TxnCommitted, got {:?}", other),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// =============================================================================
+// Blind writes to the same key
+// =============================================================================
+
+#[test]
+fn second_overlapping_commit_conflicts() {
+    let db = db();
+    let strata = Strata::from_database(db.clone()).unwrap();
+    strata.kv_put("key", Value::Int(0)).unwrap();
+
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+    begin(&mut a);
+    begin(&mut b);
+    put(&mut a, "key", Value::Int(1));
+    put(&mut b, "key", Value::Int(2));
+
+    commit(&mut a).expect("first committer should always succeed");
+    let second = commit(&mut b);
+    assert!(second.is_err(), "overlapping commit was not rejected: committed at {:?}", second);
+
+    // A's write stands, B's left no trace
+    assert_eq!(strata.kv_get("key").unwrap(), Some(Value::Int(1)));
+    let versions = strata.kv_getv("key").unwrap().unwrap();
+    assert_eq!(versions.len(), 2, "seed + A's commit only");
+    assert_eq!(versions[0].value, Value::Int(1));
+}
+
+#[test]
+fn failed_commit_applies_none_of_its_writes() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+    begin(&mut a);
+    begin(&mut b);
+    put(&mut a, "shared", Value::Int(1));
+    put(&mut b, "shared", Value::Int(2));
+    put(&mut b, "only-b", Value::Int(2));
+
+    commit(&mut a).unwrap();
+    let strata = Strata::from_database(db).unwrap();
+    let second = commit(&mut b);
+    assert!(second.is_err(), "overlapping commit was not rejected: committed at {:?}", second);
+    assert_eq!(strata.kv_get("shared").unwrap(), Some(Value::Int(1)));
+    assert_eq!(strata.kv_get("only-b").unwrap(), None, "rejected txn half-applied");
+}
+
+#[test]
+fn overlapping_commits_on_disjoint_keys_both_succeed() {
+    let db = db();
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+    begin(&mut a);
+    begin(&mut b);
+    put(&mut a, "a", Value::Int(1));
+    put(&mut b, "b", Value::Int(2));
+
+    let va = commit(&mut a).unwrap();
+    let vb = commit(&mut b).unwrap();
+    assert_ne!(va, vb, "each commit should get its own version");
+
+    let strata = Strata::from_database(db).unwrap();
+    assert_eq!(strata.kv_get("a").unwrap(), Some(Value::Int(1)));
+    assert_eq!(strata.kv_get("b").unwrap(), Some(Value::Int(2)));
+}
+
+// =============================================================================
+// Read-modify-write (lost update)
+// =============================================================================
+
+#[test]
+fn concurrent_increments_do_not_lose_updates() {
+    let db = db();
+    Strata::from_database(db.clone()).unwrap().kv_put("counter", Value::Int(0)).unwrap();
+
+    let mut a = Session::new(db.clone());
+    let mut b = Session::new(db.clone());
+    begin(&mut a);
+    begin(&mut b);
+    for s in [&mut a, &mut b] {
+        let n = match get(s, "counter") {
+            Some(Value::Int(n)) => n,
+            other => panic!("Expected Int counter, got {:?}", other),
+        };
+        put(s, "counter", Value::Int(n + 1));
+    }
+
+    let first = commit(&mut a);
+    assert!(first.is_ok(), "first committer should always succeed: {:?}", first);
+    // B read the counter before A committed; accepting it would lose A's
+    // increment
+    let second = commit(&mut b);
+    assert!(second.is_err(), "stale increment was not rejected: committed at {:?}", second);
+
+    let strata = Strata::from_database(db).unwrap();
+    assert_eq!(strata.kv_get("counter").unwrap(), Some(Value::Int(1)));
+}
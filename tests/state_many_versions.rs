@@ -0,0 +1,181 @@
+//! Black-box tests for a single state cell with a very long version chain.
+//!
+//! 50,000 `state_set`s on one cell. Point reads must stay fast regardless of
+//! history depth, `state_readv` must return the history newest-first with the
+//! right values, and neither writes nor reads may degrade quadratically as the
+//! chain grows.
+//!
+//! Timing bounds are deliberately loose ratios so the suite stays reliable on
+//! slow or noisy CI machines; they catch O(n) per-op behaviour, not tuning.
+
+use stratadb::{Strata, Value};
+use std::time::{Duration, Instant};
+
+/// Number of versions written to the cell.
+const VERSIONS: i64 = 50_000;
+/// Writes are timed in chunks of this size to compare early vs late cost.
+const CHUNK: i64 = 5_000;
+/// Point reads timed per measurement.
+const READS: u32 = 1_000;
+/// Allowed ratio between late and early per-op cost before we call it blowup.
+/// Linear-per-op growth over 50k versions would show up as ~10x or more.
+const MAX_SLOWDOWN: f64 = 8.0;
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+/// Write `VERSIONS` values 0..VERSIONS to `cell`, returning the time taken by
+/// the first and last `CHUNK` writes.
+fn write_versions(db: &Strata, cell: &str) -> (Duration, Duration) {
+    let mut first = Duration::ZERO;
+    let mut last = Duration::ZERO;
+    let mut chunk_start = Instant::now();
+    for i in 0..VERSIONS {
+        db.state_set(cell, Value::Int(i)).unwrap();
+        if (i + 1) % CHUNK == 0 {
+            let elapsed = chunk_start.elapsed();
+            if i + 1 == CHUNK {
+                first = elapsed;
+            }
+            last = elapsed;
+            chunk_start = Instant::now();
+        }
+    }
+    (first, last)
+}
+
+/// Average time of a `state_read` on `cell`.
+fn time_reads(db: &Strata, cell: &str) -> Duration {
+    let start = Instant::now();
+    for _ in 0..READS {
+        db.state_read(cell).unwrap();
+    }
+    start.elapsed() / READS
+}
+
+fn ratio(slow: Duration, fast: Duration) -> f64 {
+    slow.as_secs_f64() / fast.as_secs_f64().max(1e-9)
+}
+
+// =============================================================================
+// Point reads
+// =============================================================================
+
+#[test]
+fn read_returns_latest_after_many_versions() {
+    let db = db();
+    write_versions(&db, "cell");
+    assert_eq!(db.state_read("cell").unwrap(), Some(Value::Int(VERSIONS - 1)));
+}
+
+#[test]
+fn read_latency_independent_of_history_depth() {
+    let db = db();
+    db.state_set("shallow", Value::Int(0)).unwrap();
+    write_versions(&db, "deep");
+
+    // Warm both paths before timing
+    time_reads(&db, "shallow");
+    time_reads(&db, "deep");
+    let shallow = time_reads(&db, "shallow");
+    let deep = time_reads(&db, "deep");
+
+    assert!(
+        ratio(deep, shallow) < MAX_SLOWDOWN,
+        "state_read on a {}-version cell took {:?}/op vs {:?}/op on a 1-version cell",
+        VERSIONS,
+        deep,
+        shallow
+    );
+}
+
+// =============================================================================
+// Write cost as the chain grows
+// =============================================================================
+
+#[test]
+fn set_cost_does_not_grow_with_history() {
+    let db = db();
+    let (first, last) = write_versions(&db, "cell");
+    assert!(
+        ratio(last, first) < MAX_SLOWDOWN,
+        "last {} sets took {:?} but first {} took {:?} (quadratic version chain?)",
+        CHUNK,
+        last,
+        CHUNK,
+        first
+    );
+}
+
+// =============================================================================
+// Full history
+// =============================================================================
+
+#[test]
+fn readv_returns_history_newest_first() {
+    let db = db();
+    write_versions(&db, "cell");
+
+    let history = db.state_readv("cell").unwrap().expect("history should exist");
+    assert!(!history.is_empty());
+    // Either every version is retained, or a retention limit keeps a newest prefix
+    assert!(history.len() as i64 <= VERSIONS);
+    if (history.len() as i64) < VERSIONS {
+        eprintln!(
+            "note: state_readv retained {} of {} versions (retention limit)",
+            history.len(),
+            VERSIONS
+        );
+    }
+
+    for (i, entry) in history.iter().enumerate() {
+        assert_eq!(
+            entry.value,
+            Value::Int(VERSIONS - 1 - i as i64),
+            "history[{}] out of order",
+            i
+        );
+    }
+    for pair in history.windows(2) {
+        assert!(pair[0].version > pair[1].version, "versions not strictly decreasing");
+    }
+}
+
+#[test]
+fn readv_cost_is_linear_in_history() {
+    let db = db();
+    for i in 0..VERSIONS / 10 {
+        db.state_set("small", Value::Int(i)).unwrap();
+    }
+    write_versions(&db, "large");
+
+    let start = Instant::now();
+    let small = db.state_readv("small").unwrap().unwrap();
+    let small_time = start.elapsed();
+    let start = Instant::now();
+    let large = db.state_readv("large").unwrap().unwrap();
+    let large_time = start.elapsed();
+
+    // Scale the allowance by how many entries each call actually returned,
+    // so a retention limit doesn't skew the comparison.
+    let entries_ratio = large.len() as f64 / small.len() as f64;
+    assert!(
+        ratio(large_time, small_time) < entries_ratio * MAX_SLOWDOWN,
+        "state_readv of {} entries took {:?} vs {:?} for {} entries",
+        large.len(),
+        large_time,
+        small_time,
+        small.len()
+    );
+}
+
+#[test]
+fn many_versions_do_not_affect_other_cells() {
+    let db = db();
+    db.state_set("other", "untouched").unwrap();
+    write_versions(&db, "cell");
+
+    assert_eq!(db.state_read("other").unwrap(), Some(Value::String("untouched".into())));
+    assert_eq!(db.state_readv("other").unwrap().unwrap().len(), 1);
+}
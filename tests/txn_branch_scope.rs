@@ -0,0 +1,186 @@
+//! Black-box tests for the branch scope of Session transactions.
+//!
+//! A transaction begun with `TxnBegin { branch: Some(b) }` must commit its
+//! writes to `b` — visible there, invisible on the default branch. A command
+//! inside that transaction naming a *different* branch has no documented
+//! behaviour; it must either be rejected or land on the branch it names, and
+//! in neither case may it leak into the wrong branch or break the commit.
+
+use stratadb::{Command, Database, Output, Session, Strata, Value};
+use std::sync::Arc;
+
+const FEATURE: &str = "feature";
+const OTHER: &str = "other";
+
+/// A database with `feature` and `other` branches, plus a handle on default.
+fn setup() -> (Arc<Database>, Strata) {
+    let db = Database::cache().unwrap();
+    let strata = Strata::from_database(db.clone()).unwrap();
+    strata.create_branch(FEATURE).unwrap();
+    strata.create_branch(OTHER).unwrap();
+    (db, strata)
+}
+
+/// Read `key` on `branch` through a fresh handle, leaving `strata` untouched.
+fn get_on(strata: &Strata, branch: &str, key: &str) -> Option<Value> {
+    let mut handle = strata.new_handle().unwrap();
+    handle.set_branch(branch).unwrap();
+    handle.kv_get(key).unwrap()
+}
+
+fn begin_on(s: &mut Session, branch: &str) {
+    let output = s
+        .execute(Command::TxnBegin {
+            branch: Some(branch.into()),
+            options: None,
+        })
+        .unwrap();
+    assert!(matches!(output, Output::TxnBegun));
+}
+
+/// Issue a KvPut in the session, returning whether it was accepted.
+fn put(s: &mut Session, branch: Option<&str>, key: &str, value: i64) -> bool {
+    s.execute(Command::KvPut {
+        branch: branch.map(Into::into),
+        key: key.to_string(),
+        value: Value::Int(value),
+    })
+    .is_ok()
+}
+
+// =============================================================================
+// Writes land on the begin-branch
+// =============================================================================
+
+#[test]
+fn txn_on_branch_commits_to_that_branch() {
+    let (db, strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    assert!(put(&mut s, None, "k", 1));
+    s.execute(Command::TxnCommit).unwrap();
+
+    assert_eq!(get_on(&strata, FEATURE, "k"), Some(Value::Int(1)));
+    assert_eq!(strata.kv_get("k").unwrap(), None, "branch txn leaked into default");
+    assert_eq!(get_on(&strata, OTHER, "k"), None);
+}
+
+#[test]
+fn txn_on_branch_sees_own_writes_before_commit() {
+    let (db, _strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    assert!(put(&mut s, None, "k", 1));
+
+    let output = s
+        .execute(Command::KvGet {
+            branch: None,
+            key: "k".to_string(),
+        })
+        .unwrap();
+    assert!(matches!(output, Output::Maybe(Some(Value::Int(1)))));
+    s.execute(Command::TxnRollback).unwrap();
+}
+
+#[test]
+fn txn_on_branch_rollback_touches_no_branch() {
+    let (db, strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    assert!(put(&mut s, None, "k", 1));
+    s.execute(Command::TxnRollback).unwrap();
+
+    assert_eq!(get_on(&strata, FEATURE, "k"), None);
+    assert_eq!(strata.kv_get("k").unwrap(), None);
+}
+
+#[test]
+fn txn_on_branch_reads_that_branch() {
+    let (db, strata) = setup();
+    let mut feature = strata.new_handle().unwrap();
+    feature.set_branch(FEATURE).unwrap();
+    feature.kv_put("k", Value::Int(7)).unwrap();
+    strata.kv_put("k", Value::Int(0)).unwrap();
+
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    let output = s
+        .execute(Command::KvGet {
+            branch: None,
+            key: "k".to_string(),
+        })
+        .unwrap();
+    assert!(matches!(output, Output::Maybe(Some(Value::Int(7)))));
+    s.execute(Command::TxnCommit).unwrap();
+}
+
+#[test]
+fn explicit_same_branch_inside_txn_is_allowed() {
+    let (db, strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    assert!(put(&mut s, Some(FEATURE), "k", 1));
+    s.execute(Command::TxnCommit).unwrap();
+
+    assert_eq!(get_on(&strata, FEATURE, "k"), Some(Value::Int(1)));
+    assert_eq!(strata.kv_get("k").unwrap(), None);
+}
+
+// =============================================================================
+// Commands naming a different branch
+// =============================================================================
+
+#[test]
+fn different_branch_inside_txn_rejected_or_cross_branch() {
+    let (db, strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    assert!(put(&mut s, None, "same", 1));
+    let cross = put(&mut s, Some(OTHER), "cross", 2);
+    s.execute(Command::TxnCommit).unwrap();
+
+    // The begin-branch write always commits where it should
+    assert_eq!(get_on(&strata, FEATURE, "same"), Some(Value::Int(1)));
+    assert_eq!(strata.kv_get("cross").unwrap(), None, "cross-branch write hit default");
+
+    if cross {
+        // Cross-branch: the write lands on the branch it named, nowhere else
+        assert_eq!(get_on(&strata, OTHER, "cross"), Some(Value::Int(2)));
+    } else {
+        // Rejected: nothing was written anywhere
+        assert_eq!(get_on(&strata, OTHER, "cross"), None);
+    }
+    assert_eq!(get_on(&strata, FEATURE, "cross"), None);
+}
+
+#[test]
+fn cross_branch_command_leaves_txn_consistent() {
+    let (db, strata) = setup();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    if !put(&mut s, Some(OTHER), "cross", 1) {
+        // If the rejection left the transaction open, it must still commit normally
+        if s.in_transaction() {
+            assert!(put(&mut s, None, "after", 2));
+            s.execute(Command::TxnCommit).unwrap();
+            assert_eq!(get_on(&strata, FEATURE, "after"), Some(Value::Int(2)));
+        }
+    } else {
+        s.execute(Command::TxnRollback).unwrap();
+        assert_eq!(get_on(&strata, OTHER, "cross"), None, "rollback kept cross-branch write");
+    }
+}
+
+#[test]
+fn default_branch_named_inside_branch_txn() {
+    let (db, strata) = setup();
+    let default = strata.current_branch().to_string();
+    let mut s = Session::new(db);
+    begin_on(&mut s, FEATURE);
+    let accepted = put(&mut s, Some(&default), "k", 1);
+    s.execute(Command::TxnCommit).unwrap();
+
+    assert_eq!(get_on(&strata, FEATURE, "k"), None, "default-branch write landed on feature");
+    let expected = if accepted { Some(Value::Int(1)) } else { None };
+    assert_eq!(strata.kv_get("k").unwrap(), expected);
+}
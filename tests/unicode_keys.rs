@@ -0,0 +1,170 @@
+//! Black-box tests for Unicode key handling.
+//!
+//! Keys are compared byte-for-byte: the engine does not apply Unicode
+//! normalization, so canonically-equivalent strings in different
+//! normalization forms (NFC vs NFD) are distinct keys. These tests pin that
+//! contract so a normalization change can't silently merge or split keys.
+
+use stratadb::{Strata, Value};
+
+fn db() -> Strata {
+    Strata::open_temp().expect("failed to open temp db")
+}
+
+// "café" with a precomposed é (U+00E9)
+const CAFE_NFC: &str = "caf\u{00E9}";
+// "café" with e + combining acute accent (U+0065 U+0301)
+const CAFE_NFD: &str = "cafe\u{0301}";
+
+// =============================================================================
+// NFC vs NFD
+// =============================================================================
+
+#[test]
+fn nfc_and_nfd_forms_differ_in_bytes() {
+    assert_ne!(CAFE_NFC.as_bytes(), CAFE_NFD.as_bytes());
+}
+
+#[test]
+fn nfc_key_not_readable_via_nfd() {
+    let db = db();
+    db.kv_put(CAFE_NFC, "nfc").unwrap();
+
+    assert_eq!(db.kv_get(CAFE_NFC).unwrap(), Some(Value::String("nfc".into())));
+    assert_eq!(db.kv_get(CAFE_NFD).unwrap(), None);
+}
+
+#[test]
+fn nfd_key_not_readable_via_nfc() {
+    let db = db();
+    db.kv_put(CAFE_NFD, "nfd").unwrap();
+
+    assert_eq!(db.kv_get(CAFE_NFD).unwrap(), Some(Value::String("nfd".into())));
+    assert_eq!(db.kv_get(CAFE_NFC).unwrap(), None);
+}
+
+#[test]
+fn nfc_and_nfd_keys_coexist() {
+    let db = db();
+    db.kv_put(CAFE_NFC, "nfc").unwrap();
+    db.kv_put(CAFE_NFD, "nfd").unwrap();
+
+    assert_eq!(db.kv_get(CAFE_NFC).unwrap(), Some(Value::String("nfc".into())));
+    assert_eq!(db.kv_get(CAFE_NFD).unwrap(), Some(Value::String("nfd".into())));
+
+    let keys = db.kv_list(Some("caf")).unwrap();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.iter().any(|k| k == CAFE_NFC));
+    assert!(keys.iter().any(|k| k == CAFE_NFD));
+}
+
+#[test]
+fn deleting_nfc_key_leaves_nfd_key() {
+    let db = db();
+    db.kv_put(CAFE_NFC, "nfc").unwrap();
+    db.kv_put(CAFE_NFD, "nfd").unwrap();
+
+    assert!(db.kv_delete(CAFE_NFC).unwrap());
+    assert_eq!(db.kv_get(CAFE_NFC).unwrap(), None);
+    assert_eq!(db.kv_get(CAFE_NFD).unwrap(), Some(Value::String("nfd".into())));
+}
+
+#[test]
+fn hangul_syllable_vs_jamo_are_distinct() {
+    let db = db();
+    // "한" precomposed (U+D55C) vs conjoining jamo (U+1112 U+1161 U+11AB)
+    let composed = "\u{D55C}";
+    let decomposed = "\u{1112}\u{1161}\u{11AB}";
+
+    db.kv_put(composed, 1i64).unwrap();
+    assert_eq!(db.kv_get(composed).unwrap(), Some(Value::Int(1)));
+    assert_eq!(db.kv_get(decomposed).unwrap(), None);
+}
+
+// =============================================================================
+// Emoji, combining marks, and zero-width joiners
+// =============================================================================
+
+#[test]
+fn emoji_with_skin_tone_modifier_is_distinct_from_base() {
+    let db = db();
+    let base = "\u{1F44D}"; // 👍
+    let toned = "\u{1F44D}\u{1F3FD}"; // 👍🏽
+
+    db.kv_put(base, "base").unwrap();
+    db.kv_put(toned, "toned").unwrap();
+
+    assert_eq!(db.kv_get(base).unwrap(), Some(Value::String("base".into())));
+    assert_eq!(db.kv_get(toned).unwrap(), Some(Value::String("toned".into())));
+}
+
+#[test]
+fn emoji_with_combining_mark_round_trips() {
+    let db = db();
+    // Keycap sequence: '1' + VS16 + combining enclosing keycap
+    let keycap = "1\u{FE0F}\u{20E3}";
+
+    db.kv_put(keycap, "keycap").unwrap();
+    assert_eq!(db.kv_get(keycap).unwrap(), Some(Value::String("keycap".into())));
+    assert_eq!(db.kv_get("1").unwrap(), None);
+    assert_eq!(db.kv_get("1\u{FE0F}").unwrap(), None);
+}
+
+#[test]
+fn zwj_sequence_is_distinct_from_its_components() {
+    let db = db();
+    // Family: man + ZWJ + woman + ZWJ + girl
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    // Same code points without the joiners
+    let unjoined = "\u{1F468}\u{1F469}\u{1F467}";
+
+    db.kv_put(family, "family").unwrap();
+    assert_eq!(db.kv_get(family).unwrap(), Some(Value::String("family".into())));
+    assert_eq!(db.kv_get(unjoined).unwrap(), None);
+    assert_eq!(db.kv_get("\u{1F468}").unwrap(), None);
+}
+
+#[test]
+fn zero_width_joiner_alone_is_a_valid_key() {
+    let db = db();
+    db.kv_put("a\u{200D}b", "joined").unwrap();
+
+    assert_eq!(db.kv_get("a\u{200D}b").unwrap(), Some(Value::String("joined".into())));
+    assert_eq!(db.kv_get("ab").unwrap(), None);
+}
+
+#[test]
+fn zwj_key_prefix_listing() {
+    let db = db();
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let couple = "\u{1F468}\u{200D}\u{1F469}";
+
+    db.kv_put(&format!("emoji:{}", family), 1i64).unwrap();
+    db.kv_put(&format!("emoji:{}", couple), 2i64).unwrap();
+
+    let keys = db.kv_list(Some(&format!("emoji:{}", couple))).unwrap();
+    // Byte-prefix semantics: the family key starts with the couple sequence
+    assert_eq!(keys.len(), 2);
+}
+
+// =============================================================================
+// Other primitives
+// =============================================================================
+
+#[test]
+fn state_cells_are_byte_exact() {
+    let db = db();
+    db.state_set(CAFE_NFC, 1i64).unwrap();
+
+    assert_eq!(db.state_read(CAFE_NFC).unwrap(), Some(Value::Int(1)));
+    assert_eq!(db.state_read(CAFE_NFD).unwrap(), None);
+}
+
+#[test]
+fn json_keys_are_byte_exact() {
+    let db = db();
+    db.json_set(CAFE_NFC, "$", Value::Int(1)).unwrap();
+
+    assert_eq!(db.json_get(CAFE_NFC, "$").unwrap(), Some(Value::Int(1)));
+    assert_eq!(db.json_get(CAFE_NFD, "$").unwrap(), None);
+}
@@ -0,0 +1,163 @@
+//! Black-box tests for vector search equivalence across durability modes.
+//!
+//! Durability only changes when data reaches disk, never what a query
+//! returns. The same collection, vectors, and query must produce identical
+//! result keys in identical order under cache, standard, and always modes.
+
+use stratadb::{Database, DistanceMetric, Strata, Value};
+use tempfile::TempDir;
+
+const DIM: u64 = 16;
+const VECTORS: u64 = 500;
+const K: u64 = 20;
+
+/// Score differences below this are treated as equal across modes.
+const SCORE_EPSILON: f32 = 1e-5;
+
+/// Write a strata.toml with always durability before opening.
+fn write_always_config(path: &std::path::Path) {
+    std::fs::create_dir_all(path).expect("create dir for config");
+    std::fs::write(path.join("strata.toml"), "durability = \"always\"\n")
+        .expect("write always config");
+}
+
+/// Open a database in each mode. The temp dir is returned so it outlives the db.
+fn open_modes() -> Vec<(&'static str, Strata, Option<TempDir>)> {
+    let cache = Strata::from_database(Database::cache().unwrap()).unwrap();
+
+    let standard_dir = tempfile::tempdir().unwrap();
+    let standard = Strata::from_database(Database::open(standard_dir.path()).unwrap()).unwrap();
+
+    let always_dir = tempfile::tempdir().unwrap();
+    write_always_config(always_dir.path());
+    let always = Strata::from_database(Database::open(always_dir.path()).unwrap()).unwrap();
+
+    vec![
+        ("cache", cache, None),
+        ("standard", standard, Some(standard_dir)),
+        ("always", always, Some(always_dir)),
+    ]
+}
+
+/// Deterministic pseudo-random vector, so no two vectors tie on distance.
+fn vector(seed: u64) -> Vec<f32> {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (0..DIM)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) * 2.0 - 1.0
+        })
+        .collect()
+}
+
+fn populate(db: &Strata, collection: &str, metric: DistanceMetric) {
+    db.vector_create_collection(collection, DIM, metric).unwrap();
+    for i in 0..VECTORS {
+        let meta = Value::Object([("i".to_string(), Value::Int(i as i64))].into_iter().collect());
+        db.vector_upsert(collection, &format!("v{}", i), vector(i), Some(meta)).unwrap();
+    }
+}
+
+/// Run `query` in every mode and assert the (key, score) lists all match the
+/// cache-mode result.
+fn assert_same_results(
+    modes: &[(&'static str, Strata, Option<TempDir>)],
+    collection: &str,
+    query: Vec<f32>,
+) {
+    let results: Vec<(&str, Vec<(String, f32)>)> = modes
+        .iter()
+        .map(|(name, db, _)| {
+            let hits = db.vector_search(collection, query.clone(), K).unwrap();
+            (*name, hits.into_iter().map(|r| (r.key, r.score)).collect())
+        })
+        .collect();
+
+    let (base_name, base) = &results[0];
+    assert_eq!(base.len() as u64, K, "mode={}: expected {} results", base_name, K);
+    for (name, hits) in &results[1..] {
+        let base_keys: Vec<&String> = base.iter().map(|(k, _)| k).collect();
+        let keys: Vec<&String> = hits.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, base_keys, "mode={} ordering differs from {}", name, base_name);
+        for ((key, score), (_, base_score)) in hits.iter().zip(base) {
+            assert!(
+                (score - base_score).abs() < SCORE_EPSILON,
+                "mode={}: score for {} is {} but {} gave {}",
+                name,
+                key,
+                score,
+                base_name,
+                base_score
+            );
+        }
+    }
+}
+
+// =============================================================================
+// Same data, same query, every metric
+// =============================================================================
+
+#[test]
+fn cosine_search_matches_across_modes() {
+    let modes = open_modes();
+    for (_, db, _) in &modes {
+        populate(db, "col", DistanceMetric::Cosine);
+    }
+    for q in 0..10 {
+        assert_same_results(&modes, "col", vector(VECTORS + q));
+    }
+}
+
+#[test]
+fn euclidean_search_matches_across_modes() {
+    let modes = open_modes();
+    for (_, db, _) in &modes {
+        populate(db, "col", DistanceMetric::Euclidean);
+    }
+    for q in 0..10 {
+        assert_same_results(&modes, "col", vector(VECTORS + q));
+    }
+}
+
+#[test]
+fn dot_product_search_matches_across_modes() {
+    let modes = open_modes();
+    for (_, db, _) in &modes {
+        populate(db, "col", DistanceMetric::DotProduct);
+    }
+    for q in 0..10 {
+        assert_same_results(&modes, "col", vector(VECTORS + q));
+    }
+}
+
+// =============================================================================
+// Mutations applied identically
+// =============================================================================
+
+#[test]
+fn search_after_overwrites_and_deletes_matches_across_modes() {
+    let modes = open_modes();
+    for (_, db, _) in &modes {
+        populate(db, "col", DistanceMetric::Cosine);
+        // Move every 5th vector and delete every 7th
+        for i in (0..VECTORS).step_by(5) {
+            db.vector_upsert("col", &format!("v{}", i), vector(i + 10_000), None).unwrap();
+        }
+        for i in (0..VECTORS).step_by(7) {
+            db.vector_delete("col", &format!("v{}", i)).unwrap();
+        }
+    }
+    for q in 0..10 {
+        assert_same_results(&modes, "col", vector(VECTORS + q));
+    }
+}
+
+#[test]
+fn exact_match_is_top_hit_in_every_mode() {
+    let modes = open_modes();
+    for (name, db, _) in &modes {
+        populate(db, "col", DistanceMetric::Cosine);
+        let hits = db.vector_search("col", vector(42), 1).unwrap();
+        assert_eq!(hits[0].key, "v42", "mode={}: exact match not ranked first", name);
+    }
+}